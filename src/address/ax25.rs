@@ -0,0 +1,405 @@
+use crate::{AsSocketAddress, SpecificSocketAddress};
+use std::os::raw::c_int;
+
+/// The maximum number of digipeaters in an AX.25 socket address.
+const AX25_MAX_DIGIS: usize = 8;
+
+/// The maximum number of digipeaters in a ROSE socket address.
+const ROSE_MAX_DIGIS: usize = 6;
+
+/// An encoded AX.25 callsign with SSID.
+///
+/// The callsign consists of up to 6 alphanumeric characters and a secondary station identifier (SSID) from 0 to 15.
+/// In text form it is written as `CALL-SSID`, where the SSID may be omitted if it is 0.
+///
+/// The callsign is stored in the encoding used on the wire and by the kernel:
+/// each character is shifted left by one bit and padded with spaces to 6 characters,
+/// followed by a byte holding the SSID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Ax25Callsign {
+	/// The encoded callsign (`ax25_address`).
+	call: [u8; 7],
+}
+
+impl Ax25Callsign {
+	/// The null callsign, used as wildcard when binding sockets.
+	pub const NULL: Self = Self { call: [0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x00] };
+
+	/// Create an encoded callsign from a callsign without SSID and a separate SSID.
+	///
+	/// Returns an error if the callsign is empty, longer than 6 characters,
+	/// contains characters other than ASCII letters and digits, or if the SSID is larger than 15.
+	pub fn new(callsign: &str, ssid: u8) -> Result<Self, Ax25ParseError> {
+		if callsign.is_empty() || callsign.len() > 6 || ssid > 15 {
+			return Err(Ax25ParseError);
+		}
+		if !callsign.bytes().all(|c| c.is_ascii_alphanumeric()) {
+			return Err(Ax25ParseError);
+		}
+
+		let mut call = [b' ' << 1; 7];
+		for (dest, c) in call.iter_mut().zip(callsign.bytes()) {
+			*dest = c.to_ascii_uppercase() << 1;
+		}
+		call[6] = (ssid << 1) & 0x1E;
+		Ok(Self { call })
+	}
+
+	/// Create a callsign from the raw encoded bytes.
+	pub fn from_raw(call: [u8; 7]) -> Self {
+		Self { call }
+	}
+
+	/// Get the raw encoded bytes of the callsign.
+	pub fn into_raw(self) -> [u8; 7] {
+		self.call
+	}
+
+	/// Get the callsign without the SSID.
+	pub fn callsign(&self) -> String {
+		self.call[..6].iter()
+			.map(|&c| ((c >> 1) & 0x7F) as char)
+			.filter(|&c| c != ' ')
+			.collect()
+	}
+
+	/// Get the secondary station identifier.
+	pub fn ssid(&self) -> u8 {
+		(self.call[6] >> 1) & 0x0F
+	}
+}
+
+impl std::fmt::Display for Ax25Callsign {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str(&self.callsign())?;
+		if self.ssid() != 0 {
+			write!(f, "-{}", self.ssid())?;
+		}
+		Ok(())
+	}
+}
+
+impl std::str::FromStr for Ax25Callsign {
+	type Err = Ax25ParseError;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let mut parts = input.splitn(2, '-');
+		let callsign = parts.next().unwrap_or("");
+		let ssid = match parts.next() {
+			None => 0,
+			Some(ssid) if !ssid.is_empty() && ssid.len() <= 2 && ssid.bytes().all(|c| c.is_ascii_digit()) => {
+				ssid.parse().map_err(|_| Ax25ParseError)?
+			},
+			Some(_) => return Err(Ax25ParseError),
+		};
+		Self::new(callsign, ssid)
+	}
+}
+
+/// A ROSE address, consisting of 10 decimal digits.
+///
+/// The first four digits are the DNIC (network identifier), the last six digits identify the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(C)]
+pub struct RoseAddress {
+	/// The BCD encoded address (`rose_address`).
+	addr: [u8; 5],
+}
+
+impl RoseAddress {
+	/// Create a ROSE address from 10 decimal digits.
+	///
+	/// Returns an error if any of the digits is larger than 9.
+	pub fn new(digits: [u8; 10]) -> Result<Self, Ax25ParseError> {
+		if digits.iter().any(|&d| d > 9) {
+			return Err(Ax25ParseError);
+		}
+		let mut addr = [0; 5];
+		for (dest, pair) in addr.iter_mut().zip(digits.chunks(2)) {
+			*dest = (pair[0] << 4) | pair[1];
+		}
+		Ok(Self { addr })
+	}
+
+	/// Get the 10 decimal digits of the address.
+	pub fn digits(&self) -> [u8; 10] {
+		let mut digits = [0; 10];
+		for (pair, &byte) in digits.chunks_mut(2).zip(self.addr.iter()) {
+			pair[0] = byte >> 4;
+			pair[1] = byte & 0x0F;
+		}
+		digits
+	}
+}
+
+impl std::fmt::Display for RoseAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		for digit in self.digits().iter() {
+			write!(f, "{}", digit)?;
+		}
+		Ok(())
+	}
+}
+
+impl std::str::FromStr for RoseAddress {
+	type Err = Ax25ParseError;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		if input.len() != 10 || !input.bytes().all(|c| c.is_ascii_digit()) {
+			return Err(Ax25ParseError);
+		}
+		let mut digits = [0; 10];
+		for (dest, c) in digits.iter_mut().zip(input.bytes()) {
+			*dest = c - b'0';
+		}
+		Self::new(digits)
+	}
+}
+
+/// Error returned when parsing or creating an AX.25 callsign or ROSE address fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ax25ParseError;
+
+impl std::fmt::Display for Ax25ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str("invalid AX.25 callsign or ROSE address")
+	}
+}
+
+impl std::error::Error for Ax25ParseError {}
+
+/// The C-compatible AX.25 socket address with digipeaters (`struct full_sockaddr_ax25`).
+///
+/// The first part of the struct is the plain `struct sockaddr_ax25`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct full_sockaddr_ax25 {
+	sax25_family: libc::sa_family_t,
+	sax25_call: Ax25Callsign,
+	sax25_ndigis: c_int,
+	fsa_digipeater: [Ax25Callsign; AX25_MAX_DIGIS],
+}
+
+/// The size of the plain `struct sockaddr_ax25`, without the digipeaters.
+const SOCKADDR_AX25_LEN: usize = 16;
+
+/// AX.25 socket address.
+///
+/// This includes a callsign and optionally a list of digipeaters.
+///
+/// AX.25 socket addresses are a non-portable Linux extension.
+#[derive(Clone)]
+#[repr(C)]
+pub struct Ax25SocketAddress {
+	/// The inner C-compatible socket address.
+	inner: full_sockaddr_ax25,
+
+	/// The length of the socket address.
+	len: libc::socklen_t,
+}
+
+impl Ax25SocketAddress {
+	/// Create an AX.25 socket address from a callsign, without digipeaters.
+	pub fn new(callsign: Ax25Callsign) -> Self {
+		let inner = full_sockaddr_ax25 {
+			sax25_family: Self::static_family(),
+			sax25_call: callsign,
+			sax25_ndigis: 0,
+			fsa_digipeater: [Ax25Callsign::NULL; AX25_MAX_DIGIS],
+		};
+		Self { inner, len: SOCKADDR_AX25_LEN as libc::socklen_t }
+	}
+
+	/// Create an AX.25 socket address from a callsign and a list of digipeaters.
+	///
+	/// Returns an error if there are more than 8 digipeaters.
+	pub fn with_digipeaters(callsign: Ax25Callsign, digipeaters: &[Ax25Callsign]) -> std::io::Result<Self> {
+		if digipeaters.len() > AX25_MAX_DIGIS {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "too many digipeaters for an AX.25 socket address"));
+		}
+		let mut output = Self::new(callsign);
+		output.inner.fsa_digipeater[..digipeaters.len()].copy_from_slice(digipeaters);
+		output.inner.sax25_ndigis = digipeaters.len() as c_int;
+		output.len = Self::max_len();
+		Ok(output)
+	}
+
+	/// Get the callsign associated with the socket address.
+	pub fn callsign(&self) -> Ax25Callsign {
+		self.inner.sax25_call
+	}
+
+	/// Set the callsign associated with the socket address.
+	pub fn set_callsign(&mut self, callsign: Ax25Callsign) {
+		self.inner.sax25_call = callsign;
+	}
+
+	/// Get the digipeaters associated with the socket address.
+	pub fn digipeaters(&self) -> &[Ax25Callsign] {
+		if self.len < Self::max_len() {
+			return &[];
+		}
+		let count = (self.inner.sax25_ndigis.max(0) as usize).min(AX25_MAX_DIGIS);
+		&self.inner.fsa_digipeater[..count]
+	}
+}
+
+/// The C-compatible ROSE socket address with digipeaters (`struct full_sockaddr_rose`).
+///
+/// The first part of the struct is compatible with the plain `struct sockaddr_rose`,
+/// which has room for a single digipeater.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct full_sockaddr_rose {
+	srose_family: libc::sa_family_t,
+	srose_addr: RoseAddress,
+	srose_call: Ax25Callsign,
+	srose_ndigis: c_int,
+	srose_digis: [Ax25Callsign; ROSE_MAX_DIGIS],
+}
+
+/// The size of the plain `struct sockaddr_rose`, with room for a single digipeater.
+const SOCKADDR_ROSE_LEN: usize = 28;
+
+/// ROSE socket address.
+///
+/// This includes a ROSE address, a callsign and optionally a list of digipeaters.
+///
+/// ROSE socket addresses are a non-portable Linux extension.
+#[derive(Clone)]
+#[repr(C)]
+pub struct RoseSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: full_sockaddr_rose,
+
+	/// The length of the socket address.
+	len: libc::socklen_t,
+}
+
+impl RoseSocketAddress {
+	/// Create a ROSE socket address from a ROSE address and a callsign, without digipeaters.
+	pub fn new(address: RoseAddress, callsign: Ax25Callsign) -> Self {
+		let inner = full_sockaddr_rose {
+			srose_family: Self::static_family(),
+			srose_addr: address,
+			srose_call: callsign,
+			srose_ndigis: 0,
+			srose_digis: [Ax25Callsign::NULL; ROSE_MAX_DIGIS],
+		};
+		Self { inner, len: SOCKADDR_ROSE_LEN as libc::socklen_t }
+	}
+
+	/// Create a ROSE socket address from a ROSE address, a callsign and a list of digipeaters.
+	///
+	/// Returns an error if there are more than 6 digipeaters.
+	pub fn with_digipeaters(address: RoseAddress, callsign: Ax25Callsign, digipeaters: &[Ax25Callsign]) -> std::io::Result<Self> {
+		if digipeaters.len() > ROSE_MAX_DIGIS {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "too many digipeaters for a ROSE socket address"));
+		}
+		let mut output = Self::new(address, callsign);
+		output.inner.srose_digis[..digipeaters.len()].copy_from_slice(digipeaters);
+		output.inner.srose_ndigis = digipeaters.len() as c_int;
+		if digipeaters.len() > 1 {
+			output.len = Self::max_len();
+		}
+		Ok(output)
+	}
+
+	/// Get the ROSE address associated with the socket address.
+	pub fn address(&self) -> RoseAddress {
+		self.inner.srose_addr
+	}
+
+	/// Set the ROSE address associated with the socket address.
+	pub fn set_address(&mut self, address: RoseAddress) {
+		self.inner.srose_addr = address;
+	}
+
+	/// Get the callsign associated with the socket address.
+	pub fn callsign(&self) -> Ax25Callsign {
+		self.inner.srose_call
+	}
+
+	/// Set the callsign associated with the socket address.
+	pub fn set_callsign(&mut self, callsign: Ax25Callsign) {
+		self.inner.srose_call = callsign;
+	}
+
+	/// Get the digipeaters associated with the socket address.
+	pub fn digipeaters(&self) -> &[Ax25Callsign] {
+		let max = if self.len < Self::max_len() { 1 } else { ROSE_MAX_DIGIS };
+		let count = (self.inner.srose_ndigis.max(0) as usize).min(max);
+		&self.inner.srose_digis[..count]
+	}
+}
+
+macro_rules! impl_ax25_address {
+	($type:ty, $raw:ty, $family:expr, $family_name:literal, $min_len:expr) => {
+		impl SpecificSocketAddress for $type {
+			fn static_family() -> libc::sa_family_t {
+				$family as libc::sa_family_t
+			}
+		}
+
+		unsafe impl AsSocketAddress for $type {
+			fn as_sockaddr(&self) -> *const libc::sockaddr {
+				&self.inner as *const _ as *const _
+			}
+
+			fn as_sockaddr_mut(address: &mut std::mem::MaybeUninit<Self>) -> *mut libc::sockaddr {
+				unsafe { &mut address.as_mut_ptr().as_mut().unwrap().inner as *mut _ as *mut _ }
+			}
+
+			fn len(&self) -> libc::socklen_t {
+				self.len
+			}
+
+			fn finalize(address: std::mem::MaybeUninit<Self>, len: libc::socklen_t) -> std::io::Result<Self> {
+				unsafe {
+					let mut address = address.assume_init();
+					if address.family() != Self::static_family() {
+						return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, concat!("wrong address family, expected ", $family_name)));
+					}
+					if len < $min_len as libc::socklen_t {
+						return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "address too small"));
+					}
+					if len > Self::max_len() {
+						return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "address too large"));
+					}
+					address.len = len;
+					Ok(address)
+				}
+			}
+
+			fn max_len() -> libc::socklen_t {
+				std::mem::size_of::<$raw>() as libc::socklen_t
+			}
+		}
+
+		impl From<$type> for crate::SocketAddress {
+			fn from(other: $type) -> Self {
+				Self::from(&other)
+			}
+		}
+
+		impl From<&$type> for crate::SocketAddress {
+			fn from(other: &$type) -> Self {
+				Self::from_other(other)
+			}
+		}
+	};
+}
+
+impl_ax25_address!(Ax25SocketAddress, full_sockaddr_ax25, libc::AF_AX25, "AF_AX25", SOCKADDR_AX25_LEN);
+impl_ax25_address!(RoseSocketAddress, full_sockaddr_rose, libc::AF_ROSE, "AF_ROSE", SOCKADDR_ROSE_LEN);
+
+impl_eq_and_hash!(Ax25SocketAddress, |address| -> (Ax25Callsign, &[Ax25Callsign]) {
+	(address.callsign(), address.digipeaters())
+});
+
+impl_eq_and_hash!(RoseSocketAddress, |address| -> (RoseAddress, Ax25Callsign, &[Ax25Callsign]) {
+	(address.address(), address.callsign(), address.digipeaters())
+});
@@ -0,0 +1,365 @@
+use crate::SpecificSocketAddress;
+use std::os::raw::c_int;
+
+/// Bluetooth device address.
+///
+/// The address is stored in the usual human readable order,
+/// so `BdAddr::new([0x00, 0x11, 0x22, 0x33, 0x44, 0x55])` is formatted as `00:11:22:33:44:55`.
+/// Note that the kernel stores device addresses in the reverse order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct BdAddr([u8; 6]);
+
+impl BdAddr {
+	/// The wildcard device address `00:00:00:00:00:00`.
+	pub const ANY: Self = Self([0, 0, 0, 0, 0, 0]);
+
+	/// The local device address `00:00:00:FF:FF:FF`.
+	pub const LOCAL: Self = Self([0, 0, 0, 0xFF, 0xFF, 0xFF]);
+
+	/// Create a device address from bytes in human readable order.
+	pub fn new(bytes: [u8; 6]) -> Self {
+		Self(bytes)
+	}
+
+	/// Get the bytes of the device address in human readable order.
+	pub fn bytes(&self) -> [u8; 6] {
+		self.0
+	}
+
+	/// Create a device address from bytes in the order used by the kernel.
+	fn from_kernel(mut bytes: [u8; 6]) -> Self {
+		bytes.reverse();
+		Self(bytes)
+	}
+
+	/// Get the bytes of the device address in the order used by the kernel.
+	fn to_kernel(self) -> [u8; 6] {
+		let mut bytes = self.0;
+		bytes.reverse();
+		bytes
+	}
+}
+
+impl std::fmt::Display for BdAddr {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let [a, b, c, d, e, g] = self.0;
+		write!(f, "{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}", a, b, c, d, e, g)
+	}
+}
+
+impl std::str::FromStr for BdAddr {
+	type Err = BdAddrParseError;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		let mut bytes = [0u8; 6];
+		let mut parts = input.split(':');
+		for byte in bytes.iter_mut() {
+			let part = parts.next().ok_or(BdAddrParseError)?;
+			if part.len() != 2 {
+				return Err(BdAddrParseError);
+			}
+			*byte = u8::from_str_radix(part, 16).map_err(|_| BdAddrParseError)?;
+		}
+		if parts.next().is_some() {
+			return Err(BdAddrParseError);
+		}
+		Ok(Self(bytes))
+	}
+}
+
+/// Error returned when parsing a [`BdAddr`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BdAddrParseError;
+
+impl std::fmt::Display for BdAddrParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str("invalid Bluetooth device address syntax")
+	}
+}
+
+impl std::error::Error for BdAddrParseError {}
+
+/// The C-compatible L2CAP socket address (`struct sockaddr_l2`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+struct sockaddr_l2 {
+	l2_family: libc::sa_family_t,
+	l2_psm: u16,
+	l2_bdaddr: [u8; 6],
+	l2_cid: u16,
+	l2_bdaddr_type: u8,
+}
+
+/// The C-compatible RFCOMM socket address (`struct sockaddr_rc`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+struct sockaddr_rc {
+	rc_family: libc::sa_family_t,
+	rc_bdaddr: [u8; 6],
+	rc_channel: u8,
+}
+
+/// The C-compatible HCI socket address (`struct sockaddr_hci`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+struct sockaddr_hci {
+	hci_family: libc::sa_family_t,
+	hci_dev: u16,
+	hci_channel: u16,
+}
+
+/// Bluetooth L2CAP socket address.
+///
+/// This includes a device address, a protocol/service multiplexer (PSM),
+/// a channel identifier (CID) and the type of the device address.
+///
+/// Bluetooth socket addresses are a non-portable Linux extension.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct L2capSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: sockaddr_l2,
+}
+
+impl L2capSocketAddress {
+	/// The protocol number for L2CAP sockets (`BTPROTO_L2CAP`).
+	pub const PROTOCOL: c_int = 0;
+
+	/// Address type for BR/EDR (classic) devices (`BDADDR_BREDR`).
+	pub const ADDRESS_TYPE_BREDR: u8 = 0;
+
+	/// Address type for LE devices with a public address (`BDADDR_LE_PUBLIC`).
+	pub const ADDRESS_TYPE_LE_PUBLIC: u8 = 1;
+
+	/// Address type for LE devices with a random address (`BDADDR_LE_RANDOM`).
+	pub const ADDRESS_TYPE_LE_RANDOM: u8 = 2;
+
+	/// Create an L2CAP socket address for a BR/EDR device from a device address and a PSM.
+	pub fn new(address: BdAddr, psm: u16) -> Self {
+		let inner = sockaddr_l2 {
+			l2_family: Self::static_family(),
+			l2_psm: psm.to_le(),
+			l2_bdaddr: address.to_kernel(),
+			l2_cid: 0,
+			l2_bdaddr_type: Self::ADDRESS_TYPE_BREDR,
+		};
+		Self { inner }
+	}
+
+	/// Create an L2CAP socket address for an LE device from a device address, a CID and an address type.
+	pub fn new_le(address: BdAddr, cid: u16, address_type: u8) -> Self {
+		let mut output = Self::new(address, 0);
+		output.set_cid(cid);
+		output.set_address_type(address_type);
+		output
+	}
+
+	/// Get the device address associated with the socket address.
+	pub fn address(&self) -> BdAddr {
+		BdAddr::from_kernel(self.inner.l2_bdaddr)
+	}
+
+	/// Set the device address associated with the socket address.
+	pub fn set_address(&mut self, address: BdAddr) {
+		self.inner.l2_bdaddr = address.to_kernel();
+	}
+
+	/// Get the PSM associated with the socket address.
+	pub fn psm(&self) -> u16 {
+		u16::from_le(self.inner.l2_psm)
+	}
+
+	/// Set the PSM associated with the socket address.
+	pub fn set_psm(&mut self, psm: u16) {
+		self.inner.l2_psm = psm.to_le();
+	}
+
+	/// Get the CID associated with the socket address.
+	pub fn cid(&self) -> u16 {
+		u16::from_le(self.inner.l2_cid)
+	}
+
+	/// Set the CID associated with the socket address.
+	pub fn set_cid(&mut self, cid: u16) {
+		self.inner.l2_cid = cid.to_le();
+	}
+
+	/// Get the type of the device address associated with the socket address.
+	pub fn address_type(&self) -> u8 {
+		self.inner.l2_bdaddr_type
+	}
+
+	/// Set the type of the device address associated with the socket address.
+	pub fn set_address_type(&mut self, address_type: u8) {
+		self.inner.l2_bdaddr_type = address_type;
+	}
+}
+
+/// Bluetooth RFCOMM socket address.
+///
+/// This includes a device address and a channel number.
+///
+/// Bluetooth socket addresses are a non-portable Linux extension.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct RfcommSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: sockaddr_rc,
+}
+
+impl RfcommSocketAddress {
+	/// The protocol number for RFCOMM sockets (`BTPROTO_RFCOMM`).
+	pub const PROTOCOL: c_int = 3;
+
+	/// Create an RFCOMM socket address from a device address and a channel number.
+	pub fn new(address: BdAddr, channel: u8) -> Self {
+		let inner = sockaddr_rc {
+			rc_family: Self::static_family(),
+			rc_bdaddr: address.to_kernel(),
+			rc_channel: channel,
+		};
+		Self { inner }
+	}
+
+	/// Get the device address associated with the socket address.
+	pub fn address(&self) -> BdAddr {
+		BdAddr::from_kernel(self.inner.rc_bdaddr)
+	}
+
+	/// Set the device address associated with the socket address.
+	pub fn set_address(&mut self, address: BdAddr) {
+		self.inner.rc_bdaddr = address.to_kernel();
+	}
+
+	/// Get the channel number associated with the socket address.
+	pub fn channel(&self) -> u8 {
+		self.inner.rc_channel
+	}
+
+	/// Set the channel number associated with the socket address.
+	pub fn set_channel(&mut self, channel: u8) {
+		self.inner.rc_channel = channel;
+	}
+}
+
+/// Bluetooth HCI socket address.
+///
+/// This includes a device index and an HCI channel.
+///
+/// Bluetooth socket addresses are a non-portable Linux extension.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct HciSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: sockaddr_hci,
+}
+
+impl HciSocketAddress {
+	/// The protocol number for HCI sockets (`BTPROTO_HCI`).
+	pub const PROTOCOL: c_int = 1;
+
+	/// The device index that refers to no device (`HCI_DEV_NONE`).
+	pub const DEVICE_NONE: u16 = 0xFFFF;
+
+	/// The raw HCI channel (`HCI_CHANNEL_RAW`).
+	pub const CHANNEL_RAW: u16 = 0;
+
+	/// The user channel, for exclusive access to a device (`HCI_CHANNEL_USER`).
+	pub const CHANNEL_USER: u16 = 1;
+
+	/// The monitor channel (`HCI_CHANNEL_MONITOR`).
+	pub const CHANNEL_MONITOR: u16 = 2;
+
+	/// The management control channel (`HCI_CHANNEL_CONTROL`).
+	pub const CHANNEL_CONTROL: u16 = 3;
+
+	/// Create an HCI socket address from a device index and a channel.
+	pub fn new(device: u16, channel: u16) -> Self {
+		let inner = sockaddr_hci {
+			hci_family: Self::static_family(),
+			hci_dev: device,
+			hci_channel: channel,
+		};
+		Self { inner }
+	}
+
+	/// Get the device index associated with the socket address.
+	pub fn device(&self) -> u16 {
+		self.inner.hci_dev
+	}
+
+	/// Set the device index associated with the socket address.
+	pub fn set_device(&mut self, device: u16) {
+		self.inner.hci_dev = device;
+	}
+
+	/// Get the channel associated with the socket address.
+	pub fn channel(&self) -> u16 {
+		self.inner.hci_channel
+	}
+
+	/// Set the channel associated with the socket address.
+	pub fn set_channel(&mut self, channel: u16) {
+		self.inner.hci_channel = channel;
+	}
+}
+
+macro_rules! impl_bluetooth_address {
+	($type:ty, $raw:ty) => {
+		impl SpecificSocketAddress for $type {
+			fn static_family() -> libc::sa_family_t {
+				libc::AF_BLUETOOTH as libc::sa_family_t
+			}
+		}
+
+		unsafe impl crate::AsSocketAddress for $type {
+			fn as_sockaddr(&self) -> *const libc::sockaddr {
+				&self.inner as *const _ as *const _
+			}
+
+			fn as_sockaddr_mut(address: &mut std::mem::MaybeUninit<Self>) -> *mut libc::sockaddr {
+				unsafe { &mut address.as_mut_ptr().as_mut().unwrap().inner as *mut _ as *mut _ }
+			}
+
+			fn len(&self) -> libc::socklen_t {
+				Self::max_len()
+			}
+
+			fn finalize(address: std::mem::MaybeUninit<Self>, len: libc::socklen_t) -> std::io::Result<Self> {
+				unsafe {
+					let address = address.assume_init();
+					if address.family() != Self::static_family() {
+						return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address family, expected AF_BLUETOOTH"));
+					}
+					if len != Self::max_len() {
+						return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address size"));
+					}
+					Ok(address)
+				}
+			}
+
+			fn max_len() -> libc::socklen_t {
+				std::mem::size_of::<$raw>() as libc::socklen_t
+			}
+		}
+
+		impl From<$type> for crate::SocketAddress {
+			fn from(other: $type) -> Self {
+				Self::from(&other)
+			}
+		}
+
+		impl From<&$type> for crate::SocketAddress {
+			fn from(other: &$type) -> Self {
+				Self::from_other(other)
+			}
+		}
+	};
+}
+
+impl_bluetooth_address!(L2capSocketAddress, sockaddr_l2);
+impl_bluetooth_address!(RfcommSocketAddress, sockaddr_rc);
+impl_bluetooth_address!(HciSocketAddress, sockaddr_hci);
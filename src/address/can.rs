@@ -0,0 +1,147 @@
+use crate::SpecificSocketAddress;
+use std::os::raw::c_int;
+
+/// CAN socket address.
+///
+/// This includes the index of the network interface,
+/// and for ISO-TP sockets the receive and transmit CAN identifiers.
+/// An interface index of 0 refers to all CAN interfaces.
+///
+/// CAN socket addresses are a non-portable Linux extension.
+#[derive(Clone)]
+#[repr(C)]
+pub struct CanSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: libc::sockaddr_can,
+}
+
+impl CanSocketAddress {
+	/// Create a CAN socket address for a raw CAN socket from an interface index.
+	pub fn new(interface_index: c_int) -> Self {
+		unsafe {
+			let inner = libc::sockaddr_can {
+				can_family: Self::static_family(),
+				can_ifindex: interface_index,
+				..std::mem::zeroed()
+			};
+			Self::from_raw(inner)
+		}
+	}
+
+	/// Create a CAN socket address for an ISO-TP socket.
+	///
+	/// The receive and transmit identifiers are the CAN identifiers used by the ISO-TP protocol.
+	/// Set the `CAN_EFF_FLAG` bit on an identifier to use the extended frame format.
+	pub fn new_isotp(interface_index: c_int, rx_id: u32, tx_id: u32) -> Self {
+		let mut address = Self::new(interface_index);
+		address.set_isotp_rx_id(rx_id);
+		address.set_isotp_tx_id(tx_id);
+		address
+	}
+
+	/// Create a CAN socket address from a [`libc::sockaddr_can`].
+	pub fn from_raw(inner: libc::sockaddr_can) -> Self {
+		Self { inner }
+	}
+
+	/// Convert the [`SocketAddress`] into raw [`libc`] parts.
+	pub fn into_raw(self) -> libc::sockaddr_can {
+		self.inner
+	}
+
+	/// Get the interface index associated with the socket address.
+	pub fn interface_index(&self) -> c_int {
+		self.inner.can_ifindex
+	}
+
+	/// Set the interface index associated with the socket address.
+	pub fn set_interface_index(&mut self, interface_index: c_int) {
+		self.inner.can_ifindex = interface_index;
+	}
+
+	/// Get the ISO-TP receive identifier associated with the socket address.
+	pub fn isotp_rx_id(&self) -> u32 {
+		unsafe { self.inner.can_addr.tp.rx_id }
+	}
+
+	/// Set the ISO-TP receive identifier associated with the socket address.
+	pub fn set_isotp_rx_id(&mut self, rx_id: u32) {
+		self.inner.can_addr.tp.rx_id = rx_id;
+	}
+
+	/// Get the ISO-TP transmit identifier associated with the socket address.
+	pub fn isotp_tx_id(&self) -> u32 {
+		unsafe { self.inner.can_addr.tp.tx_id }
+	}
+
+	/// Set the ISO-TP transmit identifier associated with the socket address.
+	pub fn set_isotp_tx_id(&mut self, tx_id: u32) {
+		self.inner.can_addr.tp.tx_id = tx_id;
+	}
+
+	/// Get the minimum length of a CAN socket address.
+	///
+	/// This covers the address family and the interface index, which are used by all CAN protocols.
+	pub(crate) fn min_len() -> libc::socklen_t {
+		let address = Self::new(0);
+		let start = &address.inner as *const _ as usize;
+		let can_addr = &address.inner.can_addr as *const _ as usize;
+		(can_addr - start) as libc::socklen_t
+	}
+}
+
+impl_eq_and_hash!(CanSocketAddress, |address| -> (c_int, u32, u32) {
+	(address.interface_index(), address.isotp_rx_id(), address.isotp_tx_id())
+});
+
+impl SpecificSocketAddress for CanSocketAddress {
+	fn static_family() -> libc::sa_family_t {
+		libc::AF_CAN as libc::sa_family_t
+	}
+}
+
+unsafe impl crate::AsSocketAddress for CanSocketAddress {
+	fn as_sockaddr(&self) -> *const libc::sockaddr {
+		&self.inner as *const _ as *const _
+	}
+
+	fn as_sockaddr_mut(address: &mut std::mem::MaybeUninit<Self>) -> *mut libc::sockaddr {
+		unsafe { &mut address.as_mut_ptr().as_mut().unwrap().inner as *mut _ as *mut _ }
+	}
+
+	fn len(&self) -> libc::socklen_t {
+		Self::max_len()
+	}
+
+	fn finalize(address: std::mem::MaybeUninit<Self>, len: libc::socklen_t) -> std::io::Result<Self> {
+		unsafe {
+			let address = address.assume_init();
+			if address.family() != Self::static_family() {
+				return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address family, expected AF_CAN"));
+			}
+			// The kernel only fills in the fields that are relevant for the protocol,
+			// so the address may be shorter than a full `sockaddr_can`.
+			// The remaining fields are left zeroed.
+			if len < Self::min_len() || len > Self::max_len() {
+				return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address size"));
+			}
+			Ok(address)
+		}
+	}
+
+	fn max_len() -> libc::socklen_t {
+		std::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t
+	}
+}
+
+impl From<CanSocketAddress> for crate::SocketAddress {
+	fn from(other: CanSocketAddress) -> Self {
+		Self::from(&other)
+	}
+}
+
+impl From<&CanSocketAddress> for crate::SocketAddress {
+	fn from(other: &CanSocketAddress) -> Self {
+		Self::from_other(other)
+	}
+}
@@ -15,12 +15,23 @@ impl Inet4SocketAddress {
 	pub fn new(ip: &std::net::Ipv4Addr, port: u16) -> Self {
 		unsafe {
 			let ip : u32 = std::mem::transmute(ip.octets());
-			let inner = libc::sockaddr_in {
+			#[allow(unused_mut)]
+			let mut inner = libc::sockaddr_in {
 				sin_family: Self::static_family(),
 				sin_addr: libc::in_addr { s_addr: ip },
 				sin_port: port.to_be(),
 				..std::mem::zeroed()
 			};
+			#[cfg(any(
+				target_vendor = "apple",
+				target_os = "dragonfly",
+				target_os = "freebsd",
+				target_os = "netbsd",
+				target_os = "openbsd",
+			))]
+			{
+				inner.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+			}
 			Self::from_raw(inner)
 		}
 	}
@@ -62,6 +73,32 @@ impl Inet4SocketAddress {
 	}
 }
 
+impl_eq_and_hash!(Inet4SocketAddress, |address| -> (std::net::Ipv4Addr, u16) { (address.ip(), address.port()) });
+
+impl std::fmt::Debug for Inet4SocketAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "Inet4SocketAddress({})", self)
+	}
+}
+
+impl std::fmt::Display for Inet4SocketAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&std::net::SocketAddrV4::from(self), f)
+	}
+}
+
+impl std::str::FromStr for Inet4SocketAddress {
+	type Err = std::io::Error;
+
+	/// Parse an IPv4 socket address in the form `1.2.3.4:80`.
+	fn from_str(input: &str) -> std::io::Result<Self> {
+		match input.parse::<std::net::SocketAddrV4>() {
+			Ok(address) => Ok(address.into()),
+			Err(_) => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid IPv4 socket address")),
+		}
+	}
+}
+
 impl SpecificSocketAddress for Inet4SocketAddress {
 	fn static_family() -> libc::sa_family_t {
 		libc::AF_INET as libc::sa_family_t
@@ -13,13 +13,23 @@ pub struct Inet6SocketAddress {
 impl Inet6SocketAddress {
 	/// Create an IPv6 socket address.
 	pub fn new(ip: std::net::Ipv6Addr, port: u16, flowinfo: u32, scope_id: u32) -> Self {
-		let inner = libc::sockaddr_in6 {
-			sin6_family: Self::static_family(),
-			sin6_addr: libc::in6_addr { s6_addr: ip.octets() },
-			sin6_port: port.to_be(),
-			sin6_flowinfo: flowinfo,
-			sin6_scope_id: scope_id,
-		};
+		// Use a zeroed struct, since some platforms have additional fields like `sin6_len`.
+		let mut inner: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+		inner.sin6_family = Self::static_family();
+		inner.sin6_addr = libc::in6_addr { s6_addr: ip.octets() };
+		inner.sin6_port = port.to_be();
+		inner.sin6_flowinfo = flowinfo;
+		inner.sin6_scope_id = scope_id;
+		#[cfg(any(
+			target_vendor = "apple",
+			target_os = "dragonfly",
+			target_os = "freebsd",
+			target_os = "netbsd",
+			target_os = "openbsd",
+		))]
+		{
+			inner.sin6_len = std::mem::size_of::<libc::sockaddr_in6>() as u8;
+		}
 		Self::from_raw(inner)
 	}
 
@@ -54,7 +64,7 @@ impl Inet6SocketAddress {
 	}
 
 	/// Get the flow information associated with the socket address.
-	fn flowinfo(&self) -> u32 {
+	pub fn flowinfo(&self) -> u32 {
 		self.inner.sin6_flowinfo
 	}
 
@@ -64,16 +74,69 @@ impl Inet6SocketAddress {
 	}
 
 	/// Get the scope ID associated with the socket address.
-	fn scope_id(&self) -> u32 {
+	///
+	/// For link-local addresses, this is the index of the network interface.
+	pub fn scope_id(&self) -> u32 {
 		self.inner.sin6_scope_id
 	}
 
+	/// Get the name of the network interface identified by the scope ID.
+	///
+	/// This looks up the interface with `if_indextoname`,
+	/// so the result can be used as a zone identifier like `fe80::1%eth0`.
+	pub fn scope_interface_name(&self) -> std::io::Result<String> {
+		crate::interface::index_to_name(self.scope_id())
+	}
+
 	/// Set the scope ID associated with the socket address.
 	pub fn set_scope_id(&mut self, scope_id: u32) {
 		self.inner.sin6_scope_id = scope_id;
 	}
 }
 
+impl_eq_and_hash!(Inet6SocketAddress, |address| -> (std::net::Ipv6Addr, u16, u32, u32) {
+	(address.ip(), address.port(), address.flowinfo(), address.scope_id())
+});
+
+impl std::fmt::Debug for Inet6SocketAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "Inet6SocketAddress({})", self)
+	}
+}
+
+impl std::fmt::Display for Inet6SocketAddress {
+	/// Format the socket address in the form `[::1]:80`.
+	///
+	/// If the address has a scope ID, it is formatted as a numeric zone identifier like `[fe80::1%2]:80`.
+	/// Use [`Self::scope_interface_name()`] to get the name of the network interface instead.
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		std::fmt::Display::fmt(&std::net::SocketAddrV6::from(self), f)
+	}
+}
+
+impl std::str::FromStr for Inet6SocketAddress {
+	type Err = std::io::Error;
+
+	/// Parse an IPv6 socket address in the form `[::1]:80`.
+	///
+	/// The address may have a zone identifier like `[fe80::1%eth0]:80` or `[fe80::1%2]:80`.
+	/// An interface name is resolved to a scope ID with `if_nametoindex`.
+	fn from_str(input: &str) -> std::io::Result<Self> {
+		let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid IPv6 socket address");
+		if let Ok(address) = input.parse::<std::net::SocketAddrV6>() {
+			return Ok(address.into());
+		}
+
+		let (host, port) = input.rsplit_once(':').ok_or_else(invalid)?;
+		let host = host.strip_prefix('[').and_then(|x| x.strip_suffix(']')).ok_or_else(invalid)?;
+		let (ip, zone) = host.split_once('%').ok_or_else(invalid)?;
+		let ip: std::net::Ipv6Addr = ip.parse().map_err(|_| invalid())?;
+		let port: u16 = port.parse().map_err(|_| invalid())?;
+		let scope_id = crate::interface::name_to_index(zone)?;
+		Ok(Self::new(ip, port, 0, scope_id))
+	}
+}
+
 impl SpecificSocketAddress for Inet6SocketAddress {
 	fn static_family() -> libc::sa_family_t {
 		libc::AF_INET6 as libc::sa_family_t
@@ -1,15 +1,58 @@
 use std::os::raw::c_int;
 
+/// Implement `PartialEq`, `Eq` and `Hash` for a socket address by comparing a key derived from the address.
+///
+/// The key should only include the meaningful parts of the address.
+macro_rules! impl_eq_and_hash {
+	($type:ty, |$address:ident| -> $key_type:ty { $key:expr }) => {
+		impl PartialEq for $type {
+			fn eq(&self, other: &Self) -> bool {
+				fn key($address: &$type) -> $key_type {
+					$key
+				}
+				key(self) == key(other)
+			}
+		}
+
+		impl Eq for $type {}
+
+		impl std::hash::Hash for $type {
+			fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+				fn key($address: &$type) -> $key_type {
+					$key
+				}
+				key(self).hash(state)
+			}
+		}
+	};
+}
+
+#[cfg(all(feature = "ax25", target_os = "linux"))]
+mod ax25;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+mod bluetooth;
+#[cfg(target_os = "linux")]
+mod can;
 mod inet4;
 mod inet6;
+mod none;
+#[cfg(target_os = "linux")]
+mod pppox;
 mod unix;
 
+#[cfg(all(feature = "ax25", target_os = "linux"))]
+pub use ax25::*;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+pub use bluetooth::*;
+#[cfg(target_os = "linux")]
+pub use can::*;
 pub use inet4::*;
 pub use inet6::*;
+pub use none::*;
+#[cfg(target_os = "linux")]
+pub use pppox::*;
 pub use unix::*;
 
-// TODO: implement Debug in a nice manner for the types.
-
 /// A socket address that supports multiple address families at runtime.
 pub trait GenericSocketAddress: AsSocketAddress {}
 
@@ -82,13 +125,14 @@ impl SocketAddress {
 	/// Create a generic [`SocketAddress`] by copying data from another address.
 	pub fn from_other<Address: AsSocketAddress>(other: &Address) -> Self {
 		unsafe {
+			let bytes = address_bytes(other);
 			let mut output = std::mem::MaybeUninit::zeroed();
 			std::ptr::copy(
-				other.as_sockaddr(),
-				AsSocketAddress::as_sockaddr_mut(&mut output),
-				other.len() as usize
+				bytes.as_ptr(),
+				AsSocketAddress::as_sockaddr_mut(&mut output) as *mut u8,
+				bytes.len()
 			);
-			AsSocketAddress::finalize(output, other.len()).unwrap()
+			AsSocketAddress::finalize(output, bytes.len() as libc::socklen_t).unwrap()
 		}
 	}
 
@@ -104,37 +148,236 @@ impl SocketAddress {
 
 	/// Get the address as an IPv4 socket address.
 	///
-	/// Returns [`None`] if the address is not an IPv4 socket address.
+	/// Returns [`None`] if the address is not an IPv4 socket address,
+	/// or if the length of the address is too short for an IPv4 socket address.
 	pub fn as_inet4(&self) -> Option<Inet4SocketAddress> {
-		if self.family() == libc::AF_INET {
-			let addr: &libc::sockaddr_in = unsafe { std::mem::transmute(&self.inner) };
-			Some(Inet4SocketAddress::from_raw(*addr))
-		} else {
-			None
+		if self.family() != libc::AF_INET {
+			return None;
 		}
+		let inner = unsafe { self.copy_to::<libc::sockaddr_in>(std::mem::size_of::<libc::sockaddr_in>())? };
+		Some(Inet4SocketAddress::from_raw(inner))
 	}
 
 	/// Get the address as an IPv6 socket address.
 	///
-	/// Returns [`None`] if the address is not an IPv6 socket address.
+	/// Returns [`None`] if the address is not an IPv6 socket address,
+	/// or if the length of the address is too short for an IPv6 socket address.
 	pub fn as_inet6(&self) -> Option<Inet6SocketAddress> {
-		if self.family() == libc::AF_INET6 {
-			let addr: &libc::sockaddr_in6 = unsafe { std::mem::transmute(&self.inner) };
-			Some(Inet6SocketAddress::from_raw(*addr))
-		} else {
-			None
+		if self.family() != libc::AF_INET6 {
+			return None;
 		}
+		let inner = unsafe { self.copy_to::<libc::sockaddr_in6>(std::mem::size_of::<libc::sockaddr_in6>())? };
+		Some(Inet6SocketAddress::from_raw(inner))
 	}
 
 	/// Get the address as an unix socket address.
 	///
-	/// Returns [`None`] if the address is not a unix socket address.
+	/// Returns [`None`] if the address is not a unix socket address,
+	/// or if the length of the address is invalid for a unix socket address.
 	pub fn as_unix(&self) -> Option<UnixSocketAddress> {
-		if self.family() == libc::AF_LOCAL {
-			let addr: &libc::sockaddr_un = unsafe { std::mem::transmute(&self.inner) };
-			Some(UnixSocketAddress::from_raw(*addr, self.len))
+		if self.family() != libc::AF_LOCAL {
+			return None;
+		}
+		if self.len as usize > std::mem::size_of::<libc::sockaddr_un>() {
+			return None;
+		}
+		let path_offset = unsafe {
+			let address = std::mem::MaybeUninit::<libc::sockaddr_un>::uninit();
+			let start = address.as_ptr() as usize;
+			let sun_path = std::ptr::addr_of!((*address.as_ptr()).sun_path) as usize;
+			sun_path - start
+		};
+		let inner = unsafe { self.copy_to::<libc::sockaddr_un>(path_offset)? };
+		Some(UnixSocketAddress::from_raw(inner, self.len))
+	}
+
+	/// Get the address as a CAN socket address.
+	///
+	/// Returns [`None`] if the address is not a CAN socket address,
+	/// or if the length of the address is too short for a CAN socket address.
+	#[cfg(target_os = "linux")]
+	pub fn as_can(&self) -> Option<CanSocketAddress> {
+		if self.family() != libc::AF_CAN {
+			return None;
+		}
+		let inner = unsafe { self.copy_to::<libc::sockaddr_can>(CanSocketAddress::min_len() as usize)? };
+		Some(CanSocketAddress::from_raw(inner))
+	}
+
+	/// Copy the address into a zeroed address struct of type `T`.
+	///
+	/// Only the bytes covered by the length of the address are copied.
+	/// Returns [`None`] if the length of the address is less than `min_len`,
+	/// or larger than the `sockaddr_storage` it is stored in.
+	///
+	/// # Safety
+	/// `T` must be a plain C struct for which all zero bytes is a valid value.
+	unsafe fn copy_to<T>(&self, min_len: usize) -> Option<T> {
+		let len = self.len as usize;
+		if len < min_len || len > std::mem::size_of::<libc::sockaddr_storage>() {
+			return None;
+		}
+		let mut output = std::mem::MaybeUninit::<T>::zeroed();
+		std::ptr::copy_nonoverlapping(
+			&self.inner as *const libc::sockaddr_storage as *const u8,
+			output.as_mut_ptr() as *mut u8,
+			len.min(std::mem::size_of::<T>()),
+		);
+		Some(output.assume_init())
+	}
+}
+
+/// A [`SocketAddress`] split by address family.
+///
+/// Use [`SocketAddress::kind()`] to match on the address family of a generic socket address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AddressKind {
+	/// An IPv4 socket address.
+	Inet4(Inet4SocketAddress),
+
+	/// An IPv6 socket address.
+	Inet6(Inet6SocketAddress),
+
+	/// A unix socket address.
+	Unix(UnixSocketAddress),
+
+	/// An address of another family, with the given address family.
+	///
+	/// This is also used for addresses that are too short for their address family.
+	Other(c_int),
+}
+
+impl SocketAddress {
+	/// Get the address as one of the specific address types, depending on the address family.
+	///
+	/// ```
+	/// use posix_socket::{AddressKind, SocketAddress};
+	///
+	/// fn describe(address: &SocketAddress) -> String {
+	///     match address.kind() {
+	///         AddressKind::Inet4(address) => format!("IPv4 {}", address),
+	///         AddressKind::Inet6(address) => format!("IPv6 {}", address),
+	///         AddressKind::Unix(address) => format!("unix {:?}", address),
+	///         AddressKind::Other(family) => format!("address family {}", family),
+	///     }
+	/// }
+	/// ```
+	pub fn kind(&self) -> AddressKind {
+		if let Some(address) = self.as_inet4() {
+			AddressKind::Inet4(address)
+		} else if let Some(address) = self.as_inet6() {
+			AddressKind::Inet6(address)
+		} else if let Some(address) = self.as_unix() {
+			AddressKind::Unix(address)
+		} else {
+			AddressKind::Other(self.family())
+		}
+	}
+}
+
+/// Implement [`TryFrom`](std::convert::TryFrom) for converting a [`SocketAddress`] into a specific address type.
+macro_rules! impl_try_from_socket_address {
+	($type:ty, $method:ident, $message:literal) => {
+		impl std::convert::TryFrom<SocketAddress> for $type {
+			type Error = std::io::Error;
+
+			fn try_from(other: SocketAddress) -> Result<Self, Self::Error> {
+				Self::try_from(&other)
+			}
+		}
+
+		impl std::convert::TryFrom<&SocketAddress> for $type {
+			type Error = std::io::Error;
+
+			fn try_from(other: &SocketAddress) -> Result<Self, Self::Error> {
+				other.$method().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, $message))
+			}
+		}
+	};
+}
+
+impl_try_from_socket_address!(Inet4SocketAddress, as_inet4, "wrong address family, expected AF_INET");
+impl_try_from_socket_address!(Inet6SocketAddress, as_inet6, "wrong address family, expected AF_INET6");
+impl_try_from_socket_address!(UnixSocketAddress, as_unix, "wrong address family, expected AF_LOCAL");
+
+impl From<std::net::SocketAddr> for SocketAddress {
+	fn from(other: std::net::SocketAddr) -> Self {
+		Self::from(&other)
+	}
+}
+
+impl From<&std::net::SocketAddr> for SocketAddress {
+	fn from(other: &std::net::SocketAddr) -> Self {
+		match other {
+			std::net::SocketAddr::V4(address) => Self::from(Inet4SocketAddress::from(address)),
+			std::net::SocketAddr::V6(address) => Self::from(Inet6SocketAddress::from(address)),
+		}
+	}
+}
+
+impl std::convert::TryFrom<SocketAddress> for std::net::SocketAddr {
+	type Error = std::io::Error;
+
+	fn try_from(other: SocketAddress) -> Result<Self, Self::Error> {
+		Self::try_from(&other)
+	}
+}
+
+impl std::convert::TryFrom<&SocketAddress> for std::net::SocketAddr {
+	type Error = std::io::Error;
+
+	/// Convert a [`SocketAddress`] to a [`std::net::SocketAddr`].
+	///
+	/// This fails if the address is not an IPv4 or IPv6 address.
+	fn try_from(other: &SocketAddress) -> Result<Self, Self::Error> {
+		match other.kind() {
+			AddressKind::Inet4(address) => Ok(Self::V4(address.into())),
+			AddressKind::Inet6(address) => Ok(Self::V6(address.into())),
+			_ => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "wrong address family, expected AF_INET or AF_INET6")),
+		}
+	}
+}
+
+impl_eq_and_hash!(SocketAddress, |address| -> &[u8] { address_bytes(address) });
+
+impl std::fmt::Debug for SocketAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		if let Some(address) = self.as_inet4() {
+			std::fmt::Debug::fmt(&address, f)
+		} else if let Some(address) = self.as_inet6() {
+			std::fmt::Debug::fmt(&address, f)
+		} else if let Some(address) = self.as_unix() {
+			std::fmt::Debug::fmt(&address, f)
+		} else {
+			f.debug_struct("SocketAddress")
+				.field("family", &self.family())
+				.field("len", &self.len)
+				.finish()
+		}
+	}
+}
+
+impl std::fmt::Display for SocketAddress {
+	/// Format the socket address.
+	///
+	/// IPv4 and IPv6 addresses are formatted as `1.2.3.4:80` and `[::1]:80`.
+	/// Unix socket addresses are formatted as the path, as `@name` for abstract addresses, or as `(unnamed)`.
+	/// Addresses of other families are formatted as `(address family N)`.
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		if let Some(address) = self.as_inet4() {
+			std::fmt::Display::fmt(&address, f)
+		} else if let Some(address) = self.as_inet6() {
+			std::fmt::Display::fmt(&address, f)
+		} else if let Some(address) = self.as_unix() {
+			if let Some(path) = address.as_path() {
+				write!(f, "{}", path.display())
+			} else if let Some(name) = address.as_abstract_bytes() {
+				write!(f, "@{}", String::from_utf8_lossy(name))
+			} else {
+				write!(f, "(unnamed)")
+			}
 		} else {
-			None
+			write!(f, "(address family {})", self.family())
 		}
 	}
 }
@@ -169,3 +412,14 @@ unsafe impl AsSocketAddress for SocketAddress {
 }
 
 impl GenericSocketAddress for SocketAddress {}
+
+/// Get the bytes of a socket address up to the length of the address.
+///
+/// The length is clamped to [`AsSocketAddress::max_len()`],
+/// since safe code can create an address with any length using `from_raw()`.
+fn address_bytes<Address: AsSocketAddress>(address: &Address) -> &[u8] {
+	let len = address.len().min(Address::max_len());
+	unsafe {
+		std::slice::from_raw_parts(address.as_sockaddr() as *const u8, len as usize)
+	}
+}
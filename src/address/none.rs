@@ -0,0 +1,67 @@
+/// Placeholder address for sockets that do not use addresses.
+///
+/// Some socket families, like `PF_KEY`, have no socket address at all.
+/// Sockets of those families can use this type as address type.
+///
+/// The address has a length of zero and the family `AF_UNSPEC`.
+/// Finalizing an address written by the kernel fails if the kernel reported a non-empty address.
+#[derive(Clone)]
+#[repr(C)]
+pub struct NoSocketAddress {
+	/// The inner C-compatible socket address, never filled in with a real address.
+	inner: libc::sockaddr,
+}
+
+impl NoSocketAddress {
+	/// Create a new empty socket address.
+	pub fn new() -> Self {
+		unsafe {
+			let mut inner: libc::sockaddr = std::mem::zeroed();
+			inner.sa_family = libc::AF_UNSPEC as libc::sa_family_t;
+			Self { inner }
+		}
+	}
+}
+
+impl Default for NoSocketAddress {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PartialEq for NoSocketAddress {
+	fn eq(&self, _other: &Self) -> bool {
+		true
+	}
+}
+
+impl Eq for NoSocketAddress {}
+
+impl std::hash::Hash for NoSocketAddress {
+	fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+unsafe impl crate::AsSocketAddress for NoSocketAddress {
+	fn as_sockaddr(&self) -> *const libc::sockaddr {
+		&self.inner
+	}
+
+	fn as_sockaddr_mut(address: &mut std::mem::MaybeUninit<Self>) -> *mut libc::sockaddr {
+		unsafe { &mut address.as_mut_ptr().as_mut().unwrap().inner }
+	}
+
+	fn len(&self) -> libc::socklen_t {
+		0
+	}
+
+	fn finalize(_address: std::mem::MaybeUninit<Self>, len: libc::socklen_t) -> std::io::Result<Self> {
+		if len != 0 {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected socket address, expected no address"));
+		}
+		Ok(Self::new())
+	}
+
+	fn max_len() -> libc::socklen_t {
+		std::mem::size_of::<libc::sockaddr>() as libc::socklen_t
+	}
+}
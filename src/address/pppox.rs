@@ -0,0 +1,154 @@
+use crate::SpecificSocketAddress;
+use std::os::raw::{c_char, c_int, c_uint};
+
+/// The C-compatible PPPoE address (`struct pppoe_addr`).
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct pppoe_addr {
+	sid: u16,
+	remote: [u8; 6],
+	dev: [c_char; libc::IFNAMSIZ],
+}
+
+/// The C-compatible PPP over X socket address (`struct sockaddr_pppox`).
+///
+/// The kernel declares the struct as packed.
+/// Only the PPPoE member of the address union is included, since it is the largest member.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+struct sockaddr_pppox {
+	sa_family: libc::sa_family_t,
+	sa_protocol: c_uint,
+	sa_addr: pppoe_addr,
+}
+
+/// PPPoE socket address.
+///
+/// This includes the session ID, the MAC address of the remote peer and the name of the local network interface.
+/// A session ID of 0 is used to disconnect a session socket.
+///
+/// PPPoE socket addresses are a non-portable Linux extension.
+#[derive(Clone)]
+#[repr(C, align(4))]
+pub struct PppoeSocketAddress {
+	/// The inner C-compatible socket address.
+	inner: sockaddr_pppox,
+}
+
+impl PppoeSocketAddress {
+	/// The protocol number for PPPoE sockets (`PX_PROTO_OE`).
+	pub const PROTOCOL: c_int = 0;
+
+	/// Create a PPPoE socket address from a session ID, the MAC address of the peer and an interface name.
+	///
+	/// Returns an error if the interface name does not fit in the address or if it contains a null byte.
+	pub fn new(session_id: u16, remote: [u8; 6], device: &str) -> std::io::Result<Self> {
+		let device = device.as_bytes();
+		if device.len() >= libc::IFNAMSIZ {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name is too long for a PPPoE socket address"));
+		}
+		if device.contains(&0) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name contains a null byte"));
+		}
+
+		let mut dev = [0; libc::IFNAMSIZ];
+		for (dest, &byte) in dev.iter_mut().zip(device) {
+			*dest = byte as c_char;
+		}
+
+		let inner = sockaddr_pppox {
+			sa_family: Self::static_family(),
+			sa_protocol: Self::PROTOCOL as c_uint,
+			sa_addr: pppoe_addr {
+				sid: session_id.to_be(),
+				remote,
+				dev,
+			},
+		};
+		Ok(Self { inner })
+	}
+
+	/// Get the session ID associated with the socket address.
+	pub fn session_id(&self) -> u16 {
+		u16::from_be(self.inner.sa_addr.sid)
+	}
+
+	/// Set the session ID associated with the socket address.
+	pub fn set_session_id(&mut self, session_id: u16) {
+		self.inner.sa_addr.sid = session_id.to_be();
+	}
+
+	/// Get the MAC address of the remote peer associated with the socket address.
+	pub fn remote(&self) -> [u8; 6] {
+		self.inner.sa_addr.remote
+	}
+
+	/// Set the MAC address of the remote peer associated with the socket address.
+	pub fn set_remote(&mut self, remote: [u8; 6]) {
+		self.inner.sa_addr.remote = remote;
+	}
+
+	/// Get the name of the local network interface associated with the socket address.
+	pub fn device(&self) -> &std::ffi::OsStr {
+		use std::os::unix::ffi::OsStrExt;
+		let dev = &self.inner.sa_addr.dev;
+		let len = dev.iter().position(|&c| c == 0).unwrap_or(dev.len());
+		let dev: &[u8] = unsafe { std::slice::from_raw_parts(dev.as_ptr() as *const u8, len) };
+		std::ffi::OsStr::from_bytes(dev)
+	}
+}
+
+impl_eq_and_hash!(PppoeSocketAddress, |address| -> (u16, [u8; 6], &std::ffi::OsStr) {
+	(address.session_id(), address.remote(), address.device())
+});
+
+impl SpecificSocketAddress for PppoeSocketAddress {
+	fn static_family() -> libc::sa_family_t {
+		libc::AF_PPPOX as libc::sa_family_t
+	}
+}
+
+unsafe impl crate::AsSocketAddress for PppoeSocketAddress {
+	fn as_sockaddr(&self) -> *const libc::sockaddr {
+		&self.inner as *const _ as *const _
+	}
+
+	fn as_sockaddr_mut(address: &mut std::mem::MaybeUninit<Self>) -> *mut libc::sockaddr {
+		unsafe { &mut address.as_mut_ptr().as_mut().unwrap().inner as *mut _ as *mut _ }
+	}
+
+	fn len(&self) -> libc::socklen_t {
+		Self::max_len()
+	}
+
+	fn finalize(address: std::mem::MaybeUninit<Self>, len: libc::socklen_t) -> std::io::Result<Self> {
+		unsafe {
+			let address = address.assume_init();
+			if address.family() != Self::static_family() {
+				return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address family, expected AF_PPPOX"));
+			}
+			if len != Self::max_len() {
+				return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "wrong address size"));
+			}
+			Ok(address)
+		}
+	}
+
+	fn max_len() -> libc::socklen_t {
+		std::mem::size_of::<sockaddr_pppox>() as libc::socklen_t
+	}
+}
+
+impl From<PppoeSocketAddress> for crate::SocketAddress {
+	fn from(other: PppoeSocketAddress) -> Self {
+		Self::from(&other)
+	}
+}
+
+impl From<&PppoeSocketAddress> for crate::SocketAddress {
+	fn from(other: &PppoeSocketAddress) -> Self {
+		Self::from_other(other)
+	}
+}
@@ -23,12 +23,10 @@ impl UnixSocketAddress {
 
 		unsafe {
 			let mut output = Self {
-				inner: libc::sockaddr_un {
-					sun_family: Self::static_family(),
-					sun_path: std::mem::zeroed(),
-				},
+				inner: std::mem::zeroed(),
 				len: 0,
 			};
+			output.inner.sun_family = Self::static_family();
 			let path_offset = output.path_offset();
 			if path.len() >= Self::max_len() as usize - path_offset - 1 {
 				Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "path is too large for a socket address"))
@@ -41,6 +39,7 @@ impl UnixSocketAddress {
 					path.len(),
 				);
 				output.len = (path_offset + path.len() + 1) as libc::socklen_t;
+				output.set_sun_len();
 				Ok(output)
 			}
 		}
@@ -50,17 +49,43 @@ impl UnixSocketAddress {
 	pub fn new_unnamed() -> Self {
 		unsafe {
 			let mut address = Self {
-				inner: libc::sockaddr_un {
-					sun_family: Self::static_family(),
-					sun_path: std::mem::zeroed(),
-				},
+				inner: std::mem::zeroed(),
 				len: 0,
 			};
+			address.inner.sun_family = Self::static_family();
 			address.len = address.path_offset() as libc::socklen_t;
+			address.set_sun_len();
 			address
 		}
 	}
 
+	/// Create a Unix socket address from the raw contents of the `sun_path` field.
+	///
+	/// The bytes are copied as-is, without any assumptions about the encoding.
+	/// The length of the address is set to cover exactly the given bytes,
+	/// so path names should include the terminating null byte.
+	///
+	/// An empty slice creates an unnamed address,
+	/// and a slice starting with a null byte creates an abstract address.
+	/// This can be used to reconstruct addresses reported by `/proc/net/unix` or netlink socket diagnostics.
+	pub fn from_path_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+		let mut output = Self::new_unnamed();
+		let path_offset = output.path_offset();
+		if bytes.len() > Self::max_len() as usize - path_offset {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "path is too large for a socket address"));
+		}
+		unsafe {
+			std::ptr::copy(
+				bytes.as_ptr(),
+				output.inner.sun_path.as_mut_ptr() as *mut u8,
+				bytes.len(),
+			);
+		}
+		output.len = (path_offset + bytes.len()) as libc::socklen_t;
+		output.set_sun_len();
+		Ok(output)
+	}
+
 	/// Create a Unix socket address from a [`libc::sockaddr_un`] and a length.
 	pub fn from_raw(inner: libc::sockaddr_un, len: libc::socklen_t) -> Self {
 		Self { inner, len }
@@ -83,7 +108,9 @@ impl UnixSocketAddress {
 			} else if self.inner.sun_path[0] == 0 {
 				None
 			} else {
-				let path: &[u8] = std::mem::transmute(&self.inner.sun_path[..path_len - 1]);
+				// Some platforms do not include the terminating null byte in the reported length.
+				let path: &[u8] = std::mem::transmute(&self.inner.sun_path[..path_len]);
+				let path = path.split(|&c| c == 0).next().unwrap_or(path);
 				let path = std::ffi::OsStr::from_bytes(path);
 				Some(Path::new(path))
 			}
@@ -111,6 +138,45 @@ impl UnixSocketAddress {
 		}
 	}
 
+	/// Get the abstract name associated with the socket address as raw bytes.
+	///
+	/// Unlike [`Self::as_abstract()`], this returns the full name, even if it contains null bytes.
+	/// The leading null byte that marks the address as abstract is not included.
+	///
+	/// Returns [`None`] if the socket address is not abstract.
+	pub fn as_abstract_bytes(&self) -> Option<&[u8]> {
+		match self.as_bytes() {
+			[0, name @ ..] => Some(name),
+			_ => None,
+		}
+	}
+
+	/// Get the raw contents of the `sun_path` field, as far as it is covered by the length of the address.
+	///
+	/// The bytes are returned as-is, without any assumptions about the encoding.
+	/// For path names, this includes the terminating null byte if it was included in the address length.
+	/// For abstract addresses, this includes the leading null byte.
+	/// For unnamed addresses, this returns an empty slice.
+	pub fn as_bytes(&self) -> &[u8] {
+		unsafe {
+			std::slice::from_raw_parts(self.inner.sun_path.as_ptr() as *const u8, self.path_len())
+		}
+	}
+
+	/// Set the `sun_len` field to the length of the address on platforms that have it.
+	fn set_sun_len(&mut self) {
+		#[cfg(any(
+			target_vendor = "apple",
+			target_os = "dragonfly",
+			target_os = "freebsd",
+			target_os = "netbsd",
+			target_os = "openbsd",
+		))]
+		{
+			self.inner.sun_len = self.len as u8;
+		}
+	}
+
 	/// Get the offset of the path within the [`libc::sockaddr_un`] struct.
 	fn path_offset(&self) -> usize {
 		let start = &self.inner as *const _ as usize;
@@ -119,8 +185,26 @@ impl UnixSocketAddress {
 	}
 
 	/// Get the length of the path portion of the address including the terminating null byte.
+	///
+	/// The result is clamped to the size of the `sun_path` field.
 	fn path_len(&self) -> usize {
-		self.len() as usize - self.path_offset()
+		let len = (self.len() as usize).saturating_sub(self.path_offset());
+		len.min(self.inner.sun_path.len())
+	}
+}
+
+// Compare only the path, so an unnamed address compares equal regardless of the reported length.
+impl_eq_and_hash!(UnixSocketAddress, |address| -> &[u8] { address.as_bytes() });
+
+impl std::fmt::Debug for UnixSocketAddress {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		if let Some(path) = self.as_path() {
+			write!(f, "UnixSocketAddress({:?})", path)
+		} else if let Some(name) = self.as_abstract_bytes() {
+			write!(f, "UnixSocketAddress(abstract {:?})", String::from_utf8_lossy(name))
+		} else {
+			write!(f, "UnixSocketAddress(unnamed)")
+		}
 	}
 }
 
@@ -17,6 +17,7 @@ use libc::{gid_t, pid_t, uid_t};
 pub(crate) type CmsgLen = usize;
 
 #[cfg(any(
+	target_vendor = "apple",
 	target_os = "dragonfly",
 	target_os = "emscripten",
 	target_os = "freebsd",
@@ -198,6 +199,8 @@ impl<'a> Iterator for ScmCredentials<'a> {
 #[derive(Debug)]
 pub enum AncillaryError {
 	Unknown { cmsg_level: i32, cmsg_type: i32 },
+	/// A received file descriptor could not be duplicated.
+	Io(std::io::Error),
 }
 
 /// This enum represent one control message of variable type.
@@ -205,6 +208,52 @@ pub enum AncillaryData<'a> {
 	ScmRights(ScmRights<'a>),
 	#[cfg(any(doc, target_os = "android", target_os = "linux",))]
 	ScmCredentials(ScmCredentials<'a>),
+	#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+	ScmSecurity(&'a [u8]),
+	/// A pidfd for the sending process.
+	///
+	/// This is a duplicate of the received file descriptor, which is closed when it is dropped.
+	/// The received file descriptor itself is closed when the [`SocketAncillary`] is dropped, cleared or used for another receive.
+	#[cfg(any(doc, target_os = "android", target_os = "linux",))]
+	ScmPidfd(filedesc::FileDesc),
+	#[cfg(target_os = "linux")]
+	ExtendedError(crate::errqueue::ExtendedError),
+	#[cfg(target_os = "linux")]
+	Timestamping(crate::timestamping::Timestamps),
+	#[cfg(target_os = "linux")]
+	Ipv4PacketInfo(crate::ip::Ipv4PacketInfo),
+	#[cfg(target_os = "linux")]
+	Ipv6PacketInfo(crate::ip::Ipv6PacketInfo),
+	/// The type-of-service field of a received IPv4 packet.
+	///
+	/// Received as `IP_TOS` control message if enabled with [`Socket::set_ip_recv_tos()`](crate::Socket::set_ip_recv_tos).
+	#[cfg(target_os = "linux")]
+	Ipv4Tos(u8),
+	/// The time-to-live of a received IPv4 packet.
+	///
+	/// Received as `IP_TTL` control message if enabled with [`Socket::set_ip_recv_ttl()`](crate::Socket::set_ip_recv_ttl).
+	#[cfg(target_os = "linux")]
+	Ipv4Ttl(u8),
+	/// The traffic class of a received IPv6 packet.
+	///
+	/// Received as `IPV6_TCLASS` control message if enabled with [`Socket::set_ipv6_recv_tclass()`](crate::Socket::set_ipv6_recv_tclass).
+	#[cfg(target_os = "linux")]
+	Ipv6TrafficClass(u8),
+	/// The hop limit of a received IPv6 packet.
+	///
+	/// Received as `IPV6_HOPLIMIT` control message if enabled with [`Socket::set_ipv6_recv_hoplimit()`](crate::Socket::set_ipv6_recv_hoplimit).
+	#[cfg(target_os = "linux")]
+	Ipv6HopLimit(u8),
+	/// The number of bytes remaining in the receive queue of a TCP socket.
+	///
+	/// Received as `TCP_CM_INQ` control message if enabled with [`Socket::set_tcp_inq()`](crate::Socket::set_tcp_inq).
+	#[cfg(target_os = "linux")]
+	TcpInq(u32),
+	/// The size of the coalesced datagrams in a buffer received with UDP receive offload.
+	///
+	/// Received as `UDP_GRO` control message if enabled with [`Socket::set_udp_gro()`](crate::Socket::set_udp_gro).
+	#[cfg(target_os = "linux")]
+	UdpGroSegmentSize(u16),
 }
 
 impl<'a> AncillaryData<'a> {
@@ -245,10 +294,72 @@ impl<'a> AncillaryData<'a> {
 					libc::SCM_RIGHTS => Ok(AncillaryData::as_rights(data)),
 					#[cfg(any(target_os = "android", target_os = "linux",))]
 					libc::SCM_CREDENTIALS => Ok(AncillaryData::as_credentials(data)),
+					#[cfg(any(target_os = "android", target_os = "linux",))]
+					crate::credentials::SCM_SECURITY => Ok(AncillaryData::ScmSecurity(data)),
+					#[cfg(any(target_os = "android", target_os = "linux",))]
+					crate::credentials::SCM_PIDFD if data.len() >= size_of::<RawFd>() => {
+						let fd = std::ptr::read_unaligned(data.as_ptr() as *const RawFd);
+						filedesc::FileDesc::duplicate_raw_fd(fd)
+							.map(AncillaryData::ScmPidfd)
+							.map_err(AncillaryError::Io)
+					},
+					#[cfg(target_os = "linux")]
+					libc::SCM_TIMESTAMPING => {
+						crate::timestamping::Timestamps::parse(data)
+							.map(AncillaryData::Timestamping)
+							.map_err(|_| AncillaryError::Unknown { cmsg_level: libc::SOL_SOCKET, cmsg_type: libc::SCM_TIMESTAMPING })
+					},
 					cmsg_type => {
 						Err(AncillaryError::Unknown { cmsg_level: libc::SOL_SOCKET, cmsg_type })
 					}
 				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IP if cmsg.cmsg_type == libc::IP_PKTINFO => {
+					crate::ip::Ipv4PacketInfo::parse(data)
+						.map(AncillaryData::Ipv4PacketInfo)
+						.map_err(|_| AncillaryError::Unknown { cmsg_level: libc::IPPROTO_IP, cmsg_type: libc::IP_PKTINFO })
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IPV6 if cmsg.cmsg_type == libc::IPV6_PKTINFO => {
+					crate::ip::Ipv6PacketInfo::parse(data)
+						.map(AncillaryData::Ipv6PacketInfo)
+						.map_err(|_| AncillaryError::Unknown { cmsg_level: libc::IPPROTO_IPV6, cmsg_type: libc::IPV6_PKTINFO })
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IP if cmsg.cmsg_type == libc::IP_TOS && !data.is_empty() => {
+					Ok(AncillaryData::Ipv4Tos(data[0]))
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IP if cmsg.cmsg_type == libc::IP_TTL && data.len() >= size_of::<libc::c_int>() => {
+					let ttl = read_unaligned(data.as_ptr() as *const libc::c_int);
+					Ok(AncillaryData::Ipv4Ttl(ttl as u8))
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IPV6 if cmsg.cmsg_type == libc::IPV6_TCLASS && data.len() >= size_of::<libc::c_int>() => {
+					let traffic_class = read_unaligned(data.as_ptr() as *const libc::c_int);
+					Ok(AncillaryData::Ipv6TrafficClass(traffic_class as u8))
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_IPV6 if cmsg.cmsg_type == libc::IPV6_HOPLIMIT && data.len() >= size_of::<libc::c_int>() => {
+					let hop_limit = read_unaligned(data.as_ptr() as *const libc::c_int);
+					Ok(AncillaryData::Ipv6HopLimit(hop_limit as u8))
+				},
+				#[cfg(target_os = "linux")]
+				libc::IPPROTO_TCP if cmsg.cmsg_type == libc::TCP_CM_INQ && data.len() >= size_of::<libc::c_int>() => {
+					let inq = read_unaligned(data.as_ptr() as *const libc::c_int);
+					Ok(AncillaryData::TcpInq(inq as u32))
+				},
+				#[cfg(target_os = "linux")]
+				libc::SOL_UDP if cmsg.cmsg_type == libc::UDP_GRO && data.len() >= size_of::<libc::c_int>() => {
+					let segment_size = read_unaligned(data.as_ptr() as *const libc::c_int);
+					Ok(AncillaryData::UdpGroSegmentSize(segment_size as u16))
+				},
+				#[cfg(target_os = "linux")]
+				cmsg_level if crate::errqueue::is_extended_error(cmsg_level, cmsg.cmsg_type) => {
+					crate::errqueue::ExtendedError::parse(data)
+						.map(AncillaryData::ExtendedError)
+						.map_err(|_| AncillaryError::Unknown { cmsg_level, cmsg_type: cmsg.cmsg_type })
+				},
 				cmsg_level => {
 					Err(AncillaryError::Unknown { cmsg_level, cmsg_type: (*cmsg).cmsg_type })
 				}
@@ -267,6 +378,15 @@ impl<'a> Iterator for Messages<'a> {
 	type Item = Result<AncillaryData<'a>, AncillaryError>;
 
 	fn next(&mut self) -> Option<Self::Item> {
+		let cmsg = self.next_header()?;
+		let ancillary_result = AncillaryData::try_from_cmsghdr(cmsg);
+		Some(ancillary_result)
+	}
+}
+
+impl<'a> Messages<'a> {
+	/// Get the header of the next control message without parsing it.
+	fn next_header(&mut self) -> Option<&'a libc::cmsghdr> {
 		unsafe {
 			let mut msg: libc::msghdr = zeroed();
 			msg.msg_control = self.buffer.as_ptr() as *mut _;
@@ -280,8 +400,7 @@ impl<'a> Iterator for Messages<'a> {
 
 			let cmsg = cmsg.as_ref()?;
 			self.current = Some(cmsg);
-			let ancillary_result = AncillaryData::try_from_cmsghdr(cmsg);
-			Some(ancillary_result)
+			Some(cmsg)
 		}
 	}
 }
@@ -318,6 +437,14 @@ impl<'a> SocketAncillary<'a> {
 		self.length
 	}
 
+	/// Returns the used part of the buffer.
+	///
+	/// This can be passed as control data to [`Socket::send_msg()`](crate::Socket::send_msg)
+	/// and [`Socket::send_msg_to()`](crate::Socket::send_msg_to).
+	pub fn data(&self) -> &[u8] {
+		&self.buffer[..self.length]
+	}
+
 	/// Returns the iterator of the control messages.
 	pub fn messages(&self) -> Messages<'_> {
 		Messages { buffer: &self.buffer[..self.length], current: None }
@@ -364,9 +491,100 @@ impl<'a> SocketAncillary<'a> {
 		)
 	}
 
+	/// Add IPv4 packet information to the ancillary data.
+	///
+	/// The function returns `true` if there was enough space in the buffer.
+	/// If there was not enough space then no packet information was appended.
+	/// Technically, that means this operation adds a control message with the level `IPPROTO_IP`
+	/// and type `IP_PKTINFO`.
+	///
+	/// This can be used to select the source address and outgoing interface of a sent packet,
+	/// for example to reply from the same address a request was received on.
+	#[cfg(target_os = "linux")]
+	pub fn add_ipv4_packet_info(&mut self, info: &crate::ip::Ipv4PacketInfo) -> bool {
+		self.truncated = false;
+		add_to_ancillary_data(
+			self.buffer,
+			&mut self.length,
+			&[info.into_raw()],
+			libc::IPPROTO_IP,
+			libc::IP_PKTINFO,
+		)
+	}
+
+	/// Add IPv6 packet information to the ancillary data.
+	///
+	/// The function returns `true` if there was enough space in the buffer.
+	/// If there was not enough space then no packet information was appended.
+	/// Technically, that means this operation adds a control message with the level `IPPROTO_IPV6`
+	/// and type `IPV6_PKTINFO`.
+	///
+	/// This can be used to select the source address and outgoing interface of a sent packet,
+	/// for example to reply from the same address a request was received on.
+	#[cfg(target_os = "linux")]
+	pub fn add_ipv6_packet_info(&mut self, info: &crate::ip::Ipv6PacketInfo) -> bool {
+		self.truncated = false;
+		add_to_ancillary_data(
+			self.buffer,
+			&mut self.length,
+			&[info.into_raw()],
+			libc::IPPROTO_IPV6,
+			libc::IPV6_PKTINFO,
+		)
+	}
+
+	/// Add a UDP segment size to the ancillary data.
+	///
+	/// The function returns `true` if there was enough space in the buffer.
+	/// If there was not enough space then no segment size was appended.
+	/// Technically, that means this operation adds a control message with the level `SOL_UDP`
+	/// and type `UDP_SEGMENT`.
+	///
+	/// This splits the sent buffer into datagrams of the segment size,
+	/// overriding the [`UDP_SEGMENT`](crate::Socket::set_udp_segment) socket option for a single message.
+	#[cfg(target_os = "linux")]
+	pub fn add_udp_segment(&mut self, segment_size: u16) -> bool {
+		self.truncated = false;
+		add_to_ancillary_data(
+			self.buffer,
+			&mut self.length,
+			&[segment_size],
+			libc::SOL_UDP,
+			libc::UDP_SEGMENT,
+		)
+	}
+
 	/// Clears the ancillary data, removing all values.
+	///
+	/// Received pidfds that are still in the buffer are closed.
 	pub fn clear(&mut self) {
+		#[cfg(any(target_os = "android", target_os = "linux"))]
+		self.close_pidfds();
 		self.length = 0;
 		self.truncated = false;
 	}
+
+	/// Close the file descriptors received in `SCM_PIDFD` control messages.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	fn close_pidfds(&mut self) {
+		let mut messages = self.messages();
+		while let Some(cmsg) = messages.next_header() {
+			if cmsg.cmsg_level != libc::SOL_SOCKET || cmsg.cmsg_type != crate::credentials::SCM_PIDFD {
+				continue;
+			}
+			unsafe {
+				let data_len = cmsg.cmsg_len - libc::CMSG_LEN(0) as CmsgLen;
+				if data_len as usize >= size_of::<RawFd>() {
+					libc::close(read_unaligned(libc::CMSG_DATA(cmsg) as *const RawFd));
+				}
+			}
+		}
+	}
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl Drop for SocketAncillary<'_> {
+	fn drop(&mut self) {
+		self.close_pidfds();
+	}
 }
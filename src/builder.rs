@@ -0,0 +1,194 @@
+use std::os::raw::c_int;
+
+use crate::error::{Operation, ResultExt};
+use crate::socket::{set_fd_nonblocking, socket};
+use crate::{AsSocketAddress, Socket};
+
+/// Builder to create and set up a [`Socket`] in one go.
+///
+/// The builder performs the setup in the right order:
+/// the socket is created with the requested flags, the options are set, the socket is bound and finally put in listening mode.
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use posix_socket::{Inet4SocketAddress, SocketBuilder};
+/// use std::net::Ipv4Addr;
+///
+/// let listener = SocketBuilder::new(libc::SOCK_STREAM, 0)
+///     .reuse_address(true)
+///     .bind(Inet4SocketAddress::new(&Ipv4Addr::UNSPECIFIED, 8080))
+///     .listen(128)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SocketBuilder<Address> {
+	domain: c_int,
+	kind: c_int,
+	protocol: c_int,
+	nonblocking: bool,
+	close_on_exec: bool,
+	reuse_address: Option<bool>,
+	reuse_port: Option<bool>,
+	send_buffer_size: Option<usize>,
+	recv_buffer_size: Option<usize>,
+	bind: Option<Address>,
+	listen: Option<c_int>,
+}
+
+impl<Address: AsSocketAddress> SocketBuilder<Address> {
+	/// Create a builder for a socket with the specified type and protocol.
+	///
+	/// The domain is taken from the `Address` type.
+	pub fn new(kind: impl Into<c_int>, protocol: impl Into<c_int>) -> Self
+	where
+		Address: crate::SpecificSocketAddress,
+	{
+		Self::new_generic(Address::static_family() as c_int, kind, protocol)
+	}
+
+	/// Create a builder for a socket with the specified domain, type and protocol.
+	///
+	/// Unless you are working with generic socket addresses,
+	/// you should normally prefer `Self::new`.
+	pub fn new_generic(domain: c_int, kind: impl Into<c_int>, protocol: impl Into<c_int>) -> Self {
+		Self {
+			domain,
+			kind: kind.into(),
+			protocol: protocol.into(),
+			nonblocking: false,
+			close_on_exec: true,
+			reuse_address: None,
+			reuse_port: None,
+			send_buffer_size: None,
+			recv_buffer_size: None,
+			bind: None,
+			listen: None,
+		}
+	}
+
+	/// Create the socket in non-blocking mode.
+	///
+	/// The flag will be set atomically when the socket is created if the platform supports it.
+	pub fn nonblocking(mut self, enable: bool) -> Self {
+		self.nonblocking = enable;
+		self
+	}
+
+	/// Set the `close-on-exec` flag on the socket.
+	///
+	/// This is enabled by default.
+	/// The flag will be set atomically when the socket is created if the platform supports it.
+	pub fn close_on_exec(mut self, enable: bool) -> Self {
+		self.close_on_exec = enable;
+		self
+	}
+
+	/// Set the `SO_REUSEADDR` option before binding the socket.
+	///
+	/// See [`Socket::set_reuse_address()`] for more information.
+	pub fn reuse_address(mut self, enable: bool) -> Self {
+		self.reuse_address = Some(enable);
+		self
+	}
+
+	/// Set the `SO_REUSEPORT` option before binding the socket.
+	///
+	/// See [`Socket::set_reuse_port()`] for more information.
+	#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+	pub fn reuse_port(mut self, enable: bool) -> Self {
+		self.reuse_port = Some(enable);
+		self
+	}
+
+	/// Set the size of the send buffer with the `SO_SNDBUF` option.
+	pub fn send_buffer_size(mut self, size: usize) -> Self {
+		self.send_buffer_size = Some(size);
+		self
+	}
+
+	/// Set the size of the receive buffer with the `SO_RCVBUF` option.
+	pub fn recv_buffer_size(mut self, size: usize) -> Self {
+		self.recv_buffer_size = Some(size);
+		self
+	}
+
+	/// Bind the socket to a local address.
+	pub fn bind(mut self, address: Address) -> Self {
+		self.bind = Some(address);
+		self
+	}
+
+	/// Put the socket in listening mode with the given backlog after binding it.
+	pub fn listen(mut self, backlog: c_int) -> Self {
+		self.listen = Some(backlog);
+		self
+	}
+
+	/// Create the socket and perform the setup.
+	pub fn build(self) -> std::io::Result<Socket<Address>> {
+		#[cfg(not(target_vendor = "apple"))]
+		let fd = {
+			let mut flags = 0;
+			if self.nonblocking {
+				flags |= libc::SOCK_NONBLOCK;
+			}
+			if self.close_on_exec {
+				flags |= libc::SOCK_CLOEXEC;
+			}
+
+			socket(self.domain, self.kind | flags, self.protocol)
+				.or_else(|e| {
+					// Fall back to setting the flags after creation if they are not supported.
+					if flags != 0 && e.raw_os_error() == Some(libc::EINVAL) {
+						self.create_and_set_flags()
+					} else {
+						Err(e)
+					}
+				})?
+		};
+
+		// Apple platforms do not support SOCK_NONBLOCK and SOCK_CLOEXEC at all.
+		#[cfg(target_vendor = "apple")]
+		let fd = self.create_and_set_flags()?;
+
+		let socket = Socket::wrap(fd)?;
+
+		if let Some(enable) = self.reuse_address {
+			socket.set_reuse_address(enable)
+				.context(Operation::SetOption { level: libc::SOL_SOCKET, name: libc::SO_REUSEADDR })?;
+		}
+		#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+		if let Some(enable) = self.reuse_port {
+			socket.set_reuse_port(enable)
+				.context(Operation::SetOption { level: libc::SOL_SOCKET, name: libc::SO_REUSEPORT })?;
+		}
+		if let Some(size) = self.send_buffer_size {
+			socket.set_send_buffer_size(size)
+				.context(Operation::SetOption { level: libc::SOL_SOCKET, name: libc::SO_SNDBUF })?;
+		}
+		if let Some(size) = self.recv_buffer_size {
+			socket.set_recv_buffer_size(size)
+				.context(Operation::SetOption { level: libc::SOL_SOCKET, name: libc::SO_RCVBUF })?;
+		}
+		if let Some(address) = &self.bind {
+			socket.bind(address).context_address(Operation::Bind, address)?;
+		}
+		if let Some(backlog) = self.listen {
+			socket.listen(backlog).context(Operation::Listen)?;
+		}
+		Ok(socket)
+	}
+
+	/// Create the socket without flags, and set the requested flags after creation.
+	fn create_and_set_flags(&self) -> std::io::Result<filedesc::FileDesc> {
+		let fd = socket(self.domain, self.kind, self.protocol)?;
+		if self.close_on_exec {
+			fd.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+		}
+		if self.nonblocking {
+			set_fd_nonblocking(fd.as_raw_fd()).context(Operation::SetNonblocking)?;
+		}
+		Ok(fd)
+	}
+}
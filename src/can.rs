@@ -0,0 +1,434 @@
+//! Controller Area Network (CAN) support.
+//!
+//! This module contains the types used to configure raw CAN sockets and ISO-TP sockets.
+//! The socket options themselves are available as functions on [`Socket`].
+//! The socket address for CAN sockets is [`CanSocketAddress`](crate::CanSocketAddress).
+//!
+//! CAN sockets are a non-portable Linux extension.
+
+use std::os::raw::c_int;
+use std::time::Duration;
+
+use crate::{AsSocketAddress, Socket};
+
+/// The socket option level for ISO-TP sockets.
+const SOL_CAN_ISOTP: c_int = libc::SOL_CAN_BASE + libc::CAN_ISOTP;
+
+/// Socket option for the general ISO-TP options (`struct can_isotp_options`).
+const CAN_ISOTP_OPTS: c_int = 1;
+
+/// Socket option for the flow control options (`struct can_isotp_fc_options`).
+const CAN_ISOTP_RECV_FC: c_int = 2;
+
+/// Socket option for the minimum separation time of transmitted consecutive frames.
+const CAN_ISOTP_TX_STMIN: c_int = 3;
+
+/// Socket option for the minimum separation time of received consecutive frames.
+const CAN_ISOTP_RX_STMIN: c_int = 4;
+
+/// Socket option for the link layer options (`struct can_isotp_ll_options`).
+const CAN_ISOTP_LL_OPTS: c_int = 5;
+
+/// An identifier filter for raw CAN sockets.
+///
+/// A received frame matches a regular filter if `frame_id & mask == id & mask`.
+/// A received frame matches an inverted filter if `frame_id & mask != id & mask`.
+///
+/// The identifier and mask may include the `CAN_EFF_FLAG` and `CAN_RTR_FLAG` bits
+/// to filter on extended frame format and remote transmission requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct CanFilter {
+	/// The CAN identifier to match, including the `CAN_INV_FILTER` bit for inverted filters.
+	id: u32,
+
+	/// The mask to apply to the received and filter identifiers before comparing them.
+	mask: u32,
+}
+
+impl CanFilter {
+	/// Create a filter that matches frames where `frame_id & mask == id & mask`.
+	pub fn new(id: u32, mask: u32) -> Self {
+		Self { id: id & !libc::CAN_INV_FILTER, mask }
+	}
+
+	/// Create an inverted filter that matches frames where `frame_id & mask != id & mask`.
+	pub fn new_inverted(id: u32, mask: u32) -> Self {
+		Self { id: id | libc::CAN_INV_FILTER, mask }
+	}
+
+	/// Get the CAN identifier of the filter.
+	///
+	/// The returned identifier does not include the `CAN_INV_FILTER` bit.
+	pub fn id(&self) -> u32 {
+		self.id & !libc::CAN_INV_FILTER
+	}
+
+	/// Get the mask of the filter.
+	pub fn mask(&self) -> u32 {
+		self.mask
+	}
+
+	/// Check if the filter is inverted.
+	pub fn is_inverted(&self) -> bool {
+		self.id & libc::CAN_INV_FILTER != 0
+	}
+}
+
+/// A set of CAN error classes, used to select which error frames are delivered to a raw CAN socket.
+///
+/// Error frames are only delivered to the socket if their error class is set in the mask.
+/// By default, no error frames are delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanErrorMask(u32);
+
+impl CanErrorMask {
+	/// No error classes.
+	pub const NONE: Self = Self(0);
+
+	/// Transmission timeout (`CAN_ERR_TX_TIMEOUT`).
+	pub const TX_TIMEOUT: Self = Self(libc::CAN_ERR_TX_TIMEOUT);
+
+	/// Lost arbitration (`CAN_ERR_LOSTARB`).
+	pub const LOST_ARBITRATION: Self = Self(libc::CAN_ERR_LOSTARB);
+
+	/// Controller problems (`CAN_ERR_CRTL`).
+	pub const CONTROLLER: Self = Self(libc::CAN_ERR_CRTL);
+
+	/// Protocol violations (`CAN_ERR_PROT`).
+	pub const PROTOCOL: Self = Self(libc::CAN_ERR_PROT);
+
+	/// Transceiver status (`CAN_ERR_TRX`).
+	pub const TRANSCEIVER: Self = Self(libc::CAN_ERR_TRX);
+
+	/// No acknowledgement received on transmission (`CAN_ERR_ACK`).
+	pub const NO_ACK: Self = Self(libc::CAN_ERR_ACK);
+
+	/// Bus off (`CAN_ERR_BUSOFF`).
+	pub const BUS_OFF: Self = Self(libc::CAN_ERR_BUSOFF);
+
+	/// Bus error (`CAN_ERR_BUSERROR`).
+	pub const BUS_ERROR: Self = Self(libc::CAN_ERR_BUSERROR);
+
+	/// Controller restarted (`CAN_ERR_RESTARTED`).
+	pub const RESTARTED: Self = Self(libc::CAN_ERR_RESTARTED);
+
+	/// Error counters available in the error frame (`CAN_ERR_CNT`).
+	pub const COUNTER: Self = Self(libc::CAN_ERR_CNT);
+
+	/// All error classes (`CAN_ERR_MASK`).
+	pub const ALL: Self = Self(libc::CAN_ERR_MASK);
+
+	/// Create an error mask from the raw bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the error mask.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if all error classes in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for CanErrorMask {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for CanErrorMask {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// Flags for the general options of an ISO-TP socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct IsoTpFlags(u32);
+
+impl IsoTpFlags {
+	/// No flags.
+	pub const NONE: Self = Self(0);
+
+	/// Listen only, do not send flow control frames (`CAN_ISOTP_LISTEN_MODE`).
+	pub const LISTEN_MODE: Self = Self(0x0001);
+
+	/// Enable extended addressing (`CAN_ISOTP_EXTEND_ADDR`).
+	pub const EXTEND_ADDR: Self = Self(0x0002);
+
+	/// Pad transmitted frames to 8 bytes (`CAN_ISOTP_TX_PADDING`).
+	pub const TX_PADDING: Self = Self(0x0004);
+
+	/// Expect received frames to be padded (`CAN_ISOTP_RX_PADDING`).
+	pub const RX_PADDING: Self = Self(0x0008);
+
+	/// Check the length of received padded frames (`CAN_ISOTP_CHK_PAD_LEN`).
+	pub const CHECK_PAD_LEN: Self = Self(0x0010);
+
+	/// Check the padding content of received frames (`CAN_ISOTP_CHK_PAD_DATA`).
+	pub const CHECK_PAD_DATA: Self = Self(0x0020);
+
+	/// Use half duplex error state handling (`CAN_ISOTP_HALF_DUPLEX`).
+	pub const HALF_DUPLEX: Self = Self(0x0040);
+
+	/// Ignore the separation time requested by the receiver and use the configured value (`CAN_ISOTP_FORCE_TXSTMIN`).
+	pub const FORCE_TX_STMIN: Self = Self(0x0080);
+
+	/// Ignore received consecutive frames that arrive faster than the configured separation time (`CAN_ISOTP_FORCE_RXSTMIN`).
+	pub const FORCE_RX_STMIN: Self = Self(0x0100);
+
+	/// Use a different extended address for received frames (`CAN_ISOTP_RX_EXT_ADDR`).
+	pub const RX_EXT_ADDR: Self = Self(0x0200);
+
+	/// Wait for the transmission to complete before returning from a send call (`CAN_ISOTP_WAIT_TX_DONE`).
+	pub const WAIT_TX_DONE: Self = Self(0x0400);
+
+	/// Send single frames without waiting for flow control, for 1-to-N broadcasts (`CAN_ISOTP_SF_BROADCAST`).
+	pub const SF_BROADCAST: Self = Self(0x0800);
+
+	/// Send consecutive frames without waiting for flow control, for 1-to-N broadcasts (`CAN_ISOTP_CF_BROADCAST`).
+	pub const CF_BROADCAST: Self = Self(0x1000);
+
+	/// Use the flow control parameters of the socket for received consecutive frames (`CAN_ISOTP_DYN_FC_PARMS`).
+	pub const DYN_FC_PARMS: Self = Self(0x2000);
+
+	/// Create a set of flags from the raw bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the flags.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if all flags in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for IsoTpFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for IsoTpFlags {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// General options for an ISO-TP socket.
+///
+/// This corresponds to `struct can_isotp_options` from the Linux kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct IsoTpOptions {
+	/// The flags for the socket.
+	pub flags: IsoTpFlags,
+
+	/// The time between transmitted frames in nanoseconds.
+	pub frame_txtime: u32,
+
+	/// The extended address to use when [`IsoTpFlags::EXTEND_ADDR`] is set.
+	pub ext_address: u8,
+
+	/// The byte used to pad transmitted frames when [`IsoTpFlags::TX_PADDING`] is set.
+	pub txpad_content: u8,
+
+	/// The expected padding byte of received frames when [`IsoTpFlags::CHECK_PAD_DATA`] is set.
+	pub rxpad_content: u8,
+
+	/// The extended address for received frames when [`IsoTpFlags::RX_EXT_ADDR`] is set.
+	pub rx_ext_address: u8,
+}
+
+/// Flow control options for an ISO-TP socket.
+///
+/// These are the values sent to the peer in flow control frames.
+/// This corresponds to `struct can_isotp_fc_options` from the Linux kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct IsoTpFlowControlOptions {
+	/// The number of consecutive frames the peer may send before waiting for a new flow control frame.
+	///
+	/// A value of 0 means the peer may send all frames without waiting.
+	pub block_size: u8,
+
+	/// The minimum separation time between consecutive frames, encoded as in the ISO-TP specification.
+	pub stmin: u8,
+
+	/// The maximum number of wait frames to send before aborting the transfer.
+	pub wftmax: u8,
+}
+
+/// Link layer options for an ISO-TP socket.
+///
+/// This corresponds to `struct can_isotp_ll_options` from the Linux kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct IsoTpLinkLayerOptions {
+	/// The maximum transmission unit of the CAN frames: 16 for classic CAN or 72 for CAN FD.
+	pub mtu: u8,
+
+	/// The data length of transmitted frames, in bytes.
+	pub tx_dl: u8,
+
+	/// The flags for transmitted CAN FD frames.
+	pub tx_flags: u8,
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Set the identifier filters of a raw CAN socket.
+	///
+	/// A frame is delivered to the socket if it matches any of the filters.
+	/// If the list of filters is empty, no frames will be delivered at all.
+	/// A new raw CAN socket has a single filter that matches all frames.
+	///
+	/// This sets the `CAN_RAW_FILTER` option.
+	/// See the Linux kernel documentation on SocketCAN for more information.
+	pub fn set_can_filters(&self, filters: &[CanFilter]) -> std::io::Result<()> {
+		self.set_option_slice(libc::SOL_CAN_RAW, libc::CAN_RAW_FILTER, filters)
+	}
+
+	/// Get the identifier filters of a raw CAN socket.
+	///
+	/// This gets the `CAN_RAW_FILTER` option.
+	/// See the Linux kernel documentation on SocketCAN for more information.
+	pub fn can_filters(&self) -> std::io::Result<Vec<CanFilter>> {
+		let mut filters = vec![CanFilter::new(0, 0); libc::CAN_RAW_FILTER_MAX as usize];
+		let count = self.get_option_slice(libc::SOL_CAN_RAW, libc::CAN_RAW_FILTER, &mut filters)?;
+		filters.truncate(count);
+		Ok(filters)
+	}
+
+	/// Set the error classes for which error frames are delivered to a raw CAN socket.
+	///
+	/// This sets the `CAN_RAW_ERR_FILTER` option.
+	/// See the Linux kernel documentation on SocketCAN for more information.
+	pub fn set_can_error_filter(&self, mask: CanErrorMask) -> std::io::Result<()> {
+		self.set_option(libc::SOL_CAN_RAW, libc::CAN_RAW_ERR_FILTER, mask.bits())
+	}
+
+	/// Get the error classes for which error frames are delivered to a raw CAN socket.
+	///
+	/// This gets the `CAN_RAW_ERR_FILTER` option.
+	/// See the Linux kernel documentation on SocketCAN for more information.
+	pub fn can_error_filter(&self) -> std::io::Result<CanErrorMask> {
+		let raw: u32 = self.get_option(libc::SOL_CAN_RAW, libc::CAN_RAW_ERR_FILTER)?;
+		Ok(CanErrorMask::from_bits(raw))
+	}
+
+	/// Set the general options of an ISO-TP socket.
+	///
+	/// The options must be set before the socket is bound.
+	///
+	/// This sets the `CAN_ISOTP_OPTS` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn set_isotp_options(&self, options: &IsoTpOptions) -> std::io::Result<()> {
+		self.set_option(SOL_CAN_ISOTP, CAN_ISOTP_OPTS, *options)
+	}
+
+	/// Get the general options of an ISO-TP socket.
+	///
+	/// This gets the `CAN_ISOTP_OPTS` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn isotp_options(&self) -> std::io::Result<IsoTpOptions> {
+		self.get_option(SOL_CAN_ISOTP, CAN_ISOTP_OPTS)
+	}
+
+	/// Set the flow control options of an ISO-TP socket.
+	///
+	/// The options must be set before the socket is bound.
+	///
+	/// This sets the `CAN_ISOTP_RECV_FC` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn set_isotp_flow_control(&self, options: &IsoTpFlowControlOptions) -> std::io::Result<()> {
+		self.set_option(SOL_CAN_ISOTP, CAN_ISOTP_RECV_FC, *options)
+	}
+
+	/// Get the flow control options of an ISO-TP socket.
+	///
+	/// This gets the `CAN_ISOTP_RECV_FC` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn isotp_flow_control(&self) -> std::io::Result<IsoTpFlowControlOptions> {
+		self.get_option(SOL_CAN_ISOTP, CAN_ISOTP_RECV_FC)
+	}
+
+	/// Set the link layer options of an ISO-TP socket.
+	///
+	/// The options must be set before the socket is bound.
+	///
+	/// This sets the `CAN_ISOTP_LL_OPTS` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn set_isotp_link_layer_options(&self, options: &IsoTpLinkLayerOptions) -> std::io::Result<()> {
+		self.set_option(SOL_CAN_ISOTP, CAN_ISOTP_LL_OPTS, *options)
+	}
+
+	/// Get the link layer options of an ISO-TP socket.
+	///
+	/// This gets the `CAN_ISOTP_LL_OPTS` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn isotp_link_layer_options(&self) -> std::io::Result<IsoTpLinkLayerOptions> {
+		self.get_option(SOL_CAN_ISOTP, CAN_ISOTP_LL_OPTS)
+	}
+
+	/// Set the minimum separation time for transmitted consecutive frames of an ISO-TP socket.
+	///
+	/// The separation time requested by the receiver is only overridden
+	/// if [`IsoTpFlags::FORCE_TX_STMIN`] is set in the general options.
+	///
+	/// This sets the `CAN_ISOTP_TX_STMIN` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn set_isotp_tx_stmin(&self, stmin: Duration) -> std::io::Result<()> {
+		self.set_option(SOL_CAN_ISOTP, CAN_ISOTP_TX_STMIN, duration_to_nanos_u32(stmin)?)
+	}
+
+	/// Get the minimum separation time for transmitted consecutive frames of an ISO-TP socket.
+	///
+	/// This gets the `CAN_ISOTP_TX_STMIN` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn isotp_tx_stmin(&self) -> std::io::Result<Duration> {
+		let nanos: u32 = self.get_option(SOL_CAN_ISOTP, CAN_ISOTP_TX_STMIN)?;
+		Ok(Duration::from_nanos(nanos.into()))
+	}
+
+	/// Set the minimum separation time for received consecutive frames of an ISO-TP socket.
+	///
+	/// Consecutive frames that arrive faster are ignored
+	/// if [`IsoTpFlags::FORCE_RX_STMIN`] is set in the general options.
+	///
+	/// This sets the `CAN_ISOTP_RX_STMIN` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn set_isotp_rx_stmin(&self, stmin: Duration) -> std::io::Result<()> {
+		self.set_option(SOL_CAN_ISOTP, CAN_ISOTP_RX_STMIN, duration_to_nanos_u32(stmin)?)
+	}
+
+	/// Get the minimum separation time for received consecutive frames of an ISO-TP socket.
+	///
+	/// This gets the `CAN_ISOTP_RX_STMIN` option.
+	/// See the Linux kernel documentation on ISO-TP for more information.
+	pub fn isotp_rx_stmin(&self) -> std::io::Result<Duration> {
+		let nanos: u32 = self.get_option(SOL_CAN_ISOTP, CAN_ISOTP_RX_STMIN)?;
+		Ok(Duration::from_nanos(nanos.into()))
+	}
+}
+
+/// Convert a duration to a number of nanoseconds that fits in a `u32`.
+fn duration_to_nanos_u32(duration: Duration) -> std::io::Result<u32> {
+	use std::convert::TryFrom;
+	u32::try_from(duration.as_nanos())
+		.map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "duration is too large, the maximum is u32::MAX nanoseconds"))
+}
@@ -0,0 +1,92 @@
+//! Build messages piecewise with the `MSG_MORE` flag.
+//!
+//! When data is sent with the `MSG_MORE` flag, the kernel holds it back until data is sent without the flag.
+//! This allows protocol encoders to write a message in multiple chunks,
+//! while still sending it as a single TCP segment or UDP datagram.
+//!
+//! The `MSG_MORE` flag is a non-portable Linux extension.
+
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Send data on the socket with the `MSG_MORE` flag.
+	///
+	/// The data is held back by the kernel until data is sent without the `MSG_MORE` flag.
+	/// For UDP sockets, all data sent with `MSG_MORE` is combined into a single datagram.
+	/// For TCP sockets, the data is combined into full segments.
+	///
+	/// See [`CorkedWriter`] for a convenient way to write a message in multiple chunks.
+	pub fn send_more(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
+		self.send(data, flags | libc::MSG_MORE)
+	}
+}
+
+/// Writer that sends all data with the `MSG_MORE` flag, until the final chunk is sent with [`Self::finish()`].
+///
+/// This can be used with any code that writes to a [`std::io::Write`] to build a message piecewise.
+/// Note that [`flush()`](std::io::Write::flush) does not send the held back data:
+/// the message is only completed by [`Self::finish()`].
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use posix_socket::Inet4Socket;
+/// use posix_socket::cork::CorkedWriter;
+/// use std::io::Write;
+///
+/// # let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0)?;
+/// let mut writer = CorkedWriter::new(&socket);
+/// writer.write_all(b"header")?;
+/// writer.write_all(b"body")?;
+/// writer.finish(b"trailer")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CorkedWriter<'a, Address> {
+	socket: &'a Socket<Address>,
+}
+
+impl<'a, Address: AsSocketAddress> CorkedWriter<'a, Address> {
+	/// Create a new corked writer for a connected socket.
+	pub fn new(socket: &'a Socket<Address>) -> Self {
+		Self { socket }
+	}
+
+	/// Send the final chunk of the message without the `MSG_MORE` flag.
+	///
+	/// This causes the kernel to send all held back data.
+	/// For UDP sockets, the final chunk may be empty.
+	/// For TCP sockets, an empty send does not push out the held back data,
+	/// so the final chunk should not be empty.
+	///
+	/// For stream sockets, this keeps sending until all data is sent.
+	pub fn finish(self, data: &[u8]) -> std::io::Result<()> {
+		let mut data = data;
+		loop {
+			let sent = self.socket.send(data, 0)?;
+			data = &data[sent..];
+			if data.is_empty() {
+				return Ok(());
+			}
+			if sent == 0 {
+				return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "failed to send the final chunk"));
+			}
+		}
+	}
+
+	/// Get the wrapped socket.
+	pub fn socket(&self) -> &'a Socket<Address> {
+		self.socket
+	}
+}
+
+impl<'a, Address: AsSocketAddress> std::io::Write for CorkedWriter<'a, Address> {
+	fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+		self.socket.send_more(data, 0)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
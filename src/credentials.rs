@@ -0,0 +1,198 @@
+//! Peer credentials of unix sockets.
+//!
+//! The kernel records the credentials of the peer process of a connected unix socket.
+//! These credentials can be used for authorization of clients on a unix socket control interface.
+
+use std::os::unix::io::RawFd;
+
+use crate::{Socket, UnixSocketAddress};
+
+/// Control message type for the security label of the sender (Linux only).
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const SCM_SECURITY: std::os::raw::c_int = 0x03;
+
+/// Control message type for a pidfd of the sender (Linux only).
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub const SCM_PIDFD: std::os::raw::c_int = 0x04;
+
+/// The credentials of the peer process of a unix socket.
+///
+/// The credentials are recorded by the kernel when the connection was made,
+/// or when the socket pair was created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerCredentials {
+	/// The effective user ID of the peer process.
+	pub uid: libc::uid_t,
+
+	/// The effective group ID of the peer process.
+	pub gid: libc::gid_t,
+
+	/// The process ID of the peer process, if the platform reports it.
+	///
+	/// Note that a process ID can be reused by a new process after the peer exits.
+	pub pid: Option<libc::pid_t>,
+}
+
+impl Socket<UnixSocketAddress> {
+	/// Get the credentials of the peer process of a connected unix socket.
+	///
+	/// This uses `SO_PEERCRED` on Linux and Android,
+	/// `getpeereid()` on macOS, iOS and the BSDs,
+	/// and `getpeerucred()` on Solaris and illumos.
+	/// The process ID is only reported on Linux, Android, Solaris and illumos.
+	pub fn peer_credentials(&self) -> std::io::Result<PeerCredentials> {
+		peer_credentials(self.as_raw_fd())
+	}
+
+	/// Get a pidfd for the peer process of a connected unix socket.
+	///
+	/// This uses the `SO_PEERPIDFD` option.
+	/// Unlike the process ID, a pidfd always refers to the same process, even after it exits.
+	/// The returned file descriptor has the close-on-exec flag set.
+	///
+	/// This function is only available on Linux and Android, and requires Linux 6.5 or later.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn peer_pidfd(&self) -> std::io::Result<filedesc::FileDesc> {
+		let fd: std::os::raw::c_int = self.get_option(libc::SOL_SOCKET, libc::SO_PEERPIDFD)?;
+		unsafe {
+			Ok(<filedesc::FileDesc as std::os::unix::io::FromRawFd>::from_raw_fd(fd))
+		}
+	}
+
+	/// Enable or disable reception of `SCM_PIDFD` control messages with the `SO_PASSPIDFD` option.
+	///
+	/// When enabled, each received message carries a pidfd for the sending process,
+	/// which is reported as [`AncillaryData::ScmPidfd`](crate::ancillary::AncillaryData::ScmPidfd).
+	///
+	/// This function is only available on Linux and Android, and requires Linux 6.5 or later.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_pass_pidfd(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_PASSPIDFD, std::os::raw::c_int::from(enable))
+	}
+
+	/// Check if reception of `SCM_PIDFD` control messages is enabled with the `SO_PASSPIDFD` option.
+	///
+	/// This function is only available on Linux and Android, and requires Linux 6.5 or later.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn pass_pidfd(&self) -> std::io::Result<bool> {
+		let value: std::os::raw::c_int = self.get_option(libc::SOL_SOCKET, libc::SO_PASSPIDFD)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `SCM_CREDENTIALS` control messages with the `SO_PASSCRED` option.
+	///
+	/// When enabled, each received message carries the credentials of the sending process,
+	/// which are reported as [`AncillaryData::ScmCredentials`](crate::ancillary::AncillaryData::ScmCredentials).
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_pass_credentials(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_PASSCRED, std::os::raw::c_int::from(enable))
+	}
+
+	/// Check if reception of `SCM_CREDENTIALS` control messages is enabled with the `SO_PASSCRED` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn pass_credentials(&self) -> std::io::Result<bool> {
+		let value: std::os::raw::c_int = self.get_option(libc::SOL_SOCKET, libc::SO_PASSCRED)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `SCM_SECURITY` control messages with the `SO_PASSSEC` option.
+	///
+	/// When enabled, each received message carries the security label of the sending process,
+	/// which is reported as [`AncillaryData::ScmSecurity`](crate::ancillary::AncillaryData::ScmSecurity).
+	/// The label is only sent if a Linux Security Module that supports it is active.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_pass_security(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_PASSSEC, std::os::raw::c_int::from(enable))
+	}
+
+	/// Check if reception of `SCM_SECURITY` control messages is enabled with the `SO_PASSSEC` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn pass_security(&self) -> std::io::Result<bool> {
+		let value: std::os::raw::c_int = self.get_option(libc::SOL_SOCKET, libc::SO_PASSSEC)?;
+		Ok(value != 0)
+	}
+
+	/// Get the security label of the peer process of a connected unix socket.
+	///
+	/// This uses the `SO_PEERSEC` option.
+	/// The format of the label depends on the active Linux Security Module, such as SELinux or AppArmor.
+	/// The raw label is returned as reported by the kernel, which may include a terminating null byte.
+	///
+	/// Returns an error if no security module is active.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn peer_security_label(&self) -> std::io::Result<Vec<u8>> {
+		let mut buffer = vec![0u8; 256];
+		loop {
+			let mut len = buffer.len() as libc::socklen_t;
+			let ret = unsafe {
+				libc::getsockopt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_PEERSEC, buffer.as_mut_ptr() as *mut _, &mut len)
+			};
+			if ret == 0 {
+				buffer.truncate(len as usize);
+				return Ok(buffer);
+			}
+			let error = std::io::Error::last_os_error();
+			if error.raw_os_error() == Some(libc::ERANGE) && len as usize > buffer.len() {
+				buffer.resize(len as usize, 0);
+			} else {
+				return Err(error);
+			}
+		}
+	}
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn peer_credentials(fd: RawFd) -> std::io::Result<PeerCredentials> {
+	unsafe {
+		let mut cred = std::mem::zeroed::<libc::ucred>();
+		let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+		crate::socket::check_ret(libc::getsockopt(fd, libc::SOL_SOCKET, libc::SO_PEERCRED, &mut cred as *mut _ as *mut _, &mut len))?;
+		Ok(PeerCredentials {
+			uid: cred.uid,
+			gid: cred.gid,
+			pid: Some(cred.pid),
+		})
+	}
+}
+
+#[cfg(any(
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "ios",
+	target_os = "macos",
+	target_os = "netbsd",
+	target_os = "openbsd",
+))]
+fn peer_credentials(fd: RawFd) -> std::io::Result<PeerCredentials> {
+	unsafe {
+		let mut uid = 0;
+		let mut gid = 0;
+		crate::socket::check_ret(libc::getpeereid(fd, &mut uid, &mut gid))?;
+		Ok(PeerCredentials { uid, gid, pid: None })
+	}
+}
+
+#[cfg(any(target_os = "illumos", target_os = "solaris"))]
+fn peer_credentials(fd: RawFd) -> std::io::Result<PeerCredentials> {
+	unsafe {
+		let mut cred = std::ptr::null_mut();
+		crate::socket::check_ret(libc::getpeerucred(fd, &mut cred))?;
+		let output = PeerCredentials {
+			uid: libc::ucred_geteuid(cred),
+			gid: libc::ucred_getegid(cred),
+			pid: Some(libc::ucred_getpid(cred)).filter(|&pid| pid != -1),
+		};
+		libc::ucred_free(cred);
+		Ok(output)
+	}
+}
@@ -0,0 +1,284 @@
+//! Minimal `epoll` event loop support.
+//!
+//! This module provides a small wrapper around the Linux `epoll` API,
+//! for applications that want to wait for events on a few sockets without pulling in a full async runtime.
+//!
+//! Sockets are registered with an [`Interest`] and a `u64` token.
+//! [`Epoll::wait()`] fills an [`Events`] buffer with the token and [`Readiness`] of each ready socket.
+//!
+//! This module is enabled with the `epoll` feature and only available on Linux.
+//! See `man 7 epoll` for more information.
+
+use std::os::raw::c_int;
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::time::Duration;
+
+use filedesc::FileDesc;
+
+use crate::poll::timeout_millis;
+use crate::socket::check_ret;
+use crate::{AsSocketAddress, Socket};
+
+/// The events to wait for on a registered socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct Interest(u32);
+
+impl Interest {
+	/// Wait for the socket to become readable (`EPOLLIN`).
+	pub const READABLE: Self = Self(libc::EPOLLIN as u32);
+
+	/// Wait for the socket to become writable (`EPOLLOUT`).
+	pub const WRITABLE: Self = Self(libc::EPOLLOUT as u32);
+
+	/// Wait for urgent data or other exceptional conditions (`EPOLLPRI`).
+	pub const PRIORITY: Self = Self(libc::EPOLLPRI as u32);
+
+	/// Wait for the peer to shut down the writing half of the connection (`EPOLLRDHUP`).
+	pub const READ_CLOSED: Self = Self(libc::EPOLLRDHUP as u32);
+
+	/// Only report state changes instead of the current state (`EPOLLET`).
+	pub const EDGE_TRIGGERED: Self = Self(libc::EPOLLET as u32);
+
+	/// Disable the registration after one event has been reported (`EPOLLONESHOT`).
+	///
+	/// Use [`Epoll::modify()`] to enable the registration again.
+	pub const ONESHOT: Self = Self(libc::EPOLLONESHOT as u32);
+
+	/// Create a set of flags from the raw bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the flags.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if all flags in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for Interest {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for Interest {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// The readiness of a socket as reported by [`Epoll::wait()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct Readiness(u32);
+
+impl Readiness {
+	/// Create a readiness value from the raw `epoll` event bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw `epoll` event bits.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if the socket is readable (`EPOLLIN`).
+	pub fn is_readable(self) -> bool {
+		self.has(libc::EPOLLIN)
+	}
+
+	/// Check if the socket is writable (`EPOLLOUT`).
+	pub fn is_writable(self) -> bool {
+		self.has(libc::EPOLLOUT)
+	}
+
+	/// Check if urgent data or another exceptional condition is available (`EPOLLPRI`).
+	pub fn is_priority(self) -> bool {
+		self.has(libc::EPOLLPRI)
+	}
+
+	/// Check if the peer shut down the writing half of the connection (`EPOLLRDHUP`).
+	pub fn is_read_closed(self) -> bool {
+		self.has(libc::EPOLLRDHUP)
+	}
+
+	/// Check if the socket has been hung up (`EPOLLHUP`).
+	///
+	/// This is always reported, even if it was not part of the registered [`Interest`].
+	pub fn is_hangup(self) -> bool {
+		self.has(libc::EPOLLHUP)
+	}
+
+	/// Check if an error is pending on the socket (`EPOLLERR`).
+	///
+	/// This is always reported, even if it was not part of the registered [`Interest`].
+	/// Use [`Socket::take_error()`] to retrieve the error.
+	pub fn is_error(self) -> bool {
+		self.has(libc::EPOLLERR)
+	}
+
+	fn has(self, flag: c_int) -> bool {
+		self.0 & flag as u32 != 0
+	}
+}
+
+/// A single event reported by [`Epoll::wait()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+	/// The token the socket was registered with.
+	pub token: u64,
+
+	/// The readiness of the socket.
+	pub readiness: Readiness,
+}
+
+/// A buffer for the events reported by [`Epoll::wait()`].
+pub struct Events {
+	events: Vec<libc::epoll_event>,
+}
+
+impl Events {
+	/// Create a buffer that can hold up to `capacity` events per call to [`Epoll::wait()`].
+	///
+	/// The capacity is raised to 1 if it is 0.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			events: Vec::with_capacity(capacity.max(1)),
+		}
+	}
+
+	/// Get the maximum number of events that can be reported at once.
+	pub fn capacity(&self) -> usize {
+		self.events.capacity()
+	}
+
+	/// Get the number of events reported by the last call to [`Epoll::wait()`].
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	/// Check if the last call to [`Epoll::wait()`] reported no events.
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	/// Iterate over the events reported by the last call to [`Epoll::wait()`].
+	pub fn iter(&self) -> impl Iterator<Item = Event> + '_ {
+		self.events.iter().map(|event| Event {
+			token: event.u64,
+			readiness: Readiness(event.events),
+		})
+	}
+
+	/// Remove all events from the buffer.
+	pub fn clear(&mut self) {
+		self.events.clear();
+	}
+}
+
+impl std::fmt::Debug for Events {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+/// An `epoll` instance.
+///
+/// The `epoll` file descriptor is created with the close-on-exec flag set.
+pub struct Epoll {
+	fd: FileDesc,
+}
+
+impl Epoll {
+	/// Create a new `epoll` instance.
+	pub fn new() -> std::io::Result<Self> {
+		unsafe {
+			let fd = check_ret(libc::epoll_create1(libc::EPOLL_CLOEXEC))?;
+			Ok(Self {
+				fd: FileDesc::from_raw_fd(fd),
+			})
+		}
+	}
+
+	/// Register a socket with the `epoll` instance.
+	///
+	/// The `token` is reported back by [`Self::wait()`] when the socket is ready.
+	/// The socket must be removed with [`Self::remove()`] before it is closed,
+	/// or it stays registered for as long as a duplicate of the file descriptor remains open.
+	pub fn add<Address: AsSocketAddress>(&self, socket: &Socket<Address>, token: u64, interest: Interest) -> std::io::Result<()> {
+		self.control(libc::EPOLL_CTL_ADD, socket.as_raw_fd(), token, interest)
+	}
+
+	/// Change the token and interest of a registered socket.
+	pub fn modify<Address: AsSocketAddress>(&self, socket: &Socket<Address>, token: u64, interest: Interest) -> std::io::Result<()> {
+		self.control(libc::EPOLL_CTL_MOD, socket.as_raw_fd(), token, interest)
+	}
+
+	/// Remove a socket from the `epoll` instance.
+	pub fn remove<Address: AsSocketAddress>(&self, socket: &Socket<Address>) -> std::io::Result<()> {
+		self.control(libc::EPOLL_CTL_DEL, socket.as_raw_fd(), 0, Interest::default())
+	}
+
+	/// Wait for events on the registered sockets.
+	///
+	/// The events are stored in `events`, replacing the events of a previous call.
+	/// At most [`Events::capacity()`] events are reported at once.
+	///
+	/// If `timeout` is `None`, this function blocks until at least one event is available.
+	/// Otherwise, it returns after the timeout expires, possibly with no events.
+	/// The timeout is rounded up to whole milliseconds.
+	///
+	/// Returns the number of reported events.
+	pub fn wait(&self, events: &mut Events, timeout: Option<Duration>) -> std::io::Result<usize> {
+		events.events.clear();
+		let capacity = events.events.capacity().min(c_int::MAX as usize);
+		unsafe {
+			let count = check_ret(libc::epoll_wait(
+				self.fd.as_raw_fd(),
+				events.events.as_mut_ptr(),
+				capacity as c_int,
+				timeout_millis(timeout),
+			))?;
+			events.events.set_len(count as usize);
+			Ok(count as usize)
+		}
+	}
+
+	fn control(&self, operation: c_int, fd: RawFd, token: u64, interest: Interest) -> std::io::Result<()> {
+		let mut event = libc::epoll_event {
+			events: interest.bits(),
+			u64: token,
+		};
+		unsafe {
+			check_ret(libc::epoll_ctl(self.fd.as_raw_fd(), operation, fd, &mut event))?;
+			Ok(())
+		}
+	}
+}
+
+impl std::fmt::Debug for Epoll {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_struct("Epoll").field("fd", &self.fd.as_raw_fd()).finish()
+	}
+}
+
+impl AsRawFd for Epoll {
+	fn as_raw_fd(&self) -> RawFd {
+		self.fd.as_raw_fd()
+	}
+}
+
+impl AsFd for Epoll {
+	fn as_fd(&self) -> BorrowedFd<'_> {
+		unsafe { BorrowedFd::borrow_raw(self.fd.as_raw_fd()) }
+	}
+}
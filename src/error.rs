@@ -0,0 +1,186 @@
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, SocketAddress};
+
+/// An error with context about the operation that failed.
+///
+/// Functions in this crate still return [`std::io::Error`],
+/// but errors from setup steps and fallback logic are wrapped in this type to make them easier to attribute.
+/// The [`ErrorKind`](std::io::ErrorKind) of the wrapped error is preserved.
+///
+/// Functions that map to a single system call, like [`Socket::connect()`](crate::Socket::connect) or [`Socket::bind()`](crate::Socket::bind),
+/// return the error of the system call unchanged.
+///
+/// Use [`Error::get()`] to retrieve the context from an [`std::io::Error`].
+/// Note that [`std::io::Error::raw_os_error()`] returns [`None`] for wrapped errors.
+/// Use [`Error::raw_os_error_of()`] to get the OS error code of both wrapped and plain errors.
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use posix_socket::{Inet4SocketAddress, SocketBuilder};
+/// use std::net::Ipv4Addr;
+///
+/// let result = SocketBuilder::new(libc::SOCK_STREAM, 0)
+///     .bind(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 80))
+///     .build();
+/// if let Err(e) = result {
+///     if let Some(context) = posix_socket::Error::get(&e) {
+///         eprintln!("{} failed", context.operation());
+///     }
+///     if posix_socket::Error::raw_os_error_of(&e) == Some(libc::EADDRINUSE) {
+///         eprintln!("address already in use");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Error {
+	operation: Operation,
+	address: Option<SocketAddress>,
+	source: std::io::Error,
+}
+
+/// The operation that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Operation {
+	/// Setting the `close-on-exec` flag.
+	SetCloseOnExec,
+
+	/// Setting the `O_NONBLOCK` flag.
+	SetNonblocking,
+
+	/// Setting a socket option with `setsockopt`.
+	SetOption {
+		/// The level of the socket option, like `SOL_SOCKET`.
+		level: c_int,
+
+		/// The name of the socket option, like `SO_REUSEADDR`.
+		name: c_int,
+	},
+
+	/// Binding the socket to a local address.
+	Bind,
+
+	/// Connecting the socket to a remote address.
+	Connect,
+
+	/// Putting the socket in listening mode.
+	Listen,
+
+	/// Sending a message with `sendmsg`.
+	SendMsg,
+
+	/// Converting an address returned by the kernel to the address type of the socket.
+	FinalizeAddress,
+}
+
+impl Error {
+	/// Create a new error for a failed operation.
+	pub fn new(operation: Operation, source: std::io::Error) -> Self {
+		Self { operation, address: None, source }
+	}
+
+	/// Create a new error for a failed operation involving an address.
+	pub fn with_address<Address: AsSocketAddress>(operation: Operation, address: &Address, source: std::io::Error) -> Self {
+		Self {
+			operation,
+			address: Some(SocketAddress::from_other(address)),
+			source,
+		}
+	}
+
+	/// Get the context of an [`std::io::Error`] returned by this crate, if it has any.
+	pub fn get(error: &std::io::Error) -> Option<&Self> {
+		error.get_ref()?.downcast_ref()
+	}
+
+	/// Get the operation that failed.
+	pub fn operation(&self) -> Operation {
+		self.operation
+	}
+
+	/// Get the address involved in the failed operation, if any.
+	pub fn address(&self) -> Option<&SocketAddress> {
+		self.address.as_ref()
+	}
+
+	/// Get the underlying error.
+	pub fn source_error(&self) -> &std::io::Error {
+		&self.source
+	}
+
+	/// Get the OS error code of the underlying error, if it has one.
+	pub fn raw_os_error(&self) -> Option<i32> {
+		self.source.raw_os_error()
+	}
+
+	/// Get the kind of the underlying error.
+	pub fn kind(&self) -> std::io::ErrorKind {
+		self.source.kind()
+	}
+
+	/// Get the OS error code of an [`std::io::Error`], even if it has been wrapped in an [`Error`].
+	pub fn raw_os_error_of(error: &std::io::Error) -> Option<i32> {
+		match Self::get(error) {
+			Some(context) => context.raw_os_error(),
+			None => error.raw_os_error(),
+		}
+	}
+}
+
+impl std::fmt::Display for Operation {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::SetCloseOnExec => write!(f, "setting close-on-exec flag"),
+			Self::SetNonblocking => write!(f, "setting non-blocking mode"),
+			Self::SetOption { level, name } => write!(f, "setsockopt(level {}, option {})", level, name),
+			Self::Bind => write!(f, "bind"),
+			Self::Connect => write!(f, "connect"),
+			Self::Listen => write!(f, "listen"),
+			Self::SendMsg => write!(f, "sendmsg"),
+			Self::FinalizeAddress => write!(f, "converting socket address"),
+		}
+	}
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match &self.address {
+			Some(address) => write!(f, "{} ({}) failed: {}", self.operation, address, self.source),
+			None => write!(f, "{} failed: {}", self.operation, self.source),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		Some(&self.source)
+	}
+}
+
+impl From<Error> for std::io::Error {
+	fn from(other: Error) -> Self {
+		Self::new(other.kind(), other)
+	}
+}
+
+/// Extension trait to add context to the errors of [`std::io::Result`].
+pub(crate) trait ResultExt<T> {
+	/// Wrap the error in an [`Error`] for the given operation.
+	fn context(self, operation: Operation) -> std::io::Result<T>;
+
+	/// Wrap the error in an [`Error`] for the given operation and address.
+	fn context_address<Address: AsSocketAddress>(self, operation: Operation, address: &Address) -> std::io::Result<T>;
+}
+
+impl<T> ResultExt<T> for std::io::Result<T> {
+	fn context(self, operation: Operation) -> std::io::Result<T> {
+		self.map_err(|e| Error::new(operation, e).into())
+	}
+
+	fn context_address<Address: AsSocketAddress>(self, operation: Operation, address: &Address) -> std::io::Result<T> {
+		self.map_err(|e| Error::with_address(operation, address, e).into())
+	}
+}
@@ -0,0 +1,251 @@
+//! Socket error queue support.
+//!
+//! When `IP_RECVERR` or `IPV6_RECVERR` is enabled on a socket,
+//! the kernel queues detailed error reports on the socket error queue.
+//! For example, UDP sockets receive a report for each ICMP error caused by a sent datagram,
+//! including the address of the host that sent the ICMP message.
+//!
+//! The error queue is also used for other notifications,
+//! such as [zero-copy completions](crate::zerocopy) and transmit timestamps.
+//!
+//! The error queue is a non-portable Linux extension.
+//! See `man 7 ip` and `man 7 ipv6` for more information.
+
+use std::os::raw::{c_int, c_void};
+
+use crate::ancillary::{CmsgLen, SocketAncillary};
+use crate::{AsSocketAddress, Socket, SocketAddress};
+
+/// The origin of an extended error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendedErrorOrigin {
+	/// No origin.
+	None,
+
+	/// The error was generated by the local network stack.
+	Local,
+
+	/// The error was caused by a received ICMP message.
+	Icmp,
+
+	/// The error was caused by a received ICMPv6 message.
+	Icmp6,
+
+	/// The error holds a transmit status report, such as a transmit timestamp.
+	///
+	/// This is also known as `SO_EE_ORIGIN_TIMESTAMPING`.
+	TxStatus,
+
+	/// The error holds a zero-copy completion notification.
+	Zerocopy,
+
+	/// The error reports a packet dropped because of an invalid transmit time.
+	TxTime,
+
+	/// An unknown origin.
+	Other(u8),
+}
+
+impl ExtendedErrorOrigin {
+	/// Get the origin from the raw `SO_EE_ORIGIN_*` value.
+	pub fn from_raw(origin: u8) -> Self {
+		match origin {
+			libc::SO_EE_ORIGIN_NONE => Self::None,
+			libc::SO_EE_ORIGIN_LOCAL => Self::Local,
+			libc::SO_EE_ORIGIN_ICMP => Self::Icmp,
+			libc::SO_EE_ORIGIN_ICMP6 => Self::Icmp6,
+			libc::SO_EE_ORIGIN_TXSTATUS => Self::TxStatus,
+			crate::zerocopy::SO_EE_ORIGIN_ZEROCOPY => Self::Zerocopy,
+			SO_EE_ORIGIN_TXTIME => Self::TxTime,
+			other => Self::Other(other),
+		}
+	}
+
+	/// Get the raw `SO_EE_ORIGIN_*` value of the origin.
+	pub fn to_raw(self) -> u8 {
+		match self {
+			Self::None => libc::SO_EE_ORIGIN_NONE,
+			Self::Local => libc::SO_EE_ORIGIN_LOCAL,
+			Self::Icmp => libc::SO_EE_ORIGIN_ICMP,
+			Self::Icmp6 => libc::SO_EE_ORIGIN_ICMP6,
+			Self::TxStatus => libc::SO_EE_ORIGIN_TXSTATUS,
+			Self::Zerocopy => crate::zerocopy::SO_EE_ORIGIN_ZEROCOPY,
+			Self::TxTime => SO_EE_ORIGIN_TXTIME,
+			Self::Other(other) => other,
+		}
+	}
+}
+
+/// The origin of extended errors that report packets dropped because of an invalid transmit time.
+const SO_EE_ORIGIN_TXTIME: u8 = 6;
+
+/// An extended error report from the socket error queue (`struct sock_extended_err`).
+///
+/// The report is delivered as `IP_RECVERR` or `IPV6_RECVERR` control message
+/// when receiving from the error queue with `MSG_ERRQUEUE`.
+#[derive(Clone)]
+pub struct ExtendedError {
+	inner: libc::sock_extended_err,
+	offender: Option<SocketAddress>,
+}
+
+impl ExtendedError {
+	/// Parse an extended error from the data of an `IP_RECVERR` or `IPV6_RECVERR` control message.
+	///
+	/// Returns an error if the data is too short.
+	pub fn parse(data: &[u8]) -> std::io::Result<Self> {
+		let header_len = std::mem::size_of::<libc::sock_extended_err>();
+		if data.len() < header_len {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "extended error control message too short"));
+		}
+
+		unsafe {
+			let inner = std::ptr::read_unaligned(data.as_ptr() as *const libc::sock_extended_err);
+
+			// The offending address follows the error, if the kernel included one.
+			let offender = &data[header_len..];
+			let mut address = std::mem::zeroed::<libc::sockaddr_storage>();
+			let len = offender.len().min(std::mem::size_of_val(&address));
+			std::ptr::copy_nonoverlapping(offender.as_ptr(), &mut address as *mut _ as *mut u8, len);
+			let offender = if len < std::mem::size_of::<libc::sa_family_t>() || c_int::from(address.ss_family) == libc::AF_UNSPEC {
+				None
+			} else {
+				Some(SocketAddress::from_raw(address, len as libc::socklen_t))
+			};
+
+			Ok(Self { inner, offender })
+		}
+	}
+
+	/// Get the raw `struct sock_extended_err`.
+	pub fn as_raw(&self) -> &libc::sock_extended_err {
+		&self.inner
+	}
+
+	/// Get the origin of the error.
+	pub fn origin(&self) -> ExtendedErrorOrigin {
+		ExtendedErrorOrigin::from_raw(self.inner.ee_origin)
+	}
+
+	/// Get the error number of the error, or 0 if the report does not represent an error.
+	pub fn errno(&self) -> c_int {
+		self.inner.ee_errno as c_int
+	}
+
+	/// Get the error as [`std::io::Error`], if the report represents an error.
+	pub fn error(&self) -> Option<std::io::Error> {
+		if self.inner.ee_errno == 0 {
+			None
+		} else {
+			Some(std::io::Error::from_raw_os_error(self.errno()))
+		}
+	}
+
+	/// Get the type of the ICMP message, for errors with an ICMP or ICMPv6 origin.
+	pub fn icmp_type(&self) -> u8 {
+		self.inner.ee_type
+	}
+
+	/// Get the code of the ICMP message, for errors with an ICMP or ICMPv6 origin.
+	pub fn icmp_code(&self) -> u8 {
+		self.inner.ee_code
+	}
+
+	/// Get the extra info of the error.
+	///
+	/// For `EMSGSIZE` errors, this holds the discovered path MTU.
+	pub fn info(&self) -> u32 {
+		self.inner.ee_info
+	}
+
+	/// Get the extra data of the error.
+	pub fn data(&self) -> u32 {
+		self.inner.ee_data
+	}
+
+	/// Get the address of the host that reported the error, if known.
+	///
+	/// For errors with an ICMP origin, this is the address of the sender of the ICMP message.
+	pub fn offender(&self) -> Option<&SocketAddress> {
+		self.offender.as_ref()
+	}
+}
+
+impl std::fmt::Debug for ExtendedError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_struct("ExtendedError")
+			.field("origin", &self.origin())
+			.field("errno", &self.inner.ee_errno)
+			.field("type", &self.inner.ee_type)
+			.field("code", &self.inner.ee_code)
+			.field("info", &self.inner.ee_info)
+			.field("data", &self.inner.ee_data)
+			.field("has_offender", &self.offender.is_some())
+			.finish()
+	}
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Enable or disable extended error reports on the error queue of an IPv4 socket.
+	pub fn set_ip_recv_error(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_RECVERR, c_int::from(enable))
+	}
+
+	/// Check if extended error reports are enabled for an IPv4 socket.
+	pub fn ip_recv_error(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_RECVERR)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable extended error reports on the error queue of an IPv6 socket.
+	pub fn set_ipv6_recv_error(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_RECVERR, c_int::from(enable))
+	}
+
+	/// Check if extended error reports are enabled for an IPv6 socket.
+	pub fn ipv6_recv_error(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_RECVERR)?;
+		Ok(value != 0)
+	}
+
+	/// Receive an extended error report from the socket error queue.
+	///
+	/// This never blocks: if the error queue is empty, `None` is returned.
+	///
+	/// The data of the packet that caused the error is discarded.
+	/// Use [`Self::recv_msg()`] with the `MSG_ERRQUEUE` flag to receive it too.
+	pub fn recv_error_queue(&self) -> std::io::Result<Option<ExtendedError>> {
+		// Use u64 for the buffer to get a properly aligned control message header.
+		let mut buffer = [0u64; 32];
+		let cdata: &mut [u8] = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, std::mem::size_of_val(&buffer)) };
+		let mut ancillary = SocketAncillary::new(cdata);
+		match self.recv_msg(&[], &mut ancillary, libc::MSG_ERRQUEUE) {
+			Ok(_) => (),
+			Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(None),
+			Err(e) => return Err(e),
+		}
+
+		unsafe {
+			let mut header = std::mem::zeroed::<libc::msghdr>();
+			header.msg_control = ancillary.buffer.as_mut_ptr() as *mut c_void;
+			header.msg_controllen = ancillary.len() as _;
+
+			let mut cmsg = libc::CMSG_FIRSTHDR(&header);
+			while let Some(current) = cmsg.as_ref() {
+				if is_extended_error(current.cmsg_level, current.cmsg_type) {
+					let data_len = current.cmsg_len - libc::CMSG_LEN(0) as CmsgLen;
+					let data = std::slice::from_raw_parts(libc::CMSG_DATA(current), data_len as usize);
+					return ExtendedError::parse(data).map(Some);
+				}
+				cmsg = libc::CMSG_NXTHDR(&header, current);
+			}
+		}
+
+		Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "no extended error in socket error queue message"))
+	}
+}
+
+/// Check if a control message holds an extended error.
+pub(crate) fn is_extended_error(cmsg_level: c_int, cmsg_type: c_int) -> bool {
+	matches!((cmsg_level, cmsg_type), (libc::SOL_IP, libc::IP_RECVERR) | (libc::SOL_IPV6, libc::IPV6_RECVERR))
+}
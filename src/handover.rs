@@ -0,0 +1,110 @@
+//! Hand over listening sockets to another process.
+//!
+//! Use [`Socket::send_listeners()`] and [`Socket::recv_listeners()`] to transfer listening sockets over a unix socket.
+//! This can be used to restart a server without closing the listening sockets,
+//! so that no incoming connections are refused during the restart.
+//!
+//! The sockets are transferred with an `SCM_RIGHTS` control message,
+//! together with the domain and type of each socket.
+//! The receiving side checks that the received sockets still match the domain and type,
+//! that they are listening for connections, and that they have the expected address type.
+//!
+//! Use a `SOCK_SEQPACKET` or `SOCK_DGRAM` unix socket to transfer the listeners,
+//! so that the information for all sockets is received in a single message.
+
+use std::io::{IoSlice, IoSliceMut};
+use std::os::raw::c_int;
+
+use crate::ancillary::{AncillaryData, SocketAncillary};
+use crate::error::{Operation, ResultExt};
+use crate::{AsSocketAddress, Socket, UnixSocketAddress};
+
+/// The size of the information sent for each listener: the domain and the type as native endian `c_int`.
+const LISTENER_INFO_LEN: usize = 2 * std::mem::size_of::<c_int>();
+
+impl Socket<UnixSocketAddress> {
+	/// Send listening sockets to the peer of this unix socket.
+	///
+	/// The sockets remain open in this process.
+	/// You can close them once the peer has received them.
+	///
+	/// Returns an error if any of the sockets is not listening for connections.
+	pub fn send_listeners<Address: AsSocketAddress>(&self, listeners: &[&Socket<Address>]) -> std::io::Result<()> {
+		if listeners.is_empty() {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "no listeners to send"));
+		}
+
+		let mut info = Vec::with_capacity(listeners.len() * LISTENER_INFO_LEN);
+		let mut fds = Vec::with_capacity(listeners.len());
+		for listener in listeners {
+			if !listener.is_listening()? {
+				return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket is not listening for connections"));
+			}
+			info.extend_from_slice(&listener.domain()?.to_ne_bytes());
+			info.extend_from_slice(&listener.kind()?.to_ne_bytes());
+			fds.push(listener.as_raw_fd());
+		}
+
+		let mut cdata = vec![0u8; cmsg_space(fds.len())];
+		let mut ancillary = SocketAncillary::new(&mut cdata);
+		if !ancillary.add_fds(&fds) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "too many listeners to send"));
+		}
+
+		let sent = self.send_msg(&[IoSlice::new(&info)], Some(ancillary.data()), 0).context(Operation::SendMsg)?;
+		if sent != info.len() {
+			return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "failed to send all listener information"));
+		}
+		Ok(())
+	}
+
+	/// Receive listening sockets sent by the peer with [`send_listeners()`](Socket::send_listeners).
+	///
+	/// At most `max` sockets can be received.
+	/// If the peer sent more sockets, the extra sockets are discarded by the kernel and an error is returned.
+	///
+	/// Each received socket is checked against the domain and type reported by the peer.
+	/// The socket must also be listening for connections and its local address must be valid for the `Address` type.
+	/// If any of the checks fail, all received sockets are closed and an error is returned.
+	pub fn recv_listeners<Address: AsSocketAddress>(&self, max: usize) -> std::io::Result<Vec<Socket<Address>>> {
+		let mut info = vec![0u8; max * LISTENER_INFO_LEN];
+		let mut cdata = vec![0u8; cmsg_space(max)];
+		let mut ancillary = SocketAncillary::new(&mut cdata);
+		let received = self.recv_msg(&[IoSliceMut::new(&mut info)], &mut ancillary, 0)?;
+		let len = received.byte_count;
+
+		// Take ownership of the received file descriptors first, so they are closed on error.
+		let mut listeners = Vec::new();
+		for message in ancillary.messages() {
+			if let Ok(AncillaryData::ScmRights(fds)) = message {
+				listeners.extend(fds.map(|fd| unsafe { Socket::<Address>::from_raw_fd(fd) }));
+			}
+		}
+
+		if received.ctruncated || received.truncated {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "received more listeners than requested"));
+		}
+		if len == 0 && listeners.is_empty() {
+			return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "connection closed before receiving listeners"));
+		}
+		if len != listeners.len() * LISTENER_INFO_LEN {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "received listener information does not match the received sockets"));
+		}
+
+		for (listener, info) in listeners.iter().zip(info[..len].chunks_exact(LISTENER_INFO_LEN)) {
+			let (domain, kind) = info.split_at(LISTENER_INFO_LEN / 2);
+			let domain = c_int::from_ne_bytes([domain[0], domain[1], domain[2], domain[3]]);
+			let kind = c_int::from_ne_bytes([kind[0], kind[1], kind[2], kind[3]]);
+			listener.check_domain_and_type(domain, kind, true)?;
+			listener.local_addr()?;
+		}
+
+		Ok(listeners)
+	}
+}
+
+/// Get the size of the control message buffer needed for `count` file descriptors.
+fn cmsg_space(count: usize) -> usize {
+	let data_len = count * std::mem::size_of::<c_int>();
+	unsafe { libc::CMSG_SPACE(data_len as _) as usize }
+}
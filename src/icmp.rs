@@ -0,0 +1,280 @@
+//! ICMP echo (ping) support.
+//!
+//! Use [`Socket::new_ping()`] to create an unprivileged ICMP socket,
+//! and [`EchoHeader`] to encode and decode echo requests and replies.
+//!
+//! Unprivileged ICMP sockets use `SOCK_DGRAM` with `IPPROTO_ICMP` or `IPPROTO_ICMPV6`.
+//! They are supported on Linux and macOS.
+//! On Linux, the group ID of the process must be in the range configured in `/proc/sys/net/ipv4/ping_group_range`,
+//! or creating the socket fails with `EACCES`.
+//! The same setting is also used for ICMPv6 sockets.
+//!
+//! On Linux, the kernel fills in the checksum for unprivileged ICMP sockets
+//! and replaces the identifier with the local port of the socket.
+//! Replies are only delivered to the socket with a matching identifier.
+//!
+//! For raw ICMPv6 sockets, use [`Icmp6Filter`] with [`Socket::set_icmp6_filter()`] to select which message types are received.
+
+use crate::{Inet4SocketAddress, Inet6SocketAddress, Protocol, Socket, SocketType};
+
+/// The ICMPv4 type of an echo reply.
+pub const ECHO_REPLY_V4: u8 = 0;
+
+/// The ICMPv4 type of an echo request.
+pub const ECHO_REQUEST_V4: u8 = 8;
+
+/// The ICMPv6 type of an echo request.
+pub const ECHO_REQUEST_V6: u8 = 128;
+
+/// The ICMPv6 type of an echo reply.
+pub const ECHO_REPLY_V6: u8 = 129;
+
+/// The header of an ICMP or ICMPv6 echo request or reply.
+///
+/// The header is followed by an arbitrary payload, which is copied into the reply by the peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EchoHeader {
+	/// The ICMP type of the message.
+	pub kind: u8,
+
+	/// The ICMP code of the message, always 0 for echo messages.
+	pub code: u8,
+
+	/// The checksum of the message.
+	pub checksum: u16,
+
+	/// The identifier of the echo request.
+	pub identifier: u16,
+
+	/// The sequence number of the echo request.
+	pub sequence: u16,
+}
+
+impl EchoHeader {
+	/// The size of an encoded echo header in bytes.
+	pub const LEN: usize = 8;
+
+	/// Create the header for an ICMPv4 echo request.
+	///
+	/// The checksum is left at zero.
+	pub fn request_v4(identifier: u16, sequence: u16) -> Self {
+		Self { kind: ECHO_REQUEST_V4, code: 0, checksum: 0, identifier, sequence }
+	}
+
+	/// Create the header for an ICMPv6 echo request.
+	///
+	/// The checksum is left at zero.
+	pub fn request_v6(identifier: u16, sequence: u16) -> Self {
+		Self { kind: ECHO_REQUEST_V6, code: 0, checksum: 0, identifier, sequence }
+	}
+
+	/// Check if the header is an ICMPv4 or ICMPv6 echo reply.
+	pub fn is_reply(&self) -> bool {
+		self.kind == ECHO_REPLY_V4 || self.kind == ECHO_REPLY_V6
+	}
+
+	/// Encode the header in network byte order.
+	pub fn to_bytes(&self) -> [u8; Self::LEN] {
+		let checksum = self.checksum.to_be_bytes();
+		let identifier = self.identifier.to_be_bytes();
+		let sequence = self.sequence.to_be_bytes();
+		[
+			self.kind,
+			self.code,
+			checksum[0],
+			checksum[1],
+			identifier[0],
+			identifier[1],
+			sequence[0],
+			sequence[1],
+		]
+	}
+
+	/// Decode a header from the start of a message.
+	///
+	/// Returns [`None`] if the message is too short.
+	pub fn from_bytes(data: &[u8]) -> Option<Self> {
+		if data.len() < Self::LEN {
+			return None;
+		}
+		Some(Self {
+			kind: data[0],
+			code: data[1],
+			checksum: u16::from_be_bytes([data[2], data[3]]),
+			identifier: u16::from_be_bytes([data[4], data[5]]),
+			sequence: u16::from_be_bytes([data[6], data[7]]),
+		})
+	}
+
+	/// Encode a full message with the header and payload.
+	///
+	/// The checksum of the message is computed over the header and payload, ignoring the checksum field of `self`.
+	/// For ICMPv6, the checksum also covers a pseudo header with the IP addresses,
+	/// so the computed checksum is only correct for ICMPv4.
+	/// The kernel fills in the checksum for ICMPv6 sockets anyway.
+	pub fn encode(&self, payload: &[u8]) -> Vec<u8> {
+		let mut header = *self;
+		header.checksum = 0;
+
+		let mut message = Vec::with_capacity(Self::LEN + payload.len());
+		message.extend_from_slice(&header.to_bytes());
+		message.extend_from_slice(payload);
+
+		let checksum = checksum(&message).to_be_bytes();
+		message[2] = checksum[0];
+		message[3] = checksum[1];
+		message
+	}
+
+	/// Decode a message into the header and payload.
+	///
+	/// Returns [`None`] if the message is too short.
+	pub fn decode(data: &[u8]) -> Option<(Self, &[u8])> {
+		let header = Self::from_bytes(data)?;
+		Some((header, &data[Self::LEN..]))
+	}
+}
+
+/// Compute the Internet checksum (RFC 1071) of a message.
+///
+/// If the checksum field of the message is zero, the result is the value to put in the checksum field.
+/// If the checksum field is filled in, the result is zero for a valid message.
+pub fn checksum(data: &[u8]) -> u16 {
+	let mut sum: u32 = 0;
+	let mut chunks = data.chunks_exact(2);
+	for chunk in &mut chunks {
+		sum += u32::from(u16::from_be_bytes([chunk[0], chunk[1]]));
+	}
+	if let [last] = chunks.remainder() {
+		sum += u32::from(*last) << 8;
+	}
+	while sum > 0xFFFF {
+		sum = (sum & 0xFFFF) + (sum >> 16);
+	}
+	!(sum as u16)
+}
+
+/// A filter for the ICMPv6 message types received on a raw ICMPv6 socket.
+///
+/// Raw ICMPv6 sockets receive all ICMPv6 messages by default.
+/// Set a filter with [`Socket::set_icmp6_filter()`] to receive only the message types you are interested in.
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use posix_socket::Inet6Socket;
+/// use posix_socket::icmp::{Icmp6Filter, ECHO_REPLY_V6};
+///
+/// let socket = Inet6Socket::new(libc::SOCK_RAW, libc::IPPROTO_ICMPV6)?;
+/// socket.set_icmp6_filter(&Icmp6Filter::block_all().pass(ECHO_REPLY_V6))?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Icmp6Filter {
+	/// Bitmap of the message types that are passed, indexed by type.
+	pass: [u32; 8],
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+impl Icmp6Filter {
+	/// Create a filter that passes all message types.
+	pub fn pass_all() -> Self {
+		Self { pass: [!0; 8] }
+	}
+
+	/// Create a filter that blocks all message types.
+	pub fn block_all() -> Self {
+		Self { pass: [0; 8] }
+	}
+
+	/// Pass messages of the given type.
+	pub fn pass(mut self, kind: u8) -> Self {
+		self.pass[usize::from(kind >> 5)] |= 1 << (kind & 31);
+		self
+	}
+
+	/// Block messages of the given type.
+	pub fn block(mut self, kind: u8) -> Self {
+		self.pass[usize::from(kind >> 5)] &= !(1 << (kind & 31));
+		self
+	}
+
+	/// Check if messages of the given type will pass the filter.
+	pub fn will_pass(&self, kind: u8) -> bool {
+		self.pass[usize::from(kind >> 5)] & (1 << (kind & 31)) != 0
+	}
+
+	/// Convert the filter to the raw `struct icmp6_filter` bitmap.
+	///
+	/// On Linux, a set bit blocks a message type.
+	/// On other platforms, a set bit passes a message type.
+	fn to_raw(self) -> [u32; 8] {
+		if cfg!(any(target_os = "linux", target_os = "android")) {
+			let mut raw = self.pass;
+			for word in &mut raw {
+				*word = !*word;
+			}
+			raw
+		} else {
+			self.pass
+		}
+	}
+
+	/// Create a filter from the raw `struct icmp6_filter` bitmap.
+	fn from_raw(raw: [u32; 8]) -> Self {
+		let mut filter = Self { pass: raw };
+		if cfg!(any(target_os = "linux", target_os = "android")) {
+			for word in &mut filter.pass {
+				*word = !*word;
+			}
+		}
+		filter
+	}
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+impl Default for Icmp6Filter {
+	fn default() -> Self {
+		Self::pass_all()
+	}
+}
+
+/// The `ICMP6_FILTER` socket option at the `IPPROTO_ICMPV6` level.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const ICMP6_FILTER: std::os::raw::c_int = 1;
+
+/// The `ICMP6_FILTER` socket option at the `IPPROTO_ICMPV6` level.
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+const ICMP6_FILTER: std::os::raw::c_int = 18;
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+impl<Address: crate::AsSocketAddress> Socket<Address> {
+	/// Set the ICMPv6 message filter of a raw ICMPv6 socket with the `ICMP6_FILTER` option.
+	pub fn set_icmp6_filter(&self, filter: &Icmp6Filter) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_ICMPV6, ICMP6_FILTER, filter.to_raw())
+	}
+
+	/// Get the ICMPv6 message filter of a raw ICMPv6 socket with the `ICMP6_FILTER` option.
+	pub fn icmp6_filter(&self) -> std::io::Result<Icmp6Filter> {
+		self.get_option(libc::IPPROTO_ICMPV6, ICMP6_FILTER).map(Icmp6Filter::from_raw)
+	}
+}
+
+impl Socket<Inet4SocketAddress> {
+	/// Create an unprivileged ICMP socket for sending echo requests.
+	///
+	/// See the [module documentation](crate::icmp) for the restrictions on unprivileged ICMP sockets.
+	pub fn new_ping() -> std::io::Result<Self> {
+		Self::new(SocketType::Datagram, Protocol::ICMP)
+	}
+}
+
+impl Socket<Inet6SocketAddress> {
+	/// Create an unprivileged ICMPv6 socket for sending echo requests.
+	///
+	/// See the [module documentation](crate::icmp) for the restrictions on unprivileged ICMP sockets.
+	pub fn new_ping() -> std::io::Result<Self> {
+		Self::new(SocketType::Datagram, Protocol::ICMPV6)
+	}
+}
@@ -0,0 +1,68 @@
+//! Network interface names and indices.
+//!
+//! Interface indices are used for multicast group membership, IPv6 scope IDs and binding sockets to an interface.
+
+use std::ffi::{CStr, CString};
+
+/// A network interface, as listed by [`list()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+	/// The index of the interface.
+	pub index: u32,
+
+	/// The name of the interface.
+	pub name: String,
+}
+
+/// Get the index of a network interface from the name with `if_nametoindex`.
+///
+/// See `man if_nametoindex` for more information.
+pub fn name_to_index(name: &str) -> std::io::Result<u32> {
+	let name = CString::new(name)
+		.map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name contains a null byte"))?;
+	let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+	if index == 0 {
+		Err(std::io::Error::last_os_error())
+	} else {
+		Ok(index)
+	}
+}
+
+/// Get the name of a network interface from the index with `if_indextoname`.
+///
+/// See `man if_indextoname` for more information.
+pub fn index_to_name(index: u32) -> std::io::Result<String> {
+	let mut buffer = [0 as std::os::raw::c_char; libc::IF_NAMESIZE];
+	unsafe {
+		if libc::if_indextoname(index, buffer.as_mut_ptr()).is_null() {
+			return Err(std::io::Error::last_os_error());
+		}
+		let name = CStr::from_ptr(buffer.as_ptr());
+		Ok(name.to_string_lossy().into_owned())
+	}
+}
+
+/// List all network interfaces with `if_nameindex`.
+///
+/// See `man if_nameindex` for more information.
+pub fn list() -> std::io::Result<Vec<Interface>> {
+	unsafe {
+		let head = libc::if_nameindex();
+		if head.is_null() {
+			return Err(std::io::Error::last_os_error());
+		}
+
+		let mut output = Vec::new();
+		let mut entry = head;
+		while (*entry).if_index != 0 && !(*entry).if_name.is_null() {
+			output.push(Interface {
+				index: (*entry).if_index,
+				name: CStr::from_ptr((*entry).if_name).to_string_lossy().into_owned(),
+			});
+			entry = entry.add(1);
+		}
+
+		libc::if_freenameindex(head);
+		Ok(output)
+	}
+}
@@ -0,0 +1,422 @@
+//! `io_uring` support.
+//!
+//! This module provides helpers to submit socket operations to an [`io_uring`](::io_uring) instance.
+//! It is enabled with the `io-uring` feature and only available on Linux.
+//!
+//! Each operation is represented by a type that owns all buffers used by the kernel.
+//! The buffers are allocated on the heap, so the operation can be moved freely after it has been submitted.
+//! The [`entry()`](Accept::entry) function of an operation creates the submission queue entry,
+//! and the `finish()` function converts the result of the completion queue entry.
+//!
+//! The operation must be kept alive until the kernel has posted the completion queue entry.
+//! Dropping an operation earlier lets the kernel write to freed memory.
+//! This is part of the safety contract of [`SubmissionQueue::push()`](::io_uring::SubmissionQueue::push).
+
+use std::mem::MaybeUninit;
+use std::os::raw::c_int;
+
+use ::io_uring::{opcode, squeue, types};
+
+use crate::{AsSocketAddress, Socket};
+
+/// Convert the result of a completion queue entry to an [`std::io::Result`].
+fn check_result(result: i32) -> std::io::Result<u32> {
+	if result < 0 {
+		Err(std::io::Error::from_raw_os_error(-result))
+	} else {
+		Ok(result as u32)
+	}
+}
+
+/// Create a socket for a file descriptor returned by an accept operation.
+fn accepted_socket<Address: AsSocketAddress>(result: i32) -> std::io::Result<Socket<Address>> {
+	let fd = check_result(result)?;
+	unsafe {
+		Ok(Socket::from_raw_fd(fd as c_int))
+	}
+}
+
+/// Accept a new connection on a listening socket.
+///
+/// The accepted socket has the `close-on-exec` flag set.
+pub struct Accept<Address: AsSocketAddress> {
+	address: Box<(MaybeUninit<Address>, libc::socklen_t)>,
+}
+
+impl<Address: AsSocketAddress> Accept<Address> {
+	/// Create a new accept operation.
+	pub fn new() -> Self {
+		Self {
+			address: Box::new((MaybeUninit::zeroed(), Address::max_len())),
+		}
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		let (address, len) = &mut *self.address;
+		opcode::Accept::new(types::Fd(socket.as_raw_fd()), Address::as_sockaddr_mut(address), len)
+			.flags(libc::SOCK_CLOEXEC)
+			.build()
+	}
+
+	/// Get the accepted socket and the address of the peer from the result of the completion queue entry.
+	pub fn finish(self, result: i32) -> std::io::Result<(Socket<Address>, Address)> {
+		let socket = accepted_socket(result)?;
+		let (address, len) = *self.address;
+		let address = Address::finalize(address, len)?;
+		Ok((socket, address))
+	}
+}
+
+impl<Address: AsSocketAddress> Default for Accept<Address> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Create a submission queue entry to accept connections on a listening socket until the operation is cancelled.
+///
+/// A completion queue entry is posted for every accepted connection.
+/// Use [`::io_uring::cqueue::more()`] on the flags of the completion queue entry to check if more completions will follow.
+/// The operation does not report the peer address.
+///
+/// The accepted sockets have the `close-on-exec` flag set.
+///
+/// This requires Linux 5.19 or later.
+pub fn accept_multishot<Address: AsSocketAddress>(socket: &Socket<Address>) -> squeue::Entry {
+	opcode::AcceptMulti::new(types::Fd(socket.as_raw_fd()))
+		.flags(libc::SOCK_CLOEXEC)
+		.build()
+}
+
+/// Get the accepted socket from the result of a completion queue entry of [`accept_multishot()`].
+pub fn finish_accept_multishot<Address: AsSocketAddress>(result: i32) -> std::io::Result<Socket<Address>> {
+	accepted_socket(result)
+}
+
+/// Connect a socket to a remote address.
+pub struct Connect<Address: AsSocketAddress> {
+	address: Box<Address>,
+}
+
+impl<Address: AsSocketAddress> Connect<Address> {
+	/// Create a new connect operation.
+	pub fn new(address: Address) -> Self {
+		Self {
+			address: Box::new(address),
+		}
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		opcode::Connect::new(types::Fd(socket.as_raw_fd()), self.address.as_sockaddr(), self.address.len()).build()
+	}
+
+	/// Check the result of the completion queue entry.
+	pub fn finish(self, result: i32) -> std::io::Result<()> {
+		check_result(result)?;
+		Ok(())
+	}
+}
+
+/// Send data on a connected socket.
+pub struct Send {
+	data: Vec<u8>,
+	flags: c_int,
+}
+
+impl Send {
+	/// Create a new send operation.
+	///
+	/// The flags are the same as for [`Socket::send()`].
+	pub fn new(data: Vec<u8>, flags: c_int) -> Self {
+		Self { data, flags }
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry<Address: AsSocketAddress>(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		opcode::Send::new(types::Fd(socket.as_raw_fd()), self.data.as_ptr(), self.data.len() as u32)
+			.flags(self.flags)
+			.build()
+	}
+
+	/// Get the number of bytes sent from the result of the completion queue entry.
+	///
+	/// The data buffer is returned so it can be reused.
+	pub fn finish(self, result: i32) -> (Vec<u8>, std::io::Result<usize>) {
+		(self.data, check_result(result).map(|len| len as usize))
+	}
+}
+
+/// Receive data from a connected socket.
+pub struct Recv {
+	buffer: Vec<u8>,
+	flags: c_int,
+}
+
+impl Recv {
+	/// Create a new receive operation that receives at most `buffer.len()` bytes.
+	///
+	/// The flags are the same as for [`Socket::recv()`].
+	pub fn new(buffer: Vec<u8>, flags: c_int) -> Self {
+		Self { buffer, flags }
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry<Address: AsSocketAddress>(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		opcode::Recv::new(types::Fd(socket.as_raw_fd()), self.buffer.as_mut_ptr(), self.buffer.len() as u32)
+			.flags(self.flags)
+			.build()
+	}
+
+	/// Get the received data from the result of the completion queue entry.
+	///
+	/// On success, the buffer is truncated to the received data.
+	pub fn finish(mut self, result: i32) -> std::io::Result<Vec<u8>> {
+		let len = check_result(result)?;
+		self.buffer.truncate(len as usize);
+		Ok(self.buffer)
+	}
+}
+
+/// The heap allocated state of a [`SendMsg`] or [`RecvMsg`] operation.
+struct MsgState<Address> {
+	header: libc::msghdr,
+	iov: libc::iovec,
+	address: MaybeUninit<Address>,
+}
+
+/// Send a message with optional destination address and ancillary data.
+pub struct SendMsg<Address> {
+	state: Box<MsgState<Address>>,
+	data: Vec<u8>,
+	cdata: Vec<u8>,
+	flags: c_int,
+}
+
+impl<Address: AsSocketAddress> SendMsg<Address> {
+	/// Create a new operation to send a message on a connected socket.
+	///
+	/// The flags are the same as for [`Socket::send_msg()`].
+	pub fn new(data: Vec<u8>, flags: c_int) -> Self {
+		Self {
+			state: Box::new(MsgState {
+				header: unsafe { std::mem::zeroed() },
+				iov: libc::iovec { iov_base: std::ptr::null_mut(), iov_len: 0 },
+				address: MaybeUninit::uninit(),
+			}),
+			data,
+			cdata: Vec::new(),
+			flags,
+		}
+	}
+
+	/// Create a new operation to send a message to a specific address.
+	pub fn new_to(address: Address, data: Vec<u8>, flags: c_int) -> Self {
+		let mut output = Self::new(data, flags);
+		output.state.header.msg_namelen = address.len();
+		output.state.address = MaybeUninit::new(address);
+		output
+	}
+
+	/// Set the ancillary data to send with the message.
+	///
+	/// The ancillary data can be created with a [`SocketAncillary`](crate::ancillary::SocketAncillary).
+	pub fn set_cdata(&mut self, cdata: Vec<u8>) {
+		self.cdata = cdata;
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		let state = &mut *self.state;
+		state.iov.iov_base = self.data.as_mut_ptr().cast();
+		state.iov.iov_len = self.data.len();
+		state.header.msg_iov = &mut state.iov;
+		state.header.msg_iovlen = 1;
+		if state.header.msg_namelen > 0 {
+			state.header.msg_name = state.address.as_mut_ptr().cast();
+		}
+		if !self.cdata.is_empty() {
+			state.header.msg_control = self.cdata.as_mut_ptr().cast();
+			state.header.msg_controllen = self.cdata.len() as _;
+		}
+		opcode::SendMsg::new(types::Fd(socket.as_raw_fd()), &state.header)
+			.flags(self.flags as u32)
+			.build()
+	}
+
+	/// Get the number of bytes sent from the result of the completion queue entry.
+	///
+	/// The data buffer is returned so it can be reused.
+	pub fn finish(self, result: i32) -> (Vec<u8>, std::io::Result<usize>) {
+		(self.data, check_result(result).map(|len| len as usize))
+	}
+}
+
+/// Receive a message with the address of the sender and ancillary data.
+pub struct RecvMsg<Address> {
+	state: Box<MsgState<Address>>,
+	buffer: Vec<u8>,
+	cdata: Vec<u8>,
+	flags: c_int,
+}
+
+/// A message received with [`RecvMsg`].
+#[derive(Debug)]
+pub struct ReceivedMsg<Address> {
+	/// The received data.
+	pub data: Vec<u8>,
+
+	/// The received ancillary data.
+	///
+	/// The ancillary data can be parsed with a [`SocketAncillary`](crate::ancillary::SocketAncillary).
+	pub cdata: Vec<u8>,
+
+	/// The address of the sender, if the socket reported one.
+	pub address: Option<Address>,
+
+	/// The flags of the received message, such as `MSG_TRUNC` and `MSG_CTRUNC`.
+	pub flags: c_int,
+}
+
+impl<Address: AsSocketAddress> RecvMsg<Address> {
+	/// Create a new receive operation that receives at most `buffer.len()` bytes and `cdata.len()` bytes of ancillary data.
+	///
+	/// The flags are the same as for [`Socket::recv_msg()`].
+	pub fn new(buffer: Vec<u8>, cdata: Vec<u8>, flags: c_int) -> Self {
+		Self {
+			state: Box::new(MsgState {
+				header: unsafe { std::mem::zeroed() },
+				iov: libc::iovec { iov_base: std::ptr::null_mut(), iov_len: 0 },
+				address: MaybeUninit::zeroed(),
+			}),
+			buffer,
+			cdata,
+			flags,
+		}
+	}
+
+	/// Create the submission queue entry for the operation.
+	pub fn entry(&mut self, socket: &Socket<Address>) -> squeue::Entry {
+		let state = &mut *self.state;
+		state.iov.iov_base = self.buffer.as_mut_ptr().cast();
+		state.iov.iov_len = self.buffer.len();
+		state.header.msg_iov = &mut state.iov;
+		state.header.msg_iovlen = 1;
+		state.header.msg_name = Address::as_sockaddr_mut(&mut state.address).cast();
+		state.header.msg_namelen = Address::max_len();
+		if !self.cdata.is_empty() {
+			state.header.msg_control = self.cdata.as_mut_ptr().cast();
+			state.header.msg_controllen = self.cdata.len() as _;
+		}
+		opcode::RecvMsg::new(types::Fd(socket.as_raw_fd()), &mut state.header)
+			.flags(self.flags as u32)
+			.build()
+	}
+
+	/// Get the received message from the result of the completion queue entry.
+	pub fn finish(self, result: i32) -> std::io::Result<ReceivedMsg<Address>> {
+		let len = check_result(result)?;
+		let Self { state, mut buffer, mut cdata, .. } = self;
+		let state = *state;
+		buffer.truncate(len as usize);
+		cdata.truncate(state.header.msg_controllen as _);
+		let address = if state.header.msg_namelen == 0 {
+			None
+		} else {
+			Some(Address::finalize(state.address, state.header.msg_namelen)?)
+		};
+		Ok(ReceivedMsg {
+			data: buffer,
+			cdata,
+			address,
+			flags: state.header.msg_flags,
+		})
+	}
+}
+
+/// A group of buffers provided to the kernel for receive operations with buffer selection.
+///
+/// With buffer selection, the kernel picks a buffer from the group when data arrives,
+/// instead of reserving a buffer for every pending receive operation.
+/// The ID of the selected buffer is reported in the flags of the completion queue entry,
+/// and can be retrieved with [`::io_uring::cqueue::buffer_select()`].
+///
+/// Once the application is done with a selected buffer, it must give it back to the kernel
+/// with [`Self::reprovide_entry()`].
+pub struct ProvidedBuffers {
+	memory: Vec<u8>,
+	buffer_len: usize,
+	group_id: u16,
+}
+
+impl ProvidedBuffers {
+	/// Allocate `count` buffers of `buffer_len` bytes each for the buffer group `group_id`.
+	pub fn new(group_id: u16, count: u16, buffer_len: usize) -> Self {
+		Self {
+			memory: vec![0; usize::from(count) * buffer_len],
+			buffer_len,
+			group_id,
+		}
+	}
+
+	/// Get the ID of the buffer group.
+	pub fn group_id(&self) -> u16 {
+		self.group_id
+	}
+
+	/// Get the number of buffers in the group.
+	pub fn count(&self) -> u16 {
+		(self.memory.len() / self.buffer_len.max(1)) as u16
+	}
+
+	/// Create the submission queue entry to provide all buffers to the kernel.
+	pub fn provide_entry(&mut self) -> squeue::Entry {
+		opcode::ProvideBuffers::new(self.memory.as_mut_ptr(), self.buffer_len as i32, self.count(), self.group_id, 0).build()
+	}
+
+	/// Create the submission queue entry to give a single buffer back to the kernel.
+	///
+	/// # Panics
+	/// This function panics if the buffer ID is out of range.
+	pub fn reprovide_entry(&mut self, buffer_id: u16) -> squeue::Entry {
+		assert!(buffer_id < self.count());
+		let start = usize::from(buffer_id) * self.buffer_len;
+		opcode::ProvideBuffers::new(self.memory[start..].as_mut_ptr(), self.buffer_len as i32, 1, self.group_id, buffer_id).build()
+	}
+
+	/// Get the first `len` bytes of a buffer selected by the kernel.
+	///
+	/// # Panics
+	/// This function panics if the buffer ID is out of range or if `len` is larger than the buffer size.
+	pub fn buffer(&self, buffer_id: u16, len: usize) -> &[u8] {
+		assert!(buffer_id < self.count());
+		assert!(len <= self.buffer_len);
+		let start = usize::from(buffer_id) * self.buffer_len;
+		&self.memory[start..start + len]
+	}
+}
+
+/// Create a submission queue entry to receive data into a buffer selected by the kernel from a [`ProvidedBuffers`] group.
+///
+/// The result of the completion queue entry is the number of bytes received,
+/// and the ID of the selected buffer is reported in the flags.
+pub fn recv_select<Address: AsSocketAddress>(socket: &Socket<Address>, buffers: &ProvidedBuffers, flags: c_int) -> squeue::Entry {
+	opcode::Recv::new(types::Fd(socket.as_raw_fd()), std::ptr::null_mut(), buffers.buffer_len as u32)
+		.flags(flags)
+		.buf_group(buffers.group_id)
+		.build()
+		.flags(squeue::Flags::BUFFER_SELECT)
+}
+
+/// Create a submission queue entry to keep receiving data into buffers selected from a [`ProvidedBuffers`] group.
+///
+/// A completion queue entry is posted for every received chunk of data, until an error occurs or the buffers run out.
+/// Use [`::io_uring::cqueue::more()`] on the flags of the completion queue entry to check if more completions will follow.
+///
+/// This requires Linux 6.0 or later.
+pub fn recv_multishot<Address: AsSocketAddress>(socket: &Socket<Address>, buffers: &ProvidedBuffers, flags: c_int) -> squeue::Entry {
+	opcode::RecvMulti::new(types::Fd(socket.as_raw_fd()), buffers.group_id)
+		.flags(flags)
+		.build()
+}
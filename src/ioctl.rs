@@ -0,0 +1,212 @@
+//! Socket and network interface ioctls.
+//!
+//! The functions in this module use a socket to query or change the configuration of a network interface.
+//! Any socket can be used, the type of the socket does not matter.
+//!
+//! This module also contains functions to query the number of bytes in the send and receive queues of a socket,
+//! and to check if a socket is at the out-of-band mark.
+//!
+//! These ioctls are a non-portable Linux extension.
+
+use std::os::raw::{c_int, c_short};
+
+use crate::socket::check_ret;
+use crate::{AsSocketAddress, Socket};
+
+/// The `SIOCATMARK` ioctl to check if a socket is at the out-of-band mark.
+const SIOCATMARK: libc::c_ulong = 0x8905;
+
+/// The flags of a network interface, as used by the `SIOCGIFFLAGS` and `SIOCSIFFLAGS` ioctls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InterfaceFlags(c_int);
+
+impl InterfaceFlags {
+	/// The interface is up (`IFF_UP`).
+	pub const UP: Self = Self(libc::IFF_UP);
+
+	/// The interface has a valid broadcast address (`IFF_BROADCAST`).
+	pub const BROADCAST: Self = Self(libc::IFF_BROADCAST);
+
+	/// The interface is a loopback interface (`IFF_LOOPBACK`).
+	pub const LOOPBACK: Self = Self(libc::IFF_LOOPBACK);
+
+	/// The interface is a point-to-point link (`IFF_POINTOPOINT`).
+	pub const POINT_TO_POINT: Self = Self(libc::IFF_POINTOPOINT);
+
+	/// The interface has resources allocated and is operational (`IFF_RUNNING`).
+	pub const RUNNING: Self = Self(libc::IFF_RUNNING);
+
+	/// The interface does not use ARP (`IFF_NOARP`).
+	pub const NO_ARP: Self = Self(libc::IFF_NOARP);
+
+	/// The interface receives all packets (`IFF_PROMISC`).
+	pub const PROMISC: Self = Self(libc::IFF_PROMISC);
+
+	/// The interface receives all multicast packets (`IFF_ALLMULTI`).
+	pub const ALL_MULTI: Self = Self(libc::IFF_ALLMULTI);
+
+	/// The interface supports multicast (`IFF_MULTICAST`).
+	pub const MULTICAST: Self = Self(libc::IFF_MULTICAST);
+
+	/// Create interface flags from the raw `IFF_*` value.
+	pub const fn from_raw(raw: c_int) -> Self {
+		Self(raw)
+	}
+
+	/// Get the raw `IFF_*` value.
+	pub const fn as_raw(self) -> c_int {
+		self.0
+	}
+
+	/// Check if all flags in `other` are set.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for InterfaceFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitAnd for InterfaceFlags {
+	type Output = Self;
+
+	fn bitand(self, other: Self) -> Self {
+		Self(self.0 & other.0)
+	}
+}
+
+impl std::ops::Not for InterfaceFlags {
+	type Output = Self;
+
+	fn not(self) -> Self {
+		Self(!self.0)
+	}
+}
+
+/// The hardware address of a network interface, as reported by the `SIOCGIFHWADDR` ioctl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HardwareAddress {
+	/// The ARP hardware type of the interface, like `ARPHRD_ETHER` or `ARPHRD_CAN`.
+	pub kind: u16,
+
+	/// The first six bytes of the hardware address.
+	///
+	/// For Ethernet interfaces, this is the MAC address.
+	pub bytes: [u8; 6],
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Get the MTU of a network interface with the `SIOCGIFMTU` ioctl.
+	pub fn interface_mtu(&self, interface: &str) -> std::io::Result<u32> {
+		let mut request = new_ifreq(interface)?;
+		self.interface_ioctl(libc::SIOCGIFMTU as _, &mut request)?;
+		Ok(unsafe { request.ifr_ifru.ifru_mtu } as u32)
+	}
+
+	/// Get the hardware address of a network interface with the `SIOCGIFHWADDR` ioctl.
+	pub fn interface_hardware_address(&self, interface: &str) -> std::io::Result<HardwareAddress> {
+		let mut request = new_ifreq(interface)?;
+		self.interface_ioctl(libc::SIOCGIFHWADDR as _, &mut request)?;
+		let address = unsafe { request.ifr_ifru.ifru_hwaddr };
+		let mut bytes = [0; 6];
+		for (output, &input) in bytes.iter_mut().zip(address.sa_data.iter()) {
+			*output = input as u8;
+		}
+		Ok(HardwareAddress {
+			kind: address.sa_family,
+			bytes,
+		})
+	}
+
+	/// Get the flags of a network interface with the `SIOCGIFFLAGS` ioctl.
+	pub fn interface_flags(&self, interface: &str) -> std::io::Result<InterfaceFlags> {
+		let mut request = new_ifreq(interface)?;
+		self.interface_ioctl(libc::SIOCGIFFLAGS as _, &mut request)?;
+		let flags = unsafe { request.ifr_ifru.ifru_flags };
+		Ok(InterfaceFlags(c_int::from(flags) & 0xFFFF))
+	}
+
+	/// Set the flags of a network interface with the `SIOCSIFFLAGS` ioctl.
+	///
+	/// This requires the `CAP_NET_ADMIN` capability.
+	/// To change a single flag, get the current flags with [`interface_flags()`](Socket::interface_flags) first.
+	pub fn set_interface_flags(&self, interface: &str, flags: InterfaceFlags) -> std::io::Result<()> {
+		let mut request = new_ifreq(interface)?;
+		request.ifr_ifru.ifru_flags = flags.0 as c_short;
+		self.interface_ioctl(libc::SIOCSIFFLAGS as _, &mut request)
+	}
+
+	/// Get the index of a network interface with the `SIOCGIFINDEX` ioctl.
+	pub fn interface_index(&self, interface: &str) -> std::io::Result<u32> {
+		let mut request = new_ifreq(interface)?;
+		self.interface_ioctl(libc::SIOCGIFINDEX as _, &mut request)?;
+		Ok(unsafe { request.ifr_ifru.ifru_ifindex } as u32)
+	}
+
+	/// Get the number of bytes in the send queue with the `SIOCOUTQ` ioctl.
+	///
+	/// For TCP sockets, this is the amount of data that has not yet been acknowledged by the peer.
+	/// For UDP sockets, this is the amount of data that has not yet been sent by the kernel.
+	/// For Unix sockets, this is the amount of data that has not yet been read by the peer.
+	pub fn output_queue_len(&self) -> std::io::Result<usize> {
+		self.int_ioctl(libc::TIOCOUTQ as _)
+	}
+
+	/// Get the number of bytes in the receive queue with the `SIOCINQ` ioctl.
+	///
+	/// For TCP and Unix stream sockets, this is the amount of unread data in the receive queue.
+	/// For datagram sockets, this is the size of the next datagram.
+	pub fn input_queue_len(&self) -> std::io::Result<usize> {
+		self.int_ioctl(libc::FIONREAD as _)
+	}
+
+	/// Check if the read position is at the out-of-band mark with the `SIOCATMARK` ioctl.
+	///
+	/// When this returns true, the next byte read from the socket is the urgent byte
+	/// sent with [`send_oob()`](Socket::send_oob).
+	/// Reads from the socket stop at the mark, so all data before the urgent byte can be read first.
+	pub fn at_mark(&self) -> std::io::Result<bool> {
+		Ok(self.int_ioctl(SIOCATMARK)? != 0)
+	}
+
+	/// Perform an ioctl that reports a single `int`.
+	fn int_ioctl(&self, request: libc::c_ulong) -> std::io::Result<usize> {
+		unsafe {
+			let mut value: c_int = 0;
+			check_ret(libc::ioctl(self.as_raw_fd(), request as _, &mut value))?;
+			Ok(value as usize)
+		}
+	}
+
+	/// Perform an ioctl with a `struct ifreq` argument.
+	fn interface_ioctl(&self, request: libc::c_ulong, argument: &mut libc::ifreq) -> std::io::Result<()> {
+		unsafe {
+			check_ret(libc::ioctl(self.as_raw_fd(), request as _, argument as *mut libc::ifreq))?;
+			Ok(())
+		}
+	}
+}
+
+/// Create a `struct ifreq` for a network interface.
+///
+/// Returns an error if the interface name is too long or contains a null byte.
+fn new_ifreq(interface: &str) -> std::io::Result<libc::ifreq> {
+	let interface = interface.as_bytes();
+	if interface.len() >= libc::IFNAMSIZ {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name too long"));
+	}
+	if interface.contains(&0) {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name contains a null byte"));
+	}
+
+	let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+	for (output, &input) in request.ifr_name.iter_mut().zip(interface) {
+		*output = input as _;
+	}
+	Ok(request)
+}
@@ -0,0 +1,245 @@
+//! IP level socket options and control messages.
+//!
+//! The socket options in this module only apply to IPv4 and IPv6 sockets.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+/// Packet information for IPv4 sockets (`struct in_pktinfo`).
+///
+/// Received as `IP_PKTINFO` control message if enabled with [`Socket::set_ip_recv_pktinfo()`].
+/// It can also be sent with [`SocketAncillary::add_ipv4_packet_info()`](crate::ancillary::SocketAncillary::add_ipv4_packet_info)
+/// to select the source address and outgoing interface of a packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4PacketInfo {
+	/// The index of the interface the packet was received on, or the interface to send it on.
+	///
+	/// When sending, 0 means the interface is selected by the routing table.
+	pub interface_index: u32,
+
+	/// The local address the packet was received on, or the source address to send it from.
+	///
+	/// When sending, the unspecified address means the source address is selected by the routing table.
+	pub local_address: Ipv4Addr,
+
+	/// The destination address from the IP header of the packet.
+	///
+	/// This can differ from the local address for broadcast and multicast packets.
+	/// It is ignored when sending.
+	pub destination: Ipv4Addr,
+}
+
+impl Ipv4PacketInfo {
+	/// Create packet information to send a packet from a specific interface and source address.
+	pub fn new(interface_index: u32, local_address: Ipv4Addr) -> Self {
+		Self {
+			interface_index,
+			local_address,
+			destination: Ipv4Addr::UNSPECIFIED,
+		}
+	}
+
+	/// Convert the packet information to the C-compatible `struct in_pktinfo`.
+	pub fn into_raw(self) -> libc::in_pktinfo {
+		libc::in_pktinfo {
+			ipi_ifindex: self.interface_index as c_int,
+			ipi_spec_dst: libc::in_addr { s_addr: u32::from(self.local_address).to_be() },
+			ipi_addr: libc::in_addr { s_addr: u32::from(self.destination).to_be() },
+		}
+	}
+
+	/// Parse the packet information from the data of an `IP_PKTINFO` control message.
+	///
+	/// Returns an error if the data is too short.
+	pub fn parse(data: &[u8]) -> std::io::Result<Self> {
+		if data.len() < std::mem::size_of::<libc::in_pktinfo>() {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "IP_PKTINFO control message too short"));
+		}
+		let raw: libc::in_pktinfo = unsafe { std::ptr::read_unaligned(data.as_ptr() as *const _) };
+		Ok(Self {
+			interface_index: raw.ipi_ifindex as u32,
+			local_address: Ipv4Addr::from(u32::from_be(raw.ipi_spec_dst.s_addr)),
+			destination: Ipv4Addr::from(u32::from_be(raw.ipi_addr.s_addr)),
+		})
+	}
+}
+
+/// Packet information for IPv6 sockets (`struct in6_pktinfo`).
+///
+/// Received as `IPV6_PKTINFO` control message if enabled with [`Socket::set_ipv6_recv_pktinfo()`].
+/// It can also be sent with [`SocketAncillary::add_ipv6_packet_info()`](crate::ancillary::SocketAncillary::add_ipv6_packet_info)
+/// to select the source address and outgoing interface of a packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6PacketInfo {
+	/// The index of the interface the packet was received on, or the interface to send it on.
+	///
+	/// When sending, 0 means the interface is selected by the routing table.
+	pub interface_index: u32,
+
+	/// The destination address of a received packet, or the source address to send a packet from.
+	///
+	/// When sending, the unspecified address means the source address is selected by the routing table.
+	pub address: Ipv6Addr,
+}
+
+impl Ipv6PacketInfo {
+	/// Create packet information to send a packet from a specific interface and source address.
+	pub fn new(interface_index: u32, address: Ipv6Addr) -> Self {
+		Self { interface_index, address }
+	}
+
+	/// Convert the packet information to the C-compatible `struct in6_pktinfo`.
+	pub fn into_raw(self) -> libc::in6_pktinfo {
+		libc::in6_pktinfo {
+			ipi6_addr: libc::in6_addr { s6_addr: self.address.octets() },
+			ipi6_ifindex: self.interface_index,
+		}
+	}
+
+	/// Parse the packet information from the data of an `IPV6_PKTINFO` control message.
+	///
+	/// Returns an error if the data is too short.
+	pub fn parse(data: &[u8]) -> std::io::Result<Self> {
+		if data.len() < std::mem::size_of::<libc::in6_pktinfo>() {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "IPV6_PKTINFO control message too short"));
+		}
+		let raw: libc::in6_pktinfo = unsafe { std::ptr::read_unaligned(data.as_ptr() as *const _) };
+		Ok(Self {
+			interface_index: raw.ipi6_ifindex,
+			address: Ipv6Addr::from(raw.ipi6_addr.s6_addr),
+		})
+	}
+}
+
+/// The `IPV6_FREEBIND` socket option (Linux 4.15 and later).
+const IPV6_FREEBIND: c_int = 78;
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Enable or disable reception of `IP_PKTINFO` control messages on an IPv4 socket.
+	pub fn set_ip_recv_pktinfo(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_PKTINFO, c_int::from(enable))
+	}
+
+	/// Check if reception of `IP_PKTINFO` control messages is enabled on an IPv4 socket.
+	pub fn ip_recv_pktinfo(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_PKTINFO)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `IPV6_PKTINFO` control messages on an IPv6 socket.
+	pub fn set_ipv6_recv_pktinfo(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, c_int::from(enable))
+	}
+
+	/// Check if reception of `IPV6_PKTINFO` control messages is enabled on an IPv6 socket.
+	pub fn ipv6_recv_pktinfo(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `IP_TOS` control messages on an IPv4 socket.
+	pub fn set_ip_recv_tos(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_RECVTOS, c_int::from(enable))
+	}
+
+	/// Check if reception of `IP_TOS` control messages is enabled on an IPv4 socket.
+	pub fn ip_recv_tos(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_RECVTOS)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `IP_TTL` control messages on an IPv4 socket.
+	pub fn set_ip_recv_ttl(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_RECVTTL, c_int::from(enable))
+	}
+
+	/// Check if reception of `IP_TTL` control messages is enabled on an IPv4 socket.
+	pub fn ip_recv_ttl(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_RECVTTL)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `IPV6_TCLASS` control messages on an IPv6 socket.
+	pub fn set_ipv6_recv_tclass(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_RECVTCLASS, c_int::from(enable))
+	}
+
+	/// Check if reception of `IPV6_TCLASS` control messages is enabled on an IPv6 socket.
+	pub fn ipv6_recv_tclass(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_RECVTCLASS)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable reception of `IPV6_HOPLIMIT` control messages on an IPv6 socket.
+	pub fn set_ipv6_recv_hoplimit(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_RECVHOPLIMIT, c_int::from(enable))
+	}
+
+	/// Check if reception of `IPV6_HOPLIMIT` control messages is enabled on an IPv6 socket.
+	pub fn ipv6_recv_hoplimit(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_RECVHOPLIMIT)?;
+		Ok(value != 0)
+	}
+
+	/// Allow binding an IPv4 socket to an address that is not (yet) configured on the system with the `IP_FREEBIND` option.
+	///
+	/// This is useful for services that need to start before the network is fully configured,
+	/// or for high-availability setups where the address may move between hosts.
+	/// It does not require any special privileges.
+	pub fn set_ip_freebind(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_FREEBIND, c_int::from(enable))
+	}
+
+	/// Check if the `IP_FREEBIND` option is enabled on an IPv4 socket.
+	pub fn ip_freebind(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_FREEBIND)?;
+		Ok(value != 0)
+	}
+
+	/// Allow binding an IPv6 socket to an address that is not (yet) configured on the system with the `IPV6_FREEBIND` option.
+	///
+	/// This option requires Linux 4.15 or later.
+	/// It does not require any special privileges.
+	pub fn set_ipv6_freebind(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, IPV6_FREEBIND, c_int::from(enable))
+	}
+
+	/// Check if the `IPV6_FREEBIND` option is enabled on an IPv6 socket.
+	pub fn ipv6_freebind(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, IPV6_FREEBIND)?;
+		Ok(value != 0)
+	}
+
+	/// Enable transparent proxying on an IPv4 socket with the `IP_TRANSPARENT` option.
+	///
+	/// This allows the socket to bind to foreign addresses and to accept connections for foreign addresses,
+	/// when combined with the appropriate routing and `TPROXY` firewall rules.
+	///
+	/// This requires the `CAP_NET_ADMIN` or `CAP_NET_RAW` capability.
+	pub fn set_ip_transparent(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_TRANSPARENT, c_int::from(enable))
+	}
+
+	/// Check if the `IP_TRANSPARENT` option is enabled on an IPv4 socket.
+	pub fn ip_transparent(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_TRANSPARENT)?;
+		Ok(value != 0)
+	}
+
+	/// Enable transparent proxying on an IPv6 socket with the `IPV6_TRANSPARENT` option.
+	///
+	/// See [`set_ip_transparent()`](Socket::set_ip_transparent) for more information.
+	///
+	/// This requires the `CAP_NET_ADMIN` or `CAP_NET_RAW` capability.
+	pub fn set_ipv6_transparent(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_TRANSPARENT, c_int::from(enable))
+	}
+
+	/// Check if the `IPV6_TRANSPARENT` option is enabled on an IPv6 socket.
+	pub fn ipv6_transparent(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_TRANSPARENT)?;
+		Ok(value != 0)
+	}
+}
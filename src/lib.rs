@@ -15,14 +15,101 @@ pub use address::*;
 mod socket;
 pub use socket::*;
 
+mod error;
+pub use error::*;
+
+mod builder;
+pub use builder::*;
+
+mod socket_type;
+pub use socket_type::*;
+
+mod roles;
+pub use roles::*;
+
+mod seqpacket;
+pub use seqpacket::*;
+
+mod msg_header;
+pub use msg_header::*;
+
+mod std_net;
+
 pub mod ancillary;
 
-#[cfg(fceature = "mio")]
+pub mod credentials;
+
+#[cfg(target_os = "linux")]
+pub mod cork;
+
+#[cfg(target_os = "linux")]
+pub mod can;
+
+#[cfg(all(feature = "epoll", target_os = "linux"))]
+pub mod epoll;
+
+#[cfg(target_os = "linux")]
+pub mod errqueue;
+
+pub mod handover;
+
+pub mod icmp;
+
+pub mod interface;
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub mod io_uring;
+
+#[cfg(target_os = "linux")]
+pub mod ioctl;
+
+#[cfg(target_os = "linux")]
+pub mod ip;
+
+#[cfg(target_os = "linux")]
+pub mod meminfo;
+
+#[cfg(target_os = "linux")]
+pub mod multicast;
+
+#[cfg(target_os = "linux")]
+pub mod pfkey;
+
+pub mod poll;
+
+#[cfg(target_os = "linux")]
+pub mod pppoe;
+
+pub mod resolve;
+
+#[cfg(target_os = "linux")]
+pub mod smc;
+
+#[cfg(target_os = "linux")]
+pub mod splice;
+
+pub mod tcp;
+
+#[cfg(target_os = "linux")]
+pub mod timestamping;
+
+#[cfg(target_os = "linux")]
+pub mod udp;
+
+#[cfg(target_os = "linux")]
+pub mod zerocopy;
+
+#[cfg(feature = "mio")]
 pub mod mio;
 
+#[cfg(feature = "socket2")]
+pub mod socket2;
+
 pub type UnixSocket = Socket<UnixSocketAddress>;
 pub type Inet4Socket = Socket<Inet4SocketAddress>;
 pub type Inet6Socket = Socket<Inet6SocketAddress>;
+#[cfg(target_os = "linux")]
+pub type CanSocket = Socket<CanSocketAddress>;
 
 /// Disable SIGPIPE for the current process.
 ///
@@ -30,7 +117,7 @@ pub type Inet6Socket = Socket<Inet6SocketAddress>;
 /// On most platforms this is prevented, either by using the `MSG_NOSIGNAL` flag when writing
 /// or by setting the `SO_NOSIGPIPE` socket option.
 ///
-/// However, if a platform does not support `MSG_NOSIGNAL` or `SO_NOGSISPIPE`,
+/// However, if a platform does not support `MSG_NOSIGNAL` or `SO_NOSIGPIPE`,
 /// the signal needs to be handled or the process will be terminated by the kernel.
 /// Calling [`disable_sigpipe()`] make sure the signal is ignored without terminating the process.
 pub fn disable_sigpipe() -> std::io::Result<()> {
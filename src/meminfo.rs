@@ -0,0 +1,75 @@
+//! Socket memory accounting with the `SO_MEMINFO` option.
+//!
+//! The memory information shows how much memory the kernel has allocated for the buffers of a socket,
+//! and how many packets were dropped because the buffers were full.
+//!
+//! This option is a non-portable Linux extension.
+
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+/// The `SO_MEMINFO` socket option (Linux 4.12 and later).
+const SO_MEMINFO: c_int = 55;
+
+/// The number of values reported by the `SO_MEMINFO` option on recent kernels.
+const SK_MEMINFO_VARS: usize = 9;
+
+/// Memory accounting information of a socket, as reported by the `SO_MEMINFO` option.
+///
+/// All values are in bytes, except for [`Self::drops`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryInfo {
+	/// The memory allocated for received data (`SK_MEMINFO_RMEM_ALLOC`).
+	pub rmem_alloc: u32,
+
+	/// The size of the receive buffer (`SK_MEMINFO_RCVBUF`).
+	pub rcvbuf: u32,
+
+	/// The memory allocated for data being sent (`SK_MEMINFO_WMEM_ALLOC`).
+	pub wmem_alloc: u32,
+
+	/// The size of the send buffer (`SK_MEMINFO_SNDBUF`).
+	pub sndbuf: u32,
+
+	/// The memory reserved for the socket but not yet used (`SK_MEMINFO_FWD_ALLOC`).
+	pub fwd_alloc: u32,
+
+	/// The memory used by data queued for sending (`SK_MEMINFO_WMEM_QUEUED`).
+	pub wmem_queued: u32,
+
+	/// The memory used for socket options and ancillary data (`SK_MEMINFO_OPTMEM`).
+	pub optmem: u32,
+
+	/// The memory used by packets in the backlog queue (`SK_MEMINFO_BACKLOG`).
+	pub backlog: u32,
+
+	/// The number of packets dropped by the socket (`SK_MEMINFO_DROPS`).
+	///
+	/// This is only reported by Linux 4.19 and later.
+	pub drops: Option<u32>,
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Get the memory accounting information of the socket with the `SO_MEMINFO` option.
+	///
+	/// This requires Linux 4.12 or later.
+	pub fn memory_info(&self) -> std::io::Result<MemoryInfo> {
+		let mut raw = [0u32; SK_MEMINFO_VARS];
+		let count = self.get_option_slice(libc::SOL_SOCKET, SO_MEMINFO, &mut raw)?;
+		if count < SK_MEMINFO_VARS - 1 {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "SO_MEMINFO reported too few values"));
+		}
+		Ok(MemoryInfo {
+			rmem_alloc: raw[0],
+			rcvbuf: raw[1],
+			wmem_alloc: raw[2],
+			sndbuf: raw[3],
+			fwd_alloc: raw[4],
+			wmem_queued: raw[5],
+			optmem: raw[6],
+			backlog: raw[7],
+			drops: if count > 8 { Some(raw[8]) } else { None },
+		})
+	}
+}
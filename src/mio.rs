@@ -1,21 +1,133 @@
-///! `mio` support
-///
-/// This module enables [`mio`] support.
-/// It implements [`mio::event::Source`] for [`Socket`].
+//! `mio` support.
+//!
+//! This module enables [`mio`] support.
+//! It implements [`mio::event::Source`] for [`Socket`],
+//! and adds constructors to create sockets in non-blocking mode, ready to be registered with a [`mio::Registry`].
+//!
+//! It also implements conversions between [`Socket`] and the `mio::net` types.
+//! Converting a [`Socket`] to a `mio::net` type checks the domain and type of the socket,
+//! and puts the socket in non-blocking mode.
+
+use std::os::raw::c_int;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
 
-use crate::Socket;
 use mio::event::Source;
+use mio::unix::SourceFd;
+
+use crate::{AsSocketAddress, Inet4SocketAddress, Inet6SocketAddress, Socket, UnixSocketAddress};
+use crate::socket::set_fd_nonblocking;
+use crate::error::{Operation, ResultExt};
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Create a new non-blocking socket with the specified type and protocol.
+	///
+	/// The domain is taken from the `Address` type.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	///
+	/// See [`Self::new`] for more information.
+	pub fn new_nonblocking(kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<Self>
+	where
+		Address: crate::SpecificSocketAddress,
+	{
+		Self::new_generic_nonblocking(Address::static_family() as c_int, kind, protocol)
+	}
+
+	/// Create a new non-blocking socket with the specified domain, type and protocol.
+	///
+	/// Unless you are working with generic socket addresses,
+	/// you should normally prefer `Self::new_nonblocking`.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	///
+	/// See [`Self::new_generic`] for more information.
+	pub fn new_generic_nonblocking(domain: c_int, kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<Self> {
+		let socket = Self::new_generic(domain, kind, protocol)?;
+		set_fd_nonblocking(socket.as_raw_fd()).context(Operation::SetNonblocking)?;
+		Ok(socket)
+	}
+
+	/// Create a connected pair of non-blocking sockets with the specified type and protocol.
+	///
+	/// The domain is taken from the `Address` type.
+	///
+	/// The created sockets have the `close-on-exec` flag set.
+	///
+	/// See [`Self::pair`] for more information.
+	pub fn pair_nonblocking(kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<(Self, Self)>
+	where
+		Address: crate::SpecificSocketAddress,
+	{
+		let (a, b) = Self::pair(kind, protocol)?;
+		set_fd_nonblocking(a.as_raw_fd()).context(Operation::SetNonblocking)?;
+		set_fd_nonblocking(b.as_raw_fd()).context(Operation::SetNonblocking)?;
+		Ok((a, b))
+	}
+}
 
-impl mio::event::Source for Socket {
-	fn register(&mut self, registry: &mio::Registry, token: mio::Token, interests: mio::Interest) -> mio::Result<()> {
-		self.as_raw_fd().register(registry, token, interests)
+impl<Address: AsSocketAddress> Source for Socket<Address> {
+	fn register(&mut self, registry: &mio::Registry, token: mio::Token, interests: mio::Interest) -> std::io::Result<()> {
+		SourceFd(&self.as_raw_fd()).register(registry, token, interests)
 	}
 
-	fn reregister(&mut self, registry: &mio::Registry, token: mio::Token, interests: mio::Interest) -> mio::Result<()> {
-		self.as_raw_fd().reregister(registry, token, interests)
+	fn reregister(&mut self, registry: &mio::Registry, token: mio::Token, interests: mio::Interest) -> std::io::Result<()> {
+		SourceFd(&self.as_raw_fd()).reregister(registry, token, interests)
 	}
 
-	fn deregister(&mut self, registry: &mio::Registry) -> mio::Result<()> {
-		self.as_raw_fd().deregister(registry)
+	fn deregister(&mut self, registry: &mio::Registry) -> std::io::Result<()> {
+		SourceFd(&self.as_raw_fd()).deregister(registry)
 	}
 }
+
+/// Implement conversions between a [`Socket`] and a `mio::net` type.
+///
+/// The conversion from [`Socket`] checks the domain and type of the socket and puts it in non-blocking mode.
+/// The conversion to [`Socket`] is fallible if the `mio` type can have different domains,
+/// and infallible otherwise.
+macro_rules! impl_mio_conversion {
+	($address:ty, $domain:expr, $kind:expr, $listening:expr, $mio:ty, fallible) => {
+		impl_mio_conversion!(@from_socket $address, $domain, $kind, $listening, $mio);
+
+		impl std::convert::TryFrom<$mio> for Socket<$address> {
+			type Error = std::io::Error;
+
+			fn try_from(other: $mio) -> std::io::Result<Self> {
+				let socket = unsafe { Self::from_raw_fd(other.into_raw_fd()) };
+				socket.check_domain_and_type($domain, $kind, $listening)?;
+				Ok(socket)
+			}
+		}
+	};
+
+	($address:ty, $domain:expr, $kind:expr, $listening:expr, $mio:ty, infallible) => {
+		impl_mio_conversion!(@from_socket $address, $domain, $kind, $listening, $mio);
+
+		impl From<$mio> for Socket<$address> {
+			fn from(other: $mio) -> Self {
+				unsafe { Self::from_raw_fd(other.into_raw_fd()) }
+			}
+		}
+	};
+
+	(@from_socket $address:ty, $domain:expr, $kind:expr, $listening:expr, $mio:ty) => {
+		impl std::convert::TryFrom<Socket<$address>> for $mio {
+			type Error = std::io::Error;
+
+			fn try_from(socket: Socket<$address>) -> std::io::Result<Self> {
+				socket.check_domain_and_type($domain, $kind, $listening)?;
+				set_fd_nonblocking(socket.as_raw_fd()).context(Operation::SetNonblocking)?;
+				Ok(unsafe { Self::from_raw_fd(socket.into_raw_fd()) })
+			}
+		}
+	};
+}
+
+impl_mio_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_STREAM, false, mio::net::TcpStream, fallible);
+impl_mio_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_STREAM, false, mio::net::TcpStream, fallible);
+impl_mio_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_STREAM, true, mio::net::TcpListener, fallible);
+impl_mio_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_STREAM, true, mio::net::TcpListener, fallible);
+impl_mio_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_DGRAM, false, mio::net::UdpSocket, fallible);
+impl_mio_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_DGRAM, false, mio::net::UdpSocket, fallible);
+impl_mio_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_STREAM, false, mio::net::UnixStream, infallible);
+impl_mio_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_STREAM, true, mio::net::UnixListener, infallible);
+impl_mio_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_DGRAM, false, mio::net::UnixDatagram, infallible);
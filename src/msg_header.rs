@@ -0,0 +1,186 @@
+use std::io::{IoSlice, IoSliceMut};
+use std::os::raw::{c_int, c_void};
+
+use crate::ancillary::SocketAncillary;
+use crate::socket::{check_ret_isize, extra_flags};
+use crate::{AsSocketAddress, Socket, SocketAddress};
+
+/// A fully configurable message header for [`Socket::send_prepared()`].
+///
+/// Unlike [`Socket::send_msg_to()`], the destination address can be of any type,
+/// regardless of the `Address` type of the socket.
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use posix_socket::{MsgHeader, UnixSocket, UnixSocketAddress};
+/// use std::io::IoSlice;
+///
+/// # let socket = UnixSocket::new(libc::SOCK_DGRAM, 0)?;
+/// let address = UnixSocketAddress::new("/run/example.sock")?;
+/// let data = [IoSlice::new(b"hello")];
+/// let header = MsgHeader::new(&data).name(&address).flags(libc::MSG_DONTWAIT);
+/// socket.send_prepared(&header)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MsgHeader<'a> {
+	name: Option<SocketAddress>,
+	data: &'a [IoSlice<'a>],
+	control: &'a [u8],
+	flags: c_int,
+}
+
+impl<'a> MsgHeader<'a> {
+	/// Create a message header for the given data, without address or control data.
+	pub fn new(data: &'a [IoSlice<'a>]) -> Self {
+		Self {
+			name: None,
+			data,
+			control: &[],
+			flags: 0,
+		}
+	}
+
+	/// Set the destination address of the message.
+	///
+	/// The address is copied into the header, and can have any address type.
+	pub fn name<Address: AsSocketAddress>(mut self, address: &Address) -> Self {
+		self.name = Some(SocketAddress::from_other(address));
+		self
+	}
+
+	/// Set the control data of the message.
+	///
+	/// Use [`SocketAncillary`] to build the control data and pass [`SocketAncillary::data()`].
+	pub fn control(mut self, control: &'a [u8]) -> Self {
+		self.control = control;
+		self
+	}
+
+	/// Set the flags to pass to `sendmsg`, like `MSG_DONTWAIT` or `MSG_MORE`.
+	pub fn flags(mut self, flags: c_int) -> Self {
+		self.flags = flags;
+		self
+	}
+}
+
+/// A fully configurable message header for [`Socket::recv_prepared()`].
+///
+/// After receiving a message, the header gives access to the address of the sender,
+/// which can be of any address type, and to the flags of the received message.
+pub struct MsgHeaderMut<'a, 'b> {
+	name: Option<SocketAddress>,
+	data: &'a mut [IoSliceMut<'b>],
+	control: Option<&'a mut SocketAncillary<'b>>,
+	flags: c_int,
+	received_flags: c_int,
+}
+
+impl<'a, 'b> MsgHeaderMut<'a, 'b> {
+	/// Create a message header to receive data into the given buffers, without address or control data.
+	pub fn new(data: &'a mut [IoSliceMut<'b>]) -> Self {
+		Self {
+			name: None,
+			data,
+			control: None,
+			flags: 0,
+			received_flags: 0,
+		}
+	}
+
+	/// Receive the address of the sender of the message.
+	///
+	/// After receiving a message, the address is available with [`Self::received_name()`].
+	pub fn name(mut self) -> Self {
+		self.name = Some(SocketAddress::from_raw(unsafe { std::mem::zeroed() }, 0));
+		self
+	}
+
+	/// Receive control data into the given buffer.
+	pub fn control(mut self, control: &'a mut SocketAncillary<'b>) -> Self {
+		self.control = Some(control);
+		self
+	}
+
+	/// Set the flags to pass to `recvmsg`, like `MSG_DONTWAIT` or `MSG_PEEK`.
+	pub fn flags(mut self, flags: c_int) -> Self {
+		self.flags = flags;
+		self
+	}
+
+	/// Get the address of the sender of the last received message.
+	///
+	/// Returns [`None`] if the address was not requested with [`Self::name()`],
+	/// or if no message has been received yet.
+	pub fn received_name(&self) -> Option<&SocketAddress> {
+		self.name.as_ref().filter(|name| name.len() != 0)
+	}
+
+	/// Get the flags of the last received message, like `MSG_TRUNC` or `MSG_CTRUNC`.
+	pub fn received_flags(&self) -> c_int {
+		self.received_flags
+	}
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Send a message described by a [`MsgHeader`].
+	///
+	/// Returns the number of transferred bytes.
+	///
+	/// See `man sendmsg` for more information.
+	pub fn send_prepared(&self, header: &MsgHeader) -> std::io::Result<usize> {
+		unsafe {
+			let mut raw = std::mem::zeroed::<libc::msghdr>();
+			if let Some(name) = &header.name {
+				raw.msg_name = name.as_sockaddr() as *mut c_void;
+				raw.msg_namelen = name.len();
+			}
+			raw.msg_iov = header.data.as_ptr() as *mut libc::iovec;
+			raw.msg_iovlen = header.data.len() as _;
+			if !header.control.is_empty() {
+				raw.msg_control = header.control.as_ptr() as *mut c_void;
+				raw.msg_controllen = header.control.len() as _;
+			}
+			let ret = self.retry(|| check_ret_isize(libc::sendmsg(self.as_raw_fd(), &raw, header.flags | extra_flags::SENDMSG)))?;
+			Ok(ret as usize)
+		}
+	}
+
+	/// Receive a message described by a [`MsgHeaderMut`].
+	///
+	/// Returns the number of transferred bytes.
+	/// The address of the sender and the flags of the received message are stored in the header.
+	///
+	/// See `man recvmsg` for more information.
+	pub fn recv_prepared(&self, header: &mut MsgHeaderMut) -> std::io::Result<usize> {
+		unsafe {
+			let mut raw = std::mem::zeroed::<libc::msghdr>();
+			let mut storage = std::mem::zeroed::<libc::sockaddr_storage>();
+			if header.name.is_some() {
+				raw.msg_name = &mut storage as *mut libc::sockaddr_storage as *mut c_void;
+				raw.msg_namelen = std::mem::size_of_val(&storage) as libc::socklen_t;
+			}
+			raw.msg_iov = header.data.as_mut_ptr() as *mut libc::iovec;
+			raw.msg_iovlen = header.data.len() as _;
+			if let Some(control) = &mut header.control {
+				control.clear();
+				if control.capacity() != 0 {
+					raw.msg_control = control.buffer.as_mut_ptr() as *mut c_void;
+					raw.msg_controllen = control.capacity() as _;
+				}
+			}
+
+			let ret = self.retry(|| check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut raw, header.flags | extra_flags::RECVMSG)))?;
+
+			if header.name.is_some() {
+				header.name = Some(SocketAddress::from_raw(storage, raw.msg_namelen));
+			}
+			if let Some(control) = &mut header.control {
+				control.length = raw.msg_controllen as usize;
+				control.truncated = raw.msg_flags & libc::MSG_CTRUNC != 0;
+			}
+			header.received_flags = raw.msg_flags;
+			Ok(ret as usize)
+		}
+	}
+}
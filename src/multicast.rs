@@ -0,0 +1,129 @@
+//! IPv4 and IPv6 multicast support.
+//!
+//! The functions in this module can be used to join and leave multicast groups,
+//! and to control how multicast packets are sent from a socket.
+//! They are only valid for IPv4 or IPv6 datagram and raw sockets, as indicated by their names.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Join an IPv4 multicast group.
+	///
+	/// The `interface` is the address of the local interface to join the group on.
+	/// If it is the unspecified address, the interface is selected by the kernel.
+	pub fn join_multicast_v4(&self, group: &Ipv4Addr, interface: &Ipv4Addr) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_ADD_MEMBERSHIP, ip_mreq(group, interface))
+	}
+
+	/// Leave an IPv4 multicast group.
+	pub fn leave_multicast_v4(&self, group: &Ipv4Addr, interface: &Ipv4Addr) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_DROP_MEMBERSHIP, ip_mreq(group, interface))
+	}
+
+	/// Join an IPv6 multicast group.
+	///
+	/// The `interface_index` is the index of the local interface to join the group on.
+	/// If it is 0, the interface is selected by the kernel.
+	pub fn join_multicast_v6(&self, group: &Ipv6Addr, interface_index: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_ADD_MEMBERSHIP, ipv6_mreq(group, interface_index))
+	}
+
+	/// Leave an IPv6 multicast group.
+	pub fn leave_multicast_v6(&self, group: &Ipv6Addr, interface_index: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_DROP_MEMBERSHIP, ipv6_mreq(group, interface_index))
+	}
+
+	/// Set whether sent IPv4 multicast packets are looped back to local sockets.
+	pub fn set_multicast_loop_v4(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_MULTICAST_LOOP, c_int::from(enable))
+	}
+
+	/// Check whether sent IPv4 multicast packets are looped back to local sockets.
+	pub fn multicast_loop_v4(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_MULTICAST_LOOP)?;
+		Ok(value != 0)
+	}
+
+	/// Set whether sent IPv6 multicast packets are looped back to local sockets.
+	pub fn set_multicast_loop_v6(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_LOOP, c_int::from(enable))
+	}
+
+	/// Check whether sent IPv6 multicast packets are looped back to local sockets.
+	pub fn multicast_loop_v6(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_LOOP)?;
+		Ok(value != 0)
+	}
+
+	/// Set the time-to-live of sent IPv4 multicast packets.
+	pub fn set_multicast_ttl_v4(&self, ttl: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_MULTICAST_TTL, ttl as c_int)
+	}
+
+	/// Get the time-to-live of sent IPv4 multicast packets.
+	pub fn multicast_ttl_v4(&self) -> std::io::Result<u32> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_MULTICAST_TTL)?;
+		Ok(value as u32)
+	}
+
+	/// Set the hop limit of sent IPv6 multicast packets.
+	pub fn set_multicast_hops_v6(&self, hops: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_HOPS, hops as c_int)
+	}
+
+	/// Get the hop limit of sent IPv6 multicast packets.
+	pub fn multicast_hops_v6(&self) -> std::io::Result<u32> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_HOPS)?;
+		Ok(value as u32)
+	}
+
+	/// Set the outgoing interface for IPv4 multicast packets by the address of the interface.
+	///
+	/// If the address is the unspecified address, the interface is selected by the kernel.
+	pub fn set_multicast_interface_v4(&self, interface: &Ipv4Addr) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_MULTICAST_IF, in_addr(interface))
+	}
+
+	/// Get the address of the outgoing interface for IPv4 multicast packets.
+	pub fn multicast_interface_v4(&self) -> std::io::Result<Ipv4Addr> {
+		let value: libc::in_addr = self.get_option(libc::IPPROTO_IP, libc::IP_MULTICAST_IF)?;
+		Ok(Ipv4Addr::from(u32::from_be(value.s_addr)))
+	}
+
+	/// Set the outgoing interface for IPv6 multicast packets by interface index.
+	///
+	/// If the index is 0, the interface is selected by the kernel.
+	pub fn set_multicast_interface_v6(&self, interface_index: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_IF, interface_index as c_int)
+	}
+
+	/// Get the index of the outgoing interface for IPv6 multicast packets.
+	pub fn multicast_interface_v6(&self) -> std::io::Result<u32> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_IF)?;
+		Ok(value as u32)
+	}
+}
+
+/// Convert an IPv4 address to a C-compatible `struct in_addr`.
+fn in_addr(address: &Ipv4Addr) -> libc::in_addr {
+	libc::in_addr { s_addr: u32::from(*address).to_be() }
+}
+
+/// Create a C-compatible `struct ip_mreq`.
+fn ip_mreq(group: &Ipv4Addr, interface: &Ipv4Addr) -> libc::ip_mreq {
+	libc::ip_mreq {
+		imr_multiaddr: in_addr(group),
+		imr_interface: in_addr(interface),
+	}
+}
+
+/// Create a C-compatible `struct ipv6_mreq`.
+fn ipv6_mreq(group: &Ipv6Addr, interface_index: u32) -> libc::ipv6_mreq {
+	libc::ipv6_mreq {
+		ipv6mr_multiaddr: libc::in6_addr { s6_addr: group.octets() },
+		ipv6mr_interface: interface_index as _,
+	}
+}
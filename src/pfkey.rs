@@ -0,0 +1,159 @@
+//! PF_KEY key management socket support.
+//!
+//! PF_KEY sockets (RFC 2367) are used by IPsec key management daemons
+//! to add, remove and monitor security associations in the kernel.
+//! The sockets have no address, so they use [`NoSocketAddress`] as address type.
+//!
+//! This module contains a minimal parser and builder for the `sadb_msg` base message header.
+//! The extension headers that follow the base header must be encoded and decoded by the user.
+
+use std::os::raw::c_int;
+
+use crate::{NoSocketAddress, Socket};
+
+/// The protocol number for version 2 of the PF_KEY protocol.
+pub const PF_KEY_V2: c_int = 2;
+
+/// Message type to allocate an SPI.
+pub const SADB_GETSPI: u8 = 1;
+/// Message type to update a larval security association.
+pub const SADB_UPDATE: u8 = 2;
+/// Message type to add a security association.
+pub const SADB_ADD: u8 = 3;
+/// Message type to delete a security association.
+pub const SADB_DELETE: u8 = 4;
+/// Message type to get a security association.
+pub const SADB_GET: u8 = 5;
+/// Message type sent by the kernel to request a new security association.
+pub const SADB_ACQUIRE: u8 = 6;
+/// Message type to register as key management daemon for an SA type.
+pub const SADB_REGISTER: u8 = 7;
+/// Message type sent by the kernel when a security association expires.
+pub const SADB_EXPIRE: u8 = 8;
+/// Message type to flush all security associations.
+pub const SADB_FLUSH: u8 = 9;
+/// Message type to dump all security associations.
+pub const SADB_DUMP: u8 = 10;
+
+/// Unspecified security association type.
+pub const SADB_SATYPE_UNSPEC: u8 = 0;
+/// Authentication Header security association type.
+pub const SADB_SATYPE_AH: u8 = 2;
+/// Encapsulating Security Payload security association type.
+pub const SADB_SATYPE_ESP: u8 = 3;
+
+/// The base header of a PF_KEY message (`struct sadb_msg`).
+///
+/// All fields are in host byte order.
+/// The length of the message is expressed in units of 8 bytes and includes the base header itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SadbMsg {
+	/// The PF_KEY protocol version, normally [`PF_KEY_V2`].
+	pub version: u8,
+
+	/// The message type, one of the `SADB_*` message types.
+	pub msg_type: u8,
+
+	/// The error code reported by the kernel, or 0.
+	pub errno: u8,
+
+	/// The security association type, one of the `SADB_SATYPE_*` values.
+	pub satype: u8,
+
+	/// The length of the whole message in units of 8 bytes.
+	pub len: u16,
+
+	/// Reserved, must be zero.
+	pub reserved: u16,
+
+	/// The sequence number of the message.
+	pub seq: u32,
+
+	/// The process ID of the sender, or 0 for messages from the kernel.
+	pub pid: u32,
+}
+
+impl SadbMsg {
+	/// The size of the base header in bytes.
+	pub const SIZE: usize = 16;
+
+	/// Create a new base header for a message without extension headers.
+	pub fn new(msg_type: u8, satype: u8, seq: u32, pid: u32) -> Self {
+		Self {
+			version: PF_KEY_V2 as u8,
+			msg_type,
+			errno: 0,
+			satype,
+			len: (Self::SIZE / 8) as u16,
+			reserved: 0,
+			seq,
+			pid,
+		}
+	}
+
+	/// Get the length of the whole message in bytes.
+	pub fn message_len(&self) -> usize {
+		usize::from(self.len) * 8
+	}
+
+	/// Set the length of the whole message in bytes, including the base header.
+	///
+	/// Returns an error if the length is not a multiple of 8, or if it is too large.
+	pub fn set_message_len(&mut self, len: usize) -> std::io::Result<()> {
+		if !len.is_multiple_of(8) || len / 8 > usize::from(u16::MAX) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid PF_KEY message length"));
+		}
+		self.len = (len / 8) as u16;
+		Ok(())
+	}
+
+	/// Parse a base header from the start of a message.
+	///
+	/// Returns an error if the data is too short to contain the header,
+	/// or if the message length in the header exceeds the length of the data.
+	pub fn parse(data: &[u8]) -> std::io::Result<Self> {
+		if data.len() < Self::SIZE {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "PF_KEY message too short"));
+		}
+		let header = Self {
+			version: data[0],
+			msg_type: data[1],
+			errno: data[2],
+			satype: data[3],
+			len: u16::from_ne_bytes([data[4], data[5]]),
+			reserved: u16::from_ne_bytes([data[6], data[7]]),
+			seq: u32::from_ne_bytes([data[8], data[9], data[10], data[11]]),
+			pid: u32::from_ne_bytes([data[12], data[13], data[14], data[15]]),
+		};
+		if header.message_len() < Self::SIZE || header.message_len() > data.len() {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid PF_KEY message length"));
+		}
+		Ok(header)
+	}
+
+	/// Encode the base header as bytes.
+	pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+		let mut output = [0; Self::SIZE];
+		output[0] = self.version;
+		output[1] = self.msg_type;
+		output[2] = self.errno;
+		output[3] = self.satype;
+		output[4..6].copy_from_slice(&self.len.to_ne_bytes());
+		output[6..8].copy_from_slice(&self.reserved.to_ne_bytes());
+		output[8..12].copy_from_slice(&self.seq.to_ne_bytes());
+		output[12..16].copy_from_slice(&self.pid.to_ne_bytes());
+		output
+	}
+}
+
+impl Socket<NoSocketAddress> {
+	/// Create a new PF_KEY version 2 socket.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	///
+	/// Creating PF_KEY sockets normally requires the `CAP_NET_ADMIN` capability.
+	/// See `man 7 pf_key` or RFC 2367 for more information.
+	pub fn new_pf_key() -> std::io::Result<Self> {
+		Self::new_generic(libc::PF_KEY, libc::SOCK_RAW, PF_KEY_V2)
+	}
+}
@@ -0,0 +1,199 @@
+//! Wait for events on multiple sockets with `poll`.
+//!
+//! This module provides [`poll_many()`] to wait for a small number of sockets (or other file descriptors)
+//! without pulling in an event loop.
+//! Each socket is described by a [`PollEntry`] with the [`PollFlags`] to wait for.
+//! After [`poll_many()`] returns, the entries hold the events that occurred.
+//!
+//! On Linux and Android this uses `ppoll` to support timeouts with nanosecond precision.
+//! On other platforms it uses `poll`, and timeouts are rounded up to whole milliseconds.
+//! See `man 2 poll` for more information.
+
+use std::marker::PhantomData;
+use std::os::raw::c_short;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::time::Duration;
+
+use crate::socket::check_ret;
+
+/// Events to wait for or events reported by [`poll_many()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct PollFlags(c_short);
+
+impl PollFlags {
+	/// No events.
+	pub const NONE: Self = Self(0);
+
+	/// There is data to read (`POLLIN`).
+	pub const READABLE: Self = Self(libc::POLLIN);
+
+	/// There is urgent data or another exceptional condition (`POLLPRI`).
+	pub const PRIORITY: Self = Self(libc::POLLPRI);
+
+	/// Data can be written without blocking (`POLLOUT`).
+	pub const WRITABLE: Self = Self(libc::POLLOUT);
+
+	/// The peer shut down the writing half of the connection (`POLLRDHUP`).
+	///
+	/// This flag is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub const READ_CLOSED: Self = Self(libc::POLLRDHUP);
+
+	/// An error is pending on the file descriptor (`POLLERR`).
+	///
+	/// This is always reported and does not need to be requested.
+	pub const ERROR: Self = Self(libc::POLLERR);
+
+	/// The file descriptor has been hung up (`POLLHUP`).
+	///
+	/// This is always reported and does not need to be requested.
+	pub const HANGUP: Self = Self(libc::POLLHUP);
+
+	/// The file descriptor is not open (`POLLNVAL`).
+	///
+	/// This is always reported and does not need to be requested.
+	pub const INVALID: Self = Self(libc::POLLNVAL);
+
+	/// Create a set of flags from the raw bits.
+	pub fn from_bits(bits: c_short) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the flags.
+	pub fn bits(self) -> c_short {
+		self.0
+	}
+
+	/// Check if all flags in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Check if any flag in `other` is also set in `self`.
+	pub fn intersects(self, other: Self) -> bool {
+		self.0 & other.0 != 0
+	}
+
+	/// Check if no flags are set.
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	/// Check if the file descriptor is readable.
+	pub fn is_readable(self) -> bool {
+		self.intersects(Self::READABLE)
+	}
+
+	/// Check if the file descriptor is writable.
+	pub fn is_writable(self) -> bool {
+		self.intersects(Self::WRITABLE)
+	}
+
+	/// Check if an error is pending, the file descriptor has been hung up or is not open.
+	pub fn is_error(self) -> bool {
+		self.intersects(Self::ERROR | Self::HANGUP | Self::INVALID)
+	}
+}
+
+impl std::ops::BitOr for PollFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for PollFlags {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// A file descriptor to wait for with [`poll_many()`].
+///
+/// The entry borrows the socket for its lifetime, so the file descriptor can not be closed while it is being polled.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct PollEntry<'a> {
+	inner: libc::pollfd,
+	_fd: PhantomData<&'a ()>,
+}
+
+impl<'a> PollEntry<'a> {
+	/// Create a new entry to wait for `events` on a socket or other file descriptor.
+	pub fn new<T: AsRawFd + ?Sized>(fd: &'a T, events: PollFlags) -> Self {
+		Self {
+			inner: libc::pollfd {
+				fd: fd.as_raw_fd(),
+				events: events.bits(),
+				revents: 0,
+			},
+			_fd: PhantomData,
+		}
+	}
+
+	/// Get the file descriptor of the entry.
+	pub fn fd(&self) -> RawFd {
+		self.inner.fd
+	}
+
+	/// Get the events to wait for.
+	pub fn events(&self) -> PollFlags {
+		PollFlags(self.inner.events)
+	}
+
+	/// Change the events to wait for.
+	pub fn set_events(&mut self, events: PollFlags) {
+		self.inner.events = events.bits();
+	}
+
+	/// Get the events reported by the last call to [`poll_many()`].
+	pub fn revents(&self) -> PollFlags {
+		PollFlags(self.inner.revents)
+	}
+}
+
+/// Wait for events on multiple sockets or other file descriptors.
+///
+/// If `timeout` is `None`, this function blocks until an event occurs on at least one entry.
+/// Otherwise, it returns after the timeout expires, possibly with no events.
+///
+/// The reported events are stored in the entries and can be retrieved with [`PollEntry::revents()`].
+/// Returns the number of entries with reported events.
+pub fn poll_many(entries: &mut [PollEntry], timeout: Option<Duration>) -> std::io::Result<usize> {
+	let entries_ptr = entries.as_mut_ptr() as *mut libc::pollfd;
+	let count = entries.len() as libc::nfds_t;
+
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	let ret = unsafe {
+		let timeout = timeout.map(|timeout| libc::timespec {
+			tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+			tv_nsec: timeout.subsec_nanos() as _,
+		});
+		let timeout_ptr = timeout.as_ref().map_or(std::ptr::null(), |timeout| timeout as *const libc::timespec);
+		libc::ppoll(entries_ptr, count, timeout_ptr, std::ptr::null())
+	};
+
+	#[cfg(not(any(target_os = "linux", target_os = "android")))]
+	let ret = unsafe {
+		libc::poll(entries_ptr, count, timeout_millis(timeout))
+	};
+
+	Ok(check_ret(ret)? as usize)
+}
+
+/// Convert a timeout to milliseconds for `poll` and `epoll_wait`.
+///
+/// `None` is converted to -1 to block indefinitely.
+/// Other values are rounded up to whole milliseconds and clamped to the maximum value of a [`c_int`](std::os::raw::c_int).
+#[cfg(any(feature = "epoll", not(any(target_os = "linux", target_os = "android"))))]
+pub(crate) fn timeout_millis(timeout: Option<Duration>) -> std::os::raw::c_int {
+	match timeout {
+		None => -1,
+		Some(timeout) => {
+			let millis = timeout.as_nanos().saturating_add(999_999) / 1_000_000;
+			millis.min(std::os::raw::c_int::MAX as u128) as std::os::raw::c_int
+		},
+	}
+}
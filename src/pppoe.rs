@@ -0,0 +1,53 @@
+//! PPP over Ethernet (PPPoE) support.
+//!
+//! PPPoE session sockets are created with the `AF_PPPOX` domain, the `SOCK_STREAM` type
+//! and the protocol [`PppoeSocketAddress::PROTOCOL`](crate::PppoeSocketAddress::PROTOCOL).
+//! Connecting the socket to a [`PppoeSocketAddress`](crate::PppoeSocketAddress) with a non-zero session ID
+//! attaches it to the PPPoE session that was negotiated with the peer during the discovery stage.
+//!
+//! PPPoE sockets are a non-portable Linux extension.
+
+use std::os::raw::c_int;
+
+use crate::socket::check_ret;
+use crate::{AsSocketAddress, PppoeSocketAddress, Socket};
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Get the PPP channel index of a connected PPPoE session socket.
+	///
+	/// The channel index is used to attach the session to a PPP unit with the `PPPIOCATTCHAN` ioctl on `/dev/ppp`.
+	///
+	/// This uses the `PPPIOCGCHAN` ioctl.
+	/// See the Linux kernel documentation on the PPP generic driver for more information.
+	pub fn ppp_channel_index(&self) -> std::io::Result<c_int> {
+		unsafe {
+			let mut index: c_int = 0;
+			check_ret(libc::ioctl(self.as_raw_fd(), libc::_IOR::<c_int>(b't' as u32, 55), &mut index))?;
+			Ok(index)
+		}
+	}
+
+	/// Forward the PPPoE session of this socket to a different PPPoE address.
+	///
+	/// This is used by access concentrators and relays to forward a session to another interface and peer.
+	///
+	/// This uses the `PPPOEIOCSFWD` ioctl.
+	pub fn set_pppoe_forward(&self, address: &PppoeSocketAddress) -> std::io::Result<()> {
+		unsafe {
+			check_ret(libc::ioctl(self.as_raw_fd(), libc::_IOW::<libc::size_t>(0xB1, 0), address.as_sockaddr()))?;
+			Ok(())
+		}
+	}
+
+	/// Stop forwarding the PPPoE session of this socket.
+	///
+	/// This removes the forwarding set up with [`set_pppoe_forward()`](Socket::set_pppoe_forward).
+	///
+	/// This uses the `PPPOEIOCDFWD` ioctl.
+	pub fn clear_pppoe_forward(&self) -> std::io::Result<()> {
+		unsafe {
+			check_ret(libc::ioctl(self.as_raw_fd(), libc::_IO(0xB1, 1)))?;
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,245 @@
+//! Name resolution with `getaddrinfo` and `getnameinfo`.
+//!
+//! Use [`resolve()`] to look up the socket addresses for a host name and service,
+//! and [`Hints`] to restrict the results to a specific address family, socket type or protocol.
+//! Use [`name_info()`] to look up the host name and service for a socket address.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, SocketAddress};
+
+/// Hints to restrict the results of [`resolve()`].
+///
+/// The default hints allow any address family, socket type and protocol.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hints {
+	/// The address family of the results, or `AF_UNSPEC` (0) for any family.
+	pub family: c_int,
+
+	/// The socket type of the results, or 0 for any type.
+	pub kind: c_int,
+
+	/// The protocol of the results, or 0 for any protocol.
+	pub protocol: c_int,
+
+	/// The `AI_*` flags for the lookup, like `AI_PASSIVE`, `AI_NUMERICHOST` or `AI_CANONNAME`.
+	pub flags: c_int,
+}
+
+impl Hints {
+	/// Create hints that allow any address family, socket type and protocol.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Restrict the results to an address family.
+	pub fn family(mut self, family: c_int) -> Self {
+		self.family = family;
+		self
+	}
+
+	/// Restrict the results to a socket type.
+	pub fn kind(mut self, kind: impl Into<c_int>) -> Self {
+		self.kind = kind.into();
+		self
+	}
+
+	/// Restrict the results to a protocol.
+	pub fn protocol(mut self, protocol: impl Into<c_int>) -> Self {
+		self.protocol = protocol.into();
+		self
+	}
+
+	/// Set the `AI_*` flags for the lookup.
+	pub fn flags(mut self, flags: c_int) -> Self {
+		self.flags = flags;
+		self
+	}
+}
+
+/// A single result of [`resolve()`].
+#[derive(Debug, Clone)]
+pub struct AddressInfo {
+	/// The resolved socket address.
+	pub address: SocketAddress,
+
+	/// The address family of the socket address.
+	pub family: c_int,
+
+	/// The socket type to use with the address.
+	pub kind: c_int,
+
+	/// The protocol to use with the address.
+	pub protocol: c_int,
+
+	/// The canonical name of the host.
+	///
+	/// This is only set for the first result, and only if the `AI_CANONNAME` flag was given.
+	pub canonical_name: Option<String>,
+}
+
+/// Iterator over the results of [`resolve()`].
+pub struct AddressInfoIter {
+	head: *mut libc::addrinfo,
+	next: *mut libc::addrinfo,
+}
+
+impl Iterator for AddressInfoIter {
+	type Item = AddressInfo;
+
+	fn next(&mut self) -> Option<AddressInfo> {
+		loop {
+			let info = unsafe { self.next.as_ref()? };
+			self.next = info.ai_next;
+
+			let len = info.ai_addrlen;
+			if info.ai_addr.is_null() || len as usize > std::mem::size_of::<libc::sockaddr_storage>() {
+				continue;
+			}
+
+			unsafe {
+				let mut storage: libc::sockaddr_storage = std::mem::zeroed();
+				std::ptr::copy(info.ai_addr as *const u8, &mut storage as *mut _ as *mut u8, len as usize);
+				let canonical_name = if info.ai_canonname.is_null() {
+					None
+				} else {
+					Some(CStr::from_ptr(info.ai_canonname).to_string_lossy().into_owned())
+				};
+				return Some(AddressInfo {
+					address: SocketAddress::from_raw(storage, len),
+					family: info.ai_family,
+					kind: info.ai_socktype,
+					protocol: info.ai_protocol,
+					canonical_name,
+				});
+			}
+		}
+	}
+}
+
+impl Drop for AddressInfoIter {
+	fn drop(&mut self) {
+		unsafe {
+			libc::freeaddrinfo(self.head);
+		}
+	}
+}
+
+/// An error from `getaddrinfo` or `getnameinfo`.
+#[derive(Debug)]
+pub enum ResolveError {
+	/// A system error, reported as `EAI_SYSTEM`.
+	System(std::io::Error),
+
+	/// A resolver error with an `EAI_*` error code.
+	Resolver(c_int),
+
+	/// The host or service name contains a null byte.
+	NullByte,
+}
+
+impl ResolveError {
+	/// Create an error from the return value of `getaddrinfo` or `getnameinfo`.
+	pub(crate) fn from_code(code: c_int) -> Self {
+		if code == libc::EAI_SYSTEM {
+			Self::System(std::io::Error::last_os_error())
+		} else {
+			Self::Resolver(code)
+		}
+	}
+}
+
+impl std::fmt::Display for ResolveError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			Self::System(e) => write!(f, "{}", e),
+			Self::Resolver(code) => {
+				let message = unsafe { CStr::from_ptr(libc::gai_strerror(*code)) };
+				write!(f, "{}", message.to_string_lossy())
+			},
+			Self::NullByte => write!(f, "name contains a null byte"),
+		}
+	}
+}
+
+impl std::error::Error for ResolveError {}
+
+impl From<ResolveError> for std::io::Error {
+	fn from(other: ResolveError) -> Self {
+		match other {
+			ResolveError::System(e) => e,
+			ResolveError::NullByte => std::io::Error::new(std::io::ErrorKind::InvalidInput, other),
+			ResolveError::Resolver(code) if code == libc::EAI_NONAME => std::io::Error::new(std::io::ErrorKind::NotFound, other),
+			ResolveError::Resolver(_) => std::io::Error::other(other),
+		}
+	}
+}
+
+/// Resolve a host name and service to socket addresses with `getaddrinfo`.
+///
+/// At least one of `host` and `service` must be given.
+/// The service can be a port number or a service name like `"http"`.
+///
+/// See `man getaddrinfo` for more information.
+pub fn resolve(host: Option<&str>, service: Option<&str>, hints: &Hints) -> Result<AddressInfoIter, ResolveError> {
+	let host = host.map(CString::new).transpose().map_err(|_| ResolveError::NullByte)?;
+	let service = service.map(CString::new).transpose().map_err(|_| ResolveError::NullByte)?;
+
+	unsafe {
+		let mut raw_hints: libc::addrinfo = std::mem::zeroed();
+		raw_hints.ai_family = hints.family;
+		raw_hints.ai_socktype = hints.kind;
+		raw_hints.ai_protocol = hints.protocol;
+		raw_hints.ai_flags = hints.flags;
+
+		let mut head = std::ptr::null_mut();
+		let ret = libc::getaddrinfo(
+			host.as_ref().map_or(std::ptr::null(), |x| x.as_ptr()),
+			service.as_ref().map_or(std::ptr::null(), |x| x.as_ptr()),
+			&raw_hints,
+			&mut head,
+		);
+		if ret != 0 {
+			return Err(ResolveError::from_code(ret));
+		}
+		Ok(AddressInfoIter { head, next: head })
+	}
+}
+
+/// The maximum length of a host name returned by `getnameinfo`, including the terminating null byte.
+const NI_MAXHOST: usize = 1025;
+
+/// The maximum length of a service name returned by `getnameinfo`, including the terminating null byte.
+const NI_MAXSERV: usize = 32;
+
+/// Look up the host name and service for a socket address with `getnameinfo`.
+///
+/// The flags are the `NI_*` flags, like `NI_NUMERICHOST`, `NI_NUMERICSERV`, `NI_NOFQDN`, `NI_NAMEREQD` and `NI_DGRAM`.
+/// With `NI_NUMERICHOST` and `NI_NUMERICSERV`, no lookups are performed,
+/// which is useful for logging peer addresses.
+///
+/// Returns the host and service name.
+///
+/// See `man getnameinfo` for more information.
+pub fn name_info<Address: AsSocketAddress>(address: &Address, flags: c_int) -> Result<(String, String), ResolveError> {
+	let mut host = [0u8; NI_MAXHOST];
+	let mut service = [0u8; NI_MAXSERV];
+	unsafe {
+		let ret = libc::getnameinfo(
+			address.as_sockaddr(),
+			address.len(),
+			host.as_mut_ptr().cast(),
+			host.len() as _,
+			service.as_mut_ptr().cast(),
+			service.len() as _,
+			flags,
+		);
+		if ret != 0 {
+			return Err(ResolveError::from_code(ret));
+		}
+		let host = CStr::from_ptr(host.as_ptr().cast()).to_string_lossy().into_owned();
+		let service = CStr::from_ptr(service.as_ptr().cast()).to_string_lossy().into_owned();
+		Ok((host, service))
+	}
+}
@@ -0,0 +1,238 @@
+use std::os::raw::c_int;
+
+use crate::error::{Operation, ResultExt};
+use crate::{AsSocketAddress, Inet6SocketAddress, Socket, SpecificSocketAddress};
+
+/// A socket that is listening for incoming connections.
+///
+/// This is a thin wrapper around [`Socket`] that only exposes functions that make sense for a listening socket.
+/// Use [`Self::as_socket()`] or [`Self::into_socket()`] to access the full API.
+pub struct Listener<Address> {
+	socket: Socket<Address>,
+}
+
+/// A connection oriented socket for a byte stream.
+///
+/// This is a thin wrapper around [`Socket`] that only exposes functions that make sense for a connected stream socket.
+/// It implements [`std::io::Read`] and [`std::io::Write`].
+/// Use [`Self::as_socket()`] or [`Self::into_socket()`] to access the full API.
+pub struct StreamSocket<Address> {
+	socket: Socket<Address>,
+}
+
+/// A datagram socket.
+///
+/// This is a thin wrapper around [`Socket`] that only exposes functions that make sense for a datagram socket.
+/// Use [`Self::as_socket()`] or [`Self::into_socket()`] to access the full API.
+pub struct DatagramSocket<Address> {
+	socket: Socket<Address>,
+}
+
+impl<Address: AsSocketAddress> Listener<Address> {
+	/// Create a stream socket, bind it to a local address and put it in listening mode.
+	pub fn bind(address: &Address, backlog: c_int) -> std::io::Result<Self>
+	where
+		Address: SpecificSocketAddress,
+	{
+		let socket = Socket::new(libc::SOCK_STREAM, 0)?;
+		socket.bind(address).context_address(Operation::Bind, address)?;
+		socket.listen(backlog).context(Operation::Listen)?;
+		Ok(Self { socket })
+	}
+
+	/// Wrap a socket that is already in listening mode.
+	///
+	/// It is your own responsibility to make sure the socket is a listening socket.
+	pub fn from_socket(socket: Socket<Address>) -> Self {
+		Self { socket }
+	}
+
+	/// Accept a new connection.
+	///
+	/// Returns the connected socket and the address of the peer.
+	pub fn accept(&self) -> std::io::Result<(StreamSocket<Address>, Address)> {
+		let (socket, address) = self.socket.accept()?;
+		Ok((StreamSocket { socket }, address))
+	}
+
+	/// Get the local address the listener is bound to.
+	pub fn local_addr(&self) -> std::io::Result<Address> {
+		self.socket.local_addr()
+	}
+
+	/// Get a reference to the wrapped socket.
+	pub fn as_socket(&self) -> &Socket<Address> {
+		&self.socket
+	}
+
+	/// Unwrap the socket.
+	pub fn into_socket(self) -> Socket<Address> {
+		self.socket
+	}
+}
+
+impl Listener<Inet6SocketAddress> {
+	/// Create a dual-stack listener for both IPv6 and IPv4 connections on all interfaces.
+	///
+	/// The socket is bound to `[::]` with the given port after clearing the `IPV6_V6ONLY` option.
+	/// Connections from IPv4 peers are reported with an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`),
+	/// which can be converted with [`Ipv6Addr::to_ipv4()`](std::net::Ipv6Addr::to_ipv4).
+	pub fn bind_dual_stack(port: u16, backlog: c_int) -> std::io::Result<Self> {
+		let socket = Socket::new(libc::SOCK_STREAM, 0)?;
+		socket.set_ipv6_only(false)?;
+		let address = Inet6SocketAddress::new(std::net::Ipv6Addr::UNSPECIFIED, port, 0, 0);
+		socket.bind(&address).context_address(Operation::Bind, &address)?;
+		socket.listen(backlog).context(Operation::Listen)?;
+		Ok(Self { socket })
+	}
+}
+
+impl<Address: AsSocketAddress> StreamSocket<Address> {
+	/// Create a stream socket and connect it to a remote address.
+	pub fn connect(address: &Address) -> std::io::Result<Self>
+	where
+		Address: SpecificSocketAddress,
+	{
+		let socket = Socket::new(libc::SOCK_STREAM, 0)?;
+		socket.connect(address).context_address(Operation::Connect, address)?;
+		Ok(Self { socket })
+	}
+
+	/// Wrap a socket that is already connected.
+	///
+	/// It is your own responsibility to make sure the socket is a connected stream socket.
+	pub fn from_socket(socket: Socket<Address>) -> Self {
+		Self { socket }
+	}
+
+	/// Send data to the peer.
+	///
+	/// See [`Socket::send()`] for more information.
+	pub fn send(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
+		self.socket.send(data, flags)
+	}
+
+	/// Receive data from the peer.
+	///
+	/// See [`Socket::recv()`] for more information.
+	pub fn recv(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<usize> {
+		self.socket.recv(buffer, flags)
+	}
+
+	/// Get the local address of the socket.
+	pub fn local_addr(&self) -> std::io::Result<Address> {
+		self.socket.local_addr()
+	}
+
+	/// Get the address of the peer.
+	pub fn peer_addr(&self) -> std::io::Result<Address> {
+		self.socket.peer_addr()
+	}
+
+	/// Get a reference to the wrapped socket.
+	pub fn as_socket(&self) -> &Socket<Address> {
+		&self.socket
+	}
+
+	/// Unwrap the socket.
+	pub fn into_socket(self) -> Socket<Address> {
+		self.socket
+	}
+}
+
+impl<Address: AsSocketAddress> DatagramSocket<Address> {
+	/// Create a datagram socket and bind it to a local address.
+	pub fn bind(address: &Address) -> std::io::Result<Self>
+	where
+		Address: SpecificSocketAddress,
+	{
+		let socket = Socket::new(libc::SOCK_DGRAM, 0)?;
+		socket.bind(address).context_address(Operation::Bind, address)?;
+		Ok(Self { socket })
+	}
+
+	/// Wrap an existing datagram socket.
+	///
+	/// It is your own responsibility to make sure the socket is a datagram socket.
+	pub fn from_socket(socket: Socket<Address>) -> Self {
+		Self { socket }
+	}
+
+	/// Set the default destination of the socket and only receive datagrams from that address.
+	///
+	/// See [`Socket::connect()`] for more information.
+	pub fn connect(&self, address: &Address) -> std::io::Result<()> {
+		self.socket.connect(address)
+	}
+
+	/// Send a datagram to a specific address.
+	///
+	/// See [`Socket::send_to()`] for more information.
+	pub fn send_to(&self, data: &[u8], address: &Address, flags: c_int) -> std::io::Result<usize> {
+		self.socket.send_to(data, address, flags)
+	}
+
+	/// Receive a datagram and the address of the sender.
+	///
+	/// See [`Socket::recv_from()`] for more information.
+	pub fn recv_from(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<(Option<Address>, usize)> {
+		self.socket.recv_from(buffer, flags)
+	}
+
+	/// Send a datagram to the default destination set with [`Self::connect()`].
+	pub fn send(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
+		self.socket.send(data, flags)
+	}
+
+	/// Receive a datagram.
+	pub fn recv(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<usize> {
+		self.socket.recv(buffer, flags)
+	}
+
+	/// Get the local address of the socket.
+	pub fn local_addr(&self) -> std::io::Result<Address> {
+		self.socket.local_addr()
+	}
+
+	/// Get a reference to the wrapped socket.
+	pub fn as_socket(&self) -> &Socket<Address> {
+		&self.socket
+	}
+
+	/// Unwrap the socket.
+	pub fn into_socket(self) -> Socket<Address> {
+		self.socket
+	}
+}
+
+impl<Address: AsSocketAddress> std::io::Read for StreamSocket<Address> {
+	fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+		self.socket.recv(buffer, 0)
+	}
+}
+
+impl<Address: AsSocketAddress> std::io::Read for &StreamSocket<Address> {
+	fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+		self.socket.recv(buffer, 0)
+	}
+}
+
+impl<Address: AsSocketAddress> std::io::Write for StreamSocket<Address> {
+	fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+		self.socket.send(data, 0)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<Address: AsSocketAddress> std::io::Write for &StreamSocket<Address> {
+	fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+		self.socket.send(data, 0)
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
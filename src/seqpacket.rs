@@ -0,0 +1,153 @@
+use std::io::{IoSlice, IoSliceMut};
+use std::os::raw::c_int;
+
+use crate::ancillary::SocketAncillary;
+use crate::error::{Operation, ResultExt};
+use crate::{RecvMessage, Socket, UnixSocketAddress};
+
+/// A unix socket listening for `SOCK_SEQPACKET` connections.
+///
+/// This is a thin wrapper around [`Socket`] that only exposes functions that make sense for a listening socket.
+/// Use [`Self::as_socket()`] or [`Self::into_socket()`] to access the full API.
+pub struct UnixSeqpacketListener {
+	socket: Socket<UnixSocketAddress>,
+}
+
+/// A connected unix socket of type `SOCK_SEQPACKET`.
+///
+/// A sequenced packet socket is connection oriented like a stream socket,
+/// but it preserves message boundaries like a datagram socket.
+/// Each call to [`Self::send_record()`] sends one record,
+/// and each call to [`Self::recv_record()`] receives at most one record.
+///
+/// This is a thin wrapper around [`Socket`] that only exposes functions that make sense for a sequenced packet socket.
+/// Use [`Self::as_socket()`] or [`Self::into_socket()`] to access the full API.
+pub struct UnixSeqpacket {
+	socket: Socket<UnixSocketAddress>,
+}
+
+impl Socket<UnixSocketAddress> {
+	/// Create a new unix socket of type `SOCK_SEQPACKET`.
+	pub fn new_seqpacket() -> std::io::Result<Self> {
+		Self::new(libc::SOCK_SEQPACKET, 0)
+	}
+}
+
+impl UnixSeqpacketListener {
+	/// Create a sequenced packet socket, bind it to a local address and put it in listening mode.
+	pub fn bind(address: &UnixSocketAddress, backlog: c_int) -> std::io::Result<Self> {
+		let socket = Socket::new_seqpacket()?;
+		socket.bind(address).context_address(Operation::Bind, address)?;
+		socket.listen(backlog).context(Operation::Listen)?;
+		Ok(Self { socket })
+	}
+
+	/// Wrap a sequenced packet socket that is already in listening mode.
+	///
+	/// It is your own responsibility to make sure the socket is a listening `SOCK_SEQPACKET` socket.
+	pub fn from_socket(socket: Socket<UnixSocketAddress>) -> Self {
+		Self { socket }
+	}
+
+	/// Accept a new connection.
+	///
+	/// Returns the connected socket and the address of the peer.
+	pub fn accept(&self) -> std::io::Result<(UnixSeqpacket, UnixSocketAddress)> {
+		let (socket, address) = self.socket.accept()?;
+		Ok((UnixSeqpacket { socket }, address))
+	}
+
+	/// Get the local address the listener is bound to.
+	pub fn local_addr(&self) -> std::io::Result<UnixSocketAddress> {
+		self.socket.local_addr()
+	}
+
+	/// Get a reference to the wrapped socket.
+	pub fn as_socket(&self) -> &Socket<UnixSocketAddress> {
+		&self.socket
+	}
+
+	/// Unwrap the socket.
+	pub fn into_socket(self) -> Socket<UnixSocketAddress> {
+		self.socket
+	}
+}
+
+impl UnixSeqpacket {
+	/// Create a sequenced packet socket and connect it to a listening socket.
+	pub fn connect(address: &UnixSocketAddress) -> std::io::Result<Self> {
+		let socket = Socket::new_seqpacket()?;
+		socket.connect(address).context_address(Operation::Connect, address)?;
+		Ok(Self { socket })
+	}
+
+	/// Create a connected pair of sequenced packet sockets.
+	pub fn pair() -> std::io::Result<(Self, Self)> {
+		let (a, b) = Socket::pair(libc::SOCK_SEQPACKET, 0)?;
+		Ok((Self { socket: a }, Self { socket: b }))
+	}
+
+	/// Wrap a socket that is already connected.
+	///
+	/// It is your own responsibility to make sure the socket is a connected `SOCK_SEQPACKET` socket.
+	pub fn from_socket(socket: Socket<UnixSocketAddress>) -> Self {
+		Self { socket }
+	}
+
+	/// Send a single record to the peer.
+	///
+	/// The record is sent atomically: either the whole record is sent or an error is returned.
+	/// If the record is too large for the socket, the error is `EMSGSIZE`.
+	pub fn send_record(&self, data: &[u8]) -> std::io::Result<()> {
+		self.send_record_vectored(&[IoSlice::new(data)])
+	}
+
+	/// Send a single record, gathered from multiple buffers.
+	///
+	/// The record is sent atomically: either the whole record is sent or an error is returned.
+	pub fn send_record_vectored(&self, data: &[IoSlice]) -> std::io::Result<()> {
+		let total: usize = data.iter().map(|x| x.len()).sum();
+		let sent = self.socket.send_msg(data, None, 0)?;
+		if sent != total {
+			return Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "failed to send the whole record"));
+		}
+		Ok(())
+	}
+
+	/// Receive a single record from the peer.
+	///
+	/// If the record does not fit in the buffer, the remainder of the record is discarded
+	/// and [`RecvMessage::truncated`] is set.
+	/// A received record with a `byte_count` of zero and no flags indicates that the peer closed the connection,
+	/// unless the peer sent an empty record.
+	pub fn recv_record(&self, buffer: &mut [u8]) -> std::io::Result<RecvMessage<UnixSocketAddress>> {
+		self.recv_record_vectored(&[IoSliceMut::new(buffer)])
+	}
+
+	/// Receive a single record from the peer, scattered over multiple buffers.
+	///
+	/// See [`Self::recv_record()`] for more information.
+	pub fn recv_record_vectored(&self, buffers: &[IoSliceMut]) -> std::io::Result<RecvMessage<UnixSocketAddress>> {
+		self.socket.recv_msg(buffers, &mut SocketAncillary::new(&mut []), 0)
+	}
+
+	/// Get the local address of the socket.
+	pub fn local_addr(&self) -> std::io::Result<UnixSocketAddress> {
+		self.socket.local_addr()
+	}
+
+	/// Get the address of the peer.
+	pub fn peer_addr(&self) -> std::io::Result<UnixSocketAddress> {
+		self.socket.peer_addr()
+	}
+
+	/// Get a reference to the wrapped socket.
+	pub fn as_socket(&self) -> &Socket<UnixSocketAddress> {
+		&self.socket
+	}
+
+	/// Unwrap the socket.
+	pub fn into_socket(self) -> Socket<UnixSocketAddress> {
+		self.socket
+	}
+}
@@ -0,0 +1,74 @@
+//! Shared Memory Communications (SMC) support.
+//!
+//! SMC sockets (`AF_SMC`) provide a TCP compatible stream socket that transfers data over RDMA (SMC-R)
+//! or internal shared memory (SMC-D) when both peers support it.
+//! When the peer does not support SMC, the kernel transparently uses a normal TCP connection instead.
+//!
+//! SMC sockets use the same socket addresses as TCP sockets.
+//!
+//! SMC sockets are a non-portable Linux extension.
+
+use std::os::raw::c_int;
+
+use crate::{Inet4SocketAddress, Inet6SocketAddress, Socket};
+
+/// The address family of SMC sockets.
+pub const AF_SMC: c_int = 43;
+
+/// The protocol number for SMC sockets with IPv4 addresses.
+pub const SMCPROTO_SMC: c_int = 0;
+
+/// The protocol number for SMC sockets with IPv6 addresses.
+pub const SMCPROTO_SMC6: c_int = 1;
+
+/// Socket address for SMC sockets with IPv4 addresses.
+pub type Smc4SocketAddress = Inet4SocketAddress;
+
+/// Socket address for SMC sockets with IPv6 addresses.
+pub type Smc6SocketAddress = Inet6SocketAddress;
+
+impl Socket<Inet4SocketAddress> {
+	/// Create a new SMC socket for IPv4 addresses.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	///
+	/// Returns an error if the kernel does not support SMC sockets.
+	pub fn new_smc() -> std::io::Result<Self> {
+		Self::new_generic(AF_SMC, libc::SOCK_STREAM, SMCPROTO_SMC)
+	}
+
+	/// Create a new SMC socket for IPv4 addresses, falling back to a TCP socket if SMC is not supported.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	pub fn new_smc_or_tcp() -> std::io::Result<Self> {
+		smc_or_tcp(Self::new_smc, || Self::new(libc::SOCK_STREAM, libc::IPPROTO_TCP))
+	}
+}
+
+impl Socket<Inet6SocketAddress> {
+	/// Create a new SMC socket for IPv6 addresses.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	///
+	/// Returns an error if the kernel does not support SMC sockets.
+	pub fn new_smc() -> std::io::Result<Self> {
+		Self::new_generic(AF_SMC, libc::SOCK_STREAM, SMCPROTO_SMC6)
+	}
+
+	/// Create a new SMC socket for IPv6 addresses, falling back to a TCP socket if SMC is not supported.
+	///
+	/// The created socket has the `close-on-exec` flag set.
+	pub fn new_smc_or_tcp() -> std::io::Result<Self> {
+		smc_or_tcp(Self::new_smc, || Self::new(libc::SOCK_STREAM, libc::IPPROTO_TCP))
+	}
+}
+
+/// Try to create an SMC socket, and create a TCP socket if the kernel does not support SMC.
+fn smc_or_tcp<T>(smc: impl FnOnce() -> std::io::Result<T>, tcp: impl FnOnce() -> std::io::Result<T>) -> std::io::Result<T> {
+	smc().or_else(|e| {
+		match e.raw_os_error() {
+			Some(libc::EAFNOSUPPORT) | Some(libc::EPROTONOSUPPORT) => tcp(),
+			_ => Err(e),
+		}
+	})
+}
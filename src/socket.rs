@@ -1,25 +1,31 @@
 use filedesc::FileDesc;
 use std::io::{IoSlice, IoSliceMut};
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::os::raw::{c_int, c_void};
-use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd};
+use std::os::unix::io::{RawFd, AsRawFd, IntoRawFd, FromRawFd, AsFd, BorrowedFd, OwnedFd};
+use std::time::Instant;
 
 use crate::AsSocketAddress;
 use crate::ancillary::SocketAncillary;
+use crate::error::{Operation, ResultExt};
+use crate::poll::{poll_many, PollEntry, PollFlags};
 
 /// A POSIX socket.
 pub struct Socket<Address> {
 	fd: FileDesc,
+	retry_interrupted: AtomicBool,
 	_address: std::marker::PhantomData<fn() -> Address>,
 }
 
-#[cfg(not(any(target_os = "apple", target_os = "solaris")))]
-mod extra_flags {
+#[cfg(not(any(target_vendor = "apple", target_os = "solaris")))]
+pub(crate) mod extra_flags {
 	pub const SENDMSG: std::os::raw::c_int = libc::MSG_NOSIGNAL;
 	pub const RECVMSG: std::os::raw::c_int = libc::MSG_CMSG_CLOEXEC;
 }
 
-#[cfg(any(target_os = "apple", target_os = "solaris"))]
-mod extra_flags {
+#[cfg(any(target_vendor = "apple", target_os = "solaris"))]
+pub(crate) mod extra_flags {
 	pub const SENDMSG: std::os::raw::c_int = 0;
 	pub const RECVMSG: std::os::raw::c_int = 0;
 }
@@ -27,15 +33,17 @@ mod extra_flags {
 impl<Address: AsSocketAddress> Socket<Address> {
 	/// Wrap a file descriptor in a Socket.
 	///
-	/// On Apple systems, this sets the SO_NOSIGPIPE option to prevent SIGPIPE signals.
-	fn wrap(fd: FileDesc) -> std::io::Result<Self> {
+	/// On Apple systems, FreeBSD, NetBSD and DragonFly BSD, this sets the SO_NOSIGPIPE option to prevent SIGPIPE signals.
+	pub(crate) fn wrap(fd: FileDesc) -> std::io::Result<Self> {
 		let wrapped = Self {
 			fd,
+			retry_interrupted: AtomicBool::new(false),
 			_address: std::marker::PhantomData,
 		};
 
-		#[cfg(target_os = "apple")]
-		wrapped.set_option(libc::SOL_SOCKET, libc::SO_NOSIGPIPE, 1 as c_int)?;
+		#[cfg(any(target_vendor = "apple", target_os = "freebsd", target_os = "netbsd", target_os = "dragonfly"))]
+		wrapped.set_option(libc::SOL_SOCKET, libc::SO_NOSIGPIPE, 1 as c_int)
+			.context(Operation::SetOption { level: libc::SOL_SOCKET, name: libc::SO_NOSIGPIPE })?;
 
 		Ok(wrapped)
 	}
@@ -47,8 +55,11 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// The created socket has the `close-on-exec` flag set.
 	/// The flag will be set atomically when the socket is created if the platform supports it.
 	///
+	/// The type and protocol can be given as [`SocketType`](crate::SocketType) and [`Protocol`](crate::Protocol),
+	/// or as raw `c_int` values.
+	///
 	/// See `man socket` for more information.
-	pub fn new(kind: c_int, protocol: c_int) -> std::io::Result<Self>
+	pub fn new(kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<Self>
 	where
 		Address: crate::SpecificSocketAddress,
 	{
@@ -64,19 +75,31 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// The flag will be set atomically when the socket is created if the platform supports it.
 	///
 	/// See `man socket` for more information.
-	pub fn new_generic(domain: c_int, kind: c_int, protocol: c_int) -> std::io::Result<Self> {
-		socket(domain, kind | libc::SOCK_CLOEXEC, protocol)
+	pub fn new_generic(domain: c_int, kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<Self> {
+		let (kind, protocol) = (kind.into(), protocol.into());
+
+		#[cfg(not(target_vendor = "apple"))]
+		let fd = socket(domain, kind | libc::SOCK_CLOEXEC, protocol)
 			.or_else(|e| {
 				// Fall back to setting close-on-exec after creation if SOCK_CLOEXEC is not supported.
 				if e.raw_os_error() == Some(libc::EINVAL) {
 					let fd = socket(domain, kind, protocol)?;
-					fd.set_close_on_exec(true)?;
+					fd.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
 					Ok(fd)
 				} else {
 					Err(e)
 				}
-			})
-			.and_then(Self::wrap)
+			})?;
+
+		// Apple platforms do not support SOCK_CLOEXEC at all.
+		#[cfg(target_vendor = "apple")]
+		let fd = {
+			let fd = socket(domain, kind, protocol)?;
+			fd.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+			fd
+		};
+
+		Self::wrap(fd)
 	}
 
 	/// Create a connected pair of socket with the specified type and protocol.
@@ -86,8 +109,11 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// The created sockets have the `close-on-exec` flag set.
 	/// The flag will be set atomically when the sockets are created if the platform supports it.
 	///
+	/// The type and protocol can be given as [`SocketType`](crate::SocketType) and [`Protocol`](crate::Protocol),
+	/// or as raw `c_int` values.
+	///
 	/// See `man socketpair` and `man socket` for more information.
-	pub fn pair(kind: c_int, protocol: c_int) -> std::io::Result<(Self, Self)>
+	pub fn pair(kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<(Self, Self)>
 	where
 		Address: crate::SpecificSocketAddress,
 	{
@@ -103,22 +129,33 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// The flag will be set atomically when the sockets are created if the platform supports it.
 	///
 	/// See `man socketpair` and `man socket` for more information.
-	pub fn pair_generic(domain: c_int, kind: c_int, protocol: c_int) -> std::io::Result<(Self, Self)> {
-		socketpair(domain, kind | libc::SOCK_CLOEXEC, protocol)
+	pub fn pair_generic(domain: c_int, kind: impl Into<c_int>, protocol: impl Into<c_int>) -> std::io::Result<(Self, Self)> {
+		let (kind, protocol) = (kind.into(), protocol.into());
+
+		#[cfg(not(target_vendor = "apple"))]
+		let (a, b) = socketpair(domain, kind | libc::SOCK_CLOEXEC, protocol)
 			.or_else(|e| {
 				// Fall back to setting close-on-exec after creation if SOCK_CLOEXEC is not supported.
 				if e.raw_os_error() == Some(libc::EINVAL) {
 					let (a, b) = socketpair(domain, kind, protocol)?;
-					a.set_close_on_exec(true)?;
-					b.set_close_on_exec(true)?;
+					a.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+					b.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
 					Ok((a, b))
 				} else {
 					Err(e)
 				}
-			})
-			.and_then(|(a, b)| {
-				Ok((Self::wrap(a)?, Self::wrap(b)?))
-			})
+			})?;
+
+		// Apple platforms do not support SOCK_CLOEXEC at all.
+		#[cfg(target_vendor = "apple")]
+		let (a, b) = {
+			let (a, b) = socketpair(domain, kind, protocol)?;
+			a.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+			b.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+			(a, b)
+		};
+
+		Ok((Self::wrap(a)?, Self::wrap(b)?))
 	}
 
 	/// Try to clone the socket.
@@ -131,22 +168,46 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	pub fn try_clone(&self) -> std::io::Result<Self> {
 		Ok(Self {
 			fd: self.fd.duplicate()?,
+			retry_interrupted: AtomicBool::new(self.retry_interrupted()),
 			_address: std::marker::PhantomData,
 		})
 	}
 
+	/// Convert the socket into a socket with the generic [`SocketAddress`](crate::SocketAddress) address type.
+	///
+	/// This can not fail, since a generic socket address can hold addresses of any family.
+	/// Use [`Socket::try_into_specific()`] to convert it back.
+	pub fn into_generic(self) -> Socket<crate::SocketAddress> {
+		Socket {
+			fd: self.fd,
+			retry_interrupted: self.retry_interrupted,
+			_address: std::marker::PhantomData,
+		}
+	}
+
 	/// Wrap a raw file descriptor in a [`Socket`].
 	///
 	/// This function sets no flags or options on the file descriptor or socket.
 	/// It is your own responsibility to make sure the close-on-exec flag is already set,
-	/// and that the `SO_NOSIGPIPE` option is set on Apple platforms.
+	/// and that the `SO_NOSIGPIPE` option is set on platforms that support it.
 	pub unsafe fn from_raw_fd(fd: RawFd) -> Self {
 		Self {
 			fd: FileDesc::from_raw_fd(fd),
+			retry_interrupted: AtomicBool::new(false),
 			_address: std::marker::PhantomData,
 		}
 	}
 
+	/// Create a [`Socket`] by duplicating a borrowed file descriptor.
+	///
+	/// The new file descriptor will have the `close-on-exec` flag set.
+	/// On platforms that support it, this also sets the `SO_NOSIGPIPE` option on the socket.
+	/// The borrowed file descriptor remains owned by the caller.
+	pub fn from_borrowed_fd(fd: BorrowedFd<'_>) -> std::io::Result<Self> {
+		let fd = fd.try_clone_to_owned()?;
+		Self::wrap(unsafe { FileDesc::from_raw_fd(fd.into_raw_fd()) })
+	}
+
 	/// Get the raw file descriptor.
 	///
 	/// This function does not release ownership of the underlying file descriptor.
@@ -166,7 +227,7 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// Set a socket option.
 	///
 	/// See `man setsockopt` for more information.
-	fn set_option<T: Copy>(&self, level: c_int, option: c_int, value: T) -> std::io::Result<()> {
+	pub(crate) fn set_option<T: Copy>(&self, level: c_int, option: c_int, value: T) -> std::io::Result<()> {
 		unsafe {
 			let value = &value as *const T as *const c_void;
 			let length = std::mem::size_of::<T>() as libc::socklen_t;
@@ -175,10 +236,25 @@ impl<Address: AsSocketAddress> Socket<Address> {
 		}
 	}
 
+	/// Set a socket option to an array of values.
+	///
+	/// See `man setsockopt` for more information.
+	pub(crate) fn set_option_slice<T: Copy>(&self, level: c_int, option: c_int, values: &[T]) -> std::io::Result<()> {
+		unsafe {
+			let (value, length) = if values.is_empty() {
+				(std::ptr::null(), 0)
+			} else {
+				(values.as_ptr() as *const c_void, std::mem::size_of_val(values) as libc::socklen_t)
+			};
+			check_ret(libc::setsockopt(self.as_raw_fd(), level, option, value, length))?;
+			Ok(())
+		}
+	}
+
 	/// Get the value of a socket option.
 	///
 	/// See `man getsockopt` for more information.
-	fn get_option<T: Copy>(&self, level: c_int, option: c_int) -> std::io::Result<T> {
+	pub(crate) fn get_option<T: Copy>(&self, level: c_int, option: c_int) -> std::io::Result<T> {
 		unsafe {
 			let mut output = std::mem::MaybeUninit::zeroed();
 			let output_ptr = output.as_mut_ptr() as *mut c_void;
@@ -189,17 +265,170 @@ impl<Address: AsSocketAddress> Socket<Address> {
 		}
 	}
 
+	/// Get the value of a socket option as an array of values.
+	///
+	/// Returns the number of values written to the output buffer.
+	///
+	/// See `man getsockopt` for more information.
+	pub(crate) fn get_option_slice<T: Copy>(&self, level: c_int, option: c_int, output: &mut [T]) -> std::io::Result<usize> {
+		unsafe {
+			let output_ptr = output.as_mut_ptr() as *mut c_void;
+			let mut length = std::mem::size_of_val(output) as libc::socklen_t;
+			check_ret(libc::getsockopt(self.as_raw_fd(), level, option, output_ptr, &mut length))?;
+			Ok(length as usize / std::mem::size_of::<T>())
+		}
+	}
+
+	/// Set a socket option from raw bytes.
+	///
+	/// This can be used for options that are not modeled by this crate, like string or struct valued options.
+	/// The bytes are passed to the kernel as-is, so they must be in the format expected for the option.
+	///
+	/// See `man setsockopt` for more information.
+	pub fn set_option_bytes(&self, level: c_int, option: c_int, value: &[u8]) -> std::io::Result<()> {
+		self.set_option_slice(level, option, value)
+	}
+
+	/// Get the value of a socket option as raw bytes.
+	///
+	/// Returns the number of bytes written to the output buffer.
+	/// If the buffer is too small, the value is truncated by the kernel.
+	///
+	/// See `man getsockopt` for more information.
+	pub fn get_option_bytes(&self, level: c_int, option: c_int, output: &mut [u8]) -> std::io::Result<usize> {
+		self.get_option_slice(level, option, output)
+	}
+
 	/// Put the socket in blocking or non-blocking mode.
+	///
+	/// This sets or clears the `O_NONBLOCK` file status flag with `fcntl`.
+	/// The flag is shared by all file descriptors that refer to the same socket,
+	/// including duplicates made with [`Self::try_clone()`].
 	pub fn set_nonblocking(&self, non_blocking: bool) -> std::io::Result<()> {
-		self.set_option(libc::SOL_SOCKET, libc::O_NONBLOCK, bool_to_c_int(non_blocking))
+		unsafe {
+			let flags = check_ret(libc::fcntl(self.as_raw_fd(), libc::F_GETFL))?;
+			let new_flags = if non_blocking {
+				flags | libc::O_NONBLOCK
+			} else {
+				flags & !libc::O_NONBLOCK
+			};
+			if new_flags != flags {
+				check_ret(libc::fcntl(self.as_raw_fd(), libc::F_SETFL, new_flags))?;
+			}
+			Ok(())
+		}
 	}
 
 	/// Check if the socket in blocking or non-blocking mode.
+	///
+	/// This checks the `O_NONBLOCK` file status flag with `fcntl`.
 	pub fn get_nonblocking(&self) -> std::io::Result<bool> {
-		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::O_NONBLOCK)?;
+		unsafe {
+			let flags = check_ret(libc::fcntl(self.as_raw_fd(), libc::F_GETFL))?;
+			Ok(flags & libc::O_NONBLOCK != 0)
+		}
+	}
+
+	/// Put the socket in non-blocking mode until the returned guard is dropped.
+	///
+	/// When the guard is dropped, the previous mode of the socket is restored.
+	/// Use [`NonblockingScope::restore()`] to restore the mode explicitly and check for errors.
+	///
+	/// Like [`Self::set_nonblocking()`], this affects all file descriptors that refer to the same socket.
+	pub fn nonblocking_scope(&self) -> std::io::Result<NonblockingScope<'_, Address>> {
+		NonblockingScope::new(self, true)
+	}
+
+	/// Put the socket in blocking mode until the returned guard is dropped.
+	///
+	/// See [`Self::nonblocking_scope()`] for more information.
+	pub fn blocking_scope(&self) -> std::io::Result<NonblockingScope<'_, Address>> {
+		NonblockingScope::new(self, false)
+	}
+
+	/// Set whether to automatically retry blocking operations that are interrupted by a signal.
+	///
+	/// If enabled, sending, receiving and accepting connections are retried when they fail with `EINTR`.
+	/// This also applies to the `poll` used by the deadline functions like [`Self::recv_deadline()`].
+	/// Connecting is never retried, because an interrupted `connect` continues in the background.
+	///
+	/// This setting is disabled by default, and interrupted operations return an error of kind
+	/// [`Interrupted`](std::io::ErrorKind::Interrupted).
+	/// The setting is stored in the [`Socket`] object, not in the kernel.
+	/// It is copied by [`Self::try_clone()`] and inherited by sockets returned from [`Self::accept()`].
+	pub fn set_retry_interrupted(&self, retry: bool) {
+		self.retry_interrupted.store(retry, Ordering::Relaxed);
+	}
+
+	/// Check if blocking operations that are interrupted by a signal are retried automatically.
+	///
+	/// See [`Self::set_retry_interrupted()`] for more information.
+	pub fn retry_interrupted(&self) -> bool {
+		self.retry_interrupted.load(Ordering::Relaxed)
+	}
+
+	/// Get the domain (address family) of the socket, like `AF_INET` or `AF_UNIX`.
+	///
+	/// This uses the `SO_DOMAIN` option.
+	/// This can be used to check what kind of socket was received from another process or inherited from a service manager.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn domain(&self) -> std::io::Result<c_int> {
+		self.get_option(libc::SOL_SOCKET, libc::SO_DOMAIN)
+	}
+
+	/// Get the domain (address family) of the socket, like `AF_INET` or `AF_UNIX`.
+	///
+	/// The platform does not support the `SO_DOMAIN` option,
+	/// so this uses the address family of the local address of the socket instead.
+	#[cfg(not(any(target_os = "linux", target_os = "android")))]
+	pub fn domain(&self) -> std::io::Result<c_int> {
+		unsafe {
+			let mut address: libc::sockaddr_storage = std::mem::zeroed();
+			let mut len = std::mem::size_of_val(&address) as libc::socklen_t;
+			check_ret(libc::getsockname(self.as_raw_fd(), &mut address as *mut _ as *mut libc::sockaddr, &mut len))?;
+			Ok(address.ss_family as c_int)
+		}
+	}
+
+	/// Get the type of the socket, like `SOCK_STREAM` or `SOCK_DGRAM` with the `SO_TYPE` option.
+	///
+	/// Use [`SocketType::from_raw()`](crate::SocketType::from_raw) to convert the value to a [`SocketType`](crate::SocketType).
+	pub fn kind(&self) -> std::io::Result<c_int> {
+		self.get_option(libc::SOL_SOCKET, libc::SO_TYPE)
+	}
+
+	/// Get the protocol of the socket, like `IPPROTO_TCP` with the `SO_PROTOCOL` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn protocol(&self) -> std::io::Result<c_int> {
+		self.get_option(libc::SOL_SOCKET, libc::SO_PROTOCOL)
+	}
+
+	/// Check if the socket is listening for connections with the `SO_ACCEPTCONN` option.
+	///
+	/// This can be used to tell apart listening and connected sockets inherited from a service manager.
+	pub fn is_listening(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_ACCEPTCONN)?;
 		Ok(raw != 0)
 	}
 
+	/// Check that the socket has the expected domain and type, and optionally that it is listening for connections.
+	///
+	/// This is used to validate conversions to and from socket types of other crates.
+	pub(crate) fn check_domain_and_type(&self, domain: c_int, kind: c_int, listening: bool) -> std::io::Result<()> {
+		if self.domain()? != domain {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket has the wrong domain"));
+		}
+		if self.kind()? != kind {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket has the wrong type"));
+		}
+		if listening && !self.is_listening()? {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket is not listening for connections"));
+		}
+		Ok(())
+	}
+
 	/// Gets the value of the SO_ERROR option on this socket.
 	///
 	/// This will retrieve the stored error in the underlying socket, clearing the field in the process.
@@ -213,23 +442,374 @@ impl<Address: AsSocketAddress> Socket<Address> {
 		}
 	}
 
+	/// Enable or disable sending to broadcast addresses with the `SO_BROADCAST` option.
+	///
+	/// This is only meaningful for datagram sockets.
+	pub fn set_broadcast(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_BROADCAST, bool_to_c_int(enable))
+	}
+
+	/// Check if sending to broadcast addresses is enabled with the `SO_BROADCAST` option.
+	pub fn broadcast(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_BROADCAST)?;
+		Ok(raw != 0)
+	}
+
+	/// Receive out-of-band data in the normal data stream with the `SO_OOBINLINE` option.
+	///
+	/// When enabled, [`recv_oob()`](Socket::recv_oob) can not be used,
+	/// and the urgent byte is received with the normal data instead.
+	pub fn set_oob_inline(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_OOBINLINE, bool_to_c_int(enable))
+	}
+
+	/// Check if out-of-band data is received in the normal data stream with the `SO_OOBINLINE` option.
+	pub fn oob_inline(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_OOBINLINE)?;
+		Ok(raw != 0)
+	}
+
+	/// Set the linger behaviour of the socket on close with the `SO_LINGER` option.
+	///
+	/// With `None`, closing the socket returns immediately and the kernel tries to send any remaining data in the background.
+	/// With `Some(timeout)`, closing the socket blocks until the remaining data is sent or the timeout expires.
+	/// With a timeout of zero, the connection is aborted with a reset on close and unsent data is discarded.
+	///
+	/// The timeout has a resolution of seconds, partial seconds are rounded up.
+	pub fn set_linger(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+		let value = match timeout {
+			None => libc::linger { l_onoff: 0, l_linger: 0 },
+			Some(timeout) => {
+				let mut seconds = timeout.as_secs();
+				if timeout.subsec_nanos() != 0 {
+					seconds += 1;
+				}
+				let seconds = seconds.min(c_int::MAX as u64) as c_int;
+				libc::linger { l_onoff: 1, l_linger: seconds }
+			},
+		};
+		self.set_option(libc::SOL_SOCKET, libc::SO_LINGER, value)
+	}
+
+	/// Get the linger behaviour of the socket on close with the `SO_LINGER` option.
+	///
+	/// See [`set_linger()`](Socket::set_linger) for the meaning of the value.
+	pub fn linger(&self) -> std::io::Result<Option<std::time::Duration>> {
+		let value: libc::linger = self.get_option(libc::SOL_SOCKET, libc::SO_LINGER)?;
+		if value.l_onoff == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(std::time::Duration::from_secs(value.l_linger as u64)))
+		}
+	}
+
+	/// Enable or disable reuse of local addresses with the `SO_REUSEADDR` option.
+	///
+	/// For TCP sockets, this allows binding to an address that still has connections in the `TIME_WAIT` state.
+	/// This must be set before calling [`bind()`](Socket::bind).
+	pub fn set_reuse_address(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_REUSEADDR, bool_to_c_int(enable))
+	}
+
+	/// Check if reuse of local addresses is enabled with the `SO_REUSEADDR` option.
+	pub fn reuse_address(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_REUSEADDR)?;
+		Ok(raw != 0)
+	}
+
+	/// Enable or disable binding multiple sockets to the same address with the `SO_REUSEPORT` option.
+	///
+	/// This must be set before calling [`bind()`](Socket::bind).
+	///
+	/// The semantics differ per platform.
+	/// On Linux and Android, incoming connections and datagrams are distributed over all sockets bound to the address.
+	/// On the BSDs and macOS, only the most recently bound socket receives unicast datagrams.
+	///
+	/// This function is not available on Solaris and illumos.
+	#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+	pub fn set_reuse_port(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_REUSEPORT, bool_to_c_int(enable))
+	}
+
+	/// Check if binding multiple sockets to the same address is enabled with the `SO_REUSEPORT` option.
+	///
+	/// This function is not available on Solaris and illumos.
+	#[cfg(not(any(target_os = "solaris", target_os = "illumos")))]
+	pub fn reuse_port(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_REUSEPORT)?;
+		Ok(raw != 0)
+	}
+
+	/// Enable or disable load balancing over sockets bound to the same address with the `SO_REUSEPORT_LB` option.
+	///
+	/// This is similar to `SO_REUSEPORT` on Linux: incoming connections and datagrams are distributed over all sockets bound to the address.
+	/// This must be set before calling [`bind()`](Socket::bind).
+	///
+	/// This function is only available on FreeBSD.
+	#[cfg(target_os = "freebsd")]
+	pub fn set_reuse_port_lb(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_REUSEPORT_LB, bool_to_c_int(enable))
+	}
+
+	/// Check if load balancing over sockets bound to the same address is enabled with the `SO_REUSEPORT_LB` option.
+	///
+	/// This function is only available on FreeBSD.
+	#[cfg(target_os = "freebsd")]
+	pub fn reuse_port_lb(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_REUSEPORT_LB)?;
+		Ok(raw != 0)
+	}
+
+	/// Restrict an IPv6 socket to IPv6 traffic only with the `IPV6_V6ONLY` option.
+	///
+	/// If disabled, the socket can also be used for IPv4 traffic.
+	/// IPv4 addresses are then represented as IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`).
+	/// The default value depends on the platform and system configuration.
+	///
+	/// This must be set before calling [`bind()`](Socket::bind).
+	pub fn set_ipv6_only(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, bool_to_c_int(enable))
+	}
+
+	/// Check if an IPv6 socket is restricted to IPv6 traffic only with the `IPV6_V6ONLY` option.
+	pub fn ipv6_only(&self) -> std::io::Result<bool> {
+		let raw: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_V6ONLY)?;
+		Ok(raw != 0)
+	}
+
+	/// Set the time-to-live of sent IPv4 packets with the `IP_TTL` option.
+	pub fn set_ttl_v4(&self, ttl: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_TTL, ttl as c_int)
+	}
+
+	/// Get the time-to-live of sent IPv4 packets with the `IP_TTL` option.
+	pub fn ttl_v4(&self) -> std::io::Result<u32> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_TTL)?;
+		Ok(value as u32)
+	}
+
+	/// Set the type-of-service field of sent IPv4 packets with the `IP_TOS` option.
+	///
+	/// The upper six bits are the DSCP value, the lower two bits are the ECN field.
+	pub fn set_tos_v4(&self, tos: u8) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IP, libc::IP_TOS, c_int::from(tos))
+	}
+
+	/// Get the type-of-service field of sent IPv4 packets with the `IP_TOS` option.
+	pub fn tos_v4(&self) -> std::io::Result<u8> {
+		let value: c_int = self.get_option(libc::IPPROTO_IP, libc::IP_TOS)?;
+		Ok(value as u8)
+	}
+
+	/// Set the hop limit of sent IPv6 unicast packets with the `IPV6_UNICAST_HOPS` option.
+	pub fn set_unicast_hops_v6(&self, hops: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS, hops as c_int)
+	}
+
+	/// Get the hop limit of sent IPv6 unicast packets with the `IPV6_UNICAST_HOPS` option.
+	pub fn unicast_hops_v6(&self) -> std::io::Result<u32> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_UNICAST_HOPS)?;
+		Ok(value as u32)
+	}
+
+	/// Set the traffic class of sent IPv6 packets with the `IPV6_TCLASS` option.
+	///
+	/// The upper six bits are the DSCP value, the lower two bits are the ECN field.
+	pub fn set_traffic_class_v6(&self, traffic_class: u8) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_IPV6, libc::IPV6_TCLASS, c_int::from(traffic_class))
+	}
+
+	/// Get the traffic class of sent IPv6 packets with the `IPV6_TCLASS` option.
+	pub fn traffic_class_v6(&self) -> std::io::Result<u8> {
+		let value: c_int = self.get_option(libc::IPPROTO_IPV6, libc::IPV6_TCLASS)?;
+		Ok(value as u8)
+	}
+
+	/// Set the size of the send buffer with the `SO_SNDBUF` option.
+	///
+	/// On Linux, the kernel doubles the value to allow space for bookkeeping overhead,
+	/// and the doubled value is reported by [`send_buffer_size()`](Socket::send_buffer_size).
+	/// The value is limited by the `net.core.wmem_max` sysctl.
+	pub fn set_send_buffer_size(&self, size: usize) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_SNDBUF, size as c_int)
+	}
+
+	/// Get the size of the send buffer with the `SO_SNDBUF` option.
+	pub fn send_buffer_size(&self) -> std::io::Result<usize> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_SNDBUF)?;
+		Ok(raw as usize)
+	}
+
+	/// Set the size of the receive buffer with the `SO_RCVBUF` option.
+	///
+	/// On Linux, the kernel doubles the value to allow space for bookkeeping overhead,
+	/// and the doubled value is reported by [`recv_buffer_size()`](Socket::recv_buffer_size).
+	/// The value is limited by the `net.core.rmem_max` sysctl.
+	pub fn set_recv_buffer_size(&self, size: usize) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_RCVBUF, size as c_int)
+	}
+
+	/// Get the size of the receive buffer with the `SO_RCVBUF` option.
+	pub fn recv_buffer_size(&self) -> std::io::Result<usize> {
+		let raw: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_RCVBUF)?;
+		Ok(raw as usize)
+	}
+
+	/// Set the size of the send buffer with the `SO_SNDBUFFORCE` option.
+	///
+	/// This is the same as [`set_send_buffer_size()`](Socket::set_send_buffer_size),
+	/// but it ignores the `net.core.wmem_max` limit.
+	/// This requires the `CAP_NET_ADMIN` capability.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn set_send_buffer_size_force(&self, size: usize) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_SNDBUFFORCE, size as c_int)
+	}
+
+	/// Set the size of the receive buffer with the `SO_RCVBUFFORCE` option.
+	///
+	/// This is the same as [`set_recv_buffer_size()`](Socket::set_recv_buffer_size),
+	/// but it ignores the `net.core.rmem_max` limit.
+	/// This requires the `CAP_NET_ADMIN` capability.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn set_recv_buffer_size_force(&self, size: usize) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_RCVBUFFORCE, size as c_int)
+	}
+
+	/// Bind the socket to a network interface with the `SO_BINDTODEVICE` option.
+	///
+	/// Only packets received on the interface are processed by the socket,
+	/// and sent packets are always sent over the interface.
+	/// Pass `None` to remove the binding.
+	///
+	/// Returns an error if the interface name is too long or contains a null byte.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 socket` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn bind_device(&self, interface: Option<&str>) -> std::io::Result<()> {
+		let interface = interface.unwrap_or("").as_bytes();
+		if interface.len() >= libc::IFNAMSIZ {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name is too long"));
+		}
+		if interface.contains(&0) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "interface name contains a null byte"));
+		}
+		self.set_option_slice(libc::SOL_SOCKET, libc::SO_BINDTODEVICE, interface)
+	}
+
+	/// Get the name of the network interface the socket is bound to with the `SO_BINDTODEVICE` option.
+	///
+	/// Returns `None` if the socket is not bound to an interface.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn bound_device(&self) -> std::io::Result<Option<std::ffi::OsString>> {
+		use std::os::unix::ffi::OsStringExt;
+		let mut buffer = [0u8; libc::IFNAMSIZ];
+		let len = self.get_option_slice(libc::SOL_SOCKET, libc::SO_BINDTODEVICE, &mut buffer)?;
+		let len = buffer[..len].iter().position(|&c| c == 0).unwrap_or(len);
+		if len == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(std::ffi::OsString::from_vec(buffer[..len].to_vec())))
+		}
+	}
+
+	/// Attach an eBPF program to the socket as a socket filter.
+	///
+	/// The program is identified by a file descriptor as returned by `bpf(BPF_PROG_LOAD, ...)`,
+	/// and it must be of the type `BPF_PROG_TYPE_SOCKET_FILTER`.
+	/// The kernel keeps its own reference to the program,
+	/// so the file descriptor can be closed after the program has been attached.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 socket` and `man bpf` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn attach_bpf_filter(&self, program: RawFd) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_ATTACH_BPF, program as c_int)
+	}
+
+	/// Detach the socket filter from the socket.
+	///
+	/// This removes a filter attached with [`attach_bpf_filter()`](Socket::attach_bpf_filter).
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 socket` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn detach_bpf_filter(&self) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_DETACH_BPF, 0 as c_int)
+	}
+
+	/// Attach an eBPF program to the `SO_REUSEPORT` group of the socket.
+	///
+	/// The program is used to select which socket of the reuseport group receives a packet or a new connection.
+	/// It is identified by a file descriptor as returned by `bpf(BPF_PROG_LOAD, ...)`,
+	/// and it must be of the type `BPF_PROG_TYPE_SOCKET_FILTER` or `BPF_PROG_TYPE_SK_REUSEPORT`.
+	/// The kernel keeps its own reference to the program,
+	/// so the file descriptor can be closed after the program has been attached.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 socket` and `man bpf` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn attach_reuseport_bpf(&self, program: RawFd) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_ATTACH_REUSEPORT_EBPF, program as c_int)
+	}
+
+	/// Detach the reuseport program from the `SO_REUSEPORT` group of the socket.
+	///
+	/// This removes a program attached with [`attach_reuseport_bpf()`](Socket::attach_reuseport_bpf).
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 socket` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn detach_reuseport_bpf(&self) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_DETACH_REUSEPORT_BPF, 0 as c_int)
+	}
+
 	/// Get the local address the socket is bound to.
 	pub fn local_addr(&self) -> std::io::Result<Address> {
+		self.local_addr_as()
+	}
+
+	/// Get the local address the socket is bound to as a generic [`SocketAddress`](crate::SocketAddress).
+	///
+	/// This is the same as [`local_addr()`](Socket::local_addr),
+	/// but the address is not checked against the address type of the socket.
+	/// This is useful to log the address of a socket even if it has an unexpected address family.
+	pub fn local_addr_generic(&self) -> std::io::Result<crate::SocketAddress> {
+		self.local_addr_as()
+	}
+
+	/// Get the local address of the socket as the given address type.
+	fn local_addr_as<LocalAddress: AsSocketAddress>(&self) -> std::io::Result<LocalAddress> {
 		unsafe {
-			let mut address = std::mem::MaybeUninit::<Address>::zeroed();
-			let mut len = Address::max_len();
-			check_ret(libc::getsockname(self.as_raw_fd(), Address::as_sockaddr_mut(&mut address), &mut len))?;
-			Address::finalize(address, len)
+			let mut address = std::mem::MaybeUninit::<LocalAddress>::zeroed();
+			let mut len = LocalAddress::max_len();
+			check_ret(libc::getsockname(self.as_raw_fd(), LocalAddress::as_sockaddr_mut(&mut address), &mut len))?;
+			LocalAddress::finalize(address, len).context(Operation::FinalizeAddress)
 		}
 	}
 
 	/// Get the remote address the socket is connected to.
 	pub fn peer_addr(&self) -> std::io::Result<Address> {
+		self.peer_addr_as()
+	}
+
+	/// Get the remote address the socket is connected to as a generic [`SocketAddress`](crate::SocketAddress).
+	///
+	/// This is the same as [`peer_addr()`](Socket::peer_addr),
+	/// but the address is not checked against the address type of the socket.
+	pub fn peer_addr_generic(&self) -> std::io::Result<crate::SocketAddress> {
+		self.peer_addr_as()
+	}
+
+	/// Get the remote address of the socket as the given address type.
+	fn peer_addr_as<PeerAddress: AsSocketAddress>(&self) -> std::io::Result<PeerAddress> {
 		unsafe {
-			let mut address = std::mem::MaybeUninit::<Address>::zeroed();
-			let mut len = Address::max_len();
-			check_ret(libc::getpeername(self.as_raw_fd(), Address::as_sockaddr_mut(&mut address), &mut len))?;
-			Address::finalize(address, len)
+			let mut address = std::mem::MaybeUninit::<PeerAddress>::zeroed();
+			let mut len = PeerAddress::max_len();
+			check_ret(libc::getpeername(self.as_raw_fd(), PeerAddress::as_sockaddr_mut(&mut address), &mut len))?;
+			PeerAddress::finalize(address, len).context(Operation::FinalizeAddress)
 		}
 	}
 
@@ -277,12 +857,38 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// Not all socket types can be put into listening mode or accept connections.
 	/// See `man listen` for more information.
 	pub fn accept(&self) -> std::io::Result<(Self, Address)> {
+		self.accept_as()
+	}
+
+	/// Accept a new connection on the socket, and get the address of the peer as a generic [`SocketAddress`](crate::SocketAddress).
+	///
+	/// This works like [`accept()`](Socket::accept),
+	/// but the address of the peer is not checked against the address type of the socket.
+	/// This is useful for sockets with a generic address type, and for listeners that do not care about the address family of the peer.
+	pub fn accept_generic(&self) -> std::io::Result<(Self, crate::SocketAddress)> {
+		self.accept_as()
+	}
+
+	/// Accept a new connection on the socket, and get the address of the peer as the given address type.
+	fn accept_as<PeerAddress: AsSocketAddress>(&self) -> std::io::Result<(Self, PeerAddress)> {
 		unsafe {
 			let mut address = std::mem::MaybeUninit::zeroed();
-			let mut len = Address::max_len();
-			let fd = check_ret(libc::accept4(self.as_raw_fd(), Address::as_sockaddr_mut(&mut address), &mut len, libc::SOCK_CLOEXEC))?;
-			let socket = Self::wrap(FileDesc::from_raw_fd(fd))?;
-			let address = Address::finalize(address, len)?;
+			let mut len = PeerAddress::max_len();
+
+			#[cfg(not(target_vendor = "apple"))]
+			let fd = FileDesc::from_raw_fd(self.retry(|| check_ret(libc::accept4(self.as_raw_fd(), PeerAddress::as_sockaddr_mut(&mut address), &mut len, libc::SOCK_CLOEXEC)))?);
+
+			// Apple platforms do not have accept4, so set close-on-exec after accepting the connection.
+			#[cfg(target_vendor = "apple")]
+			let fd = {
+				let fd = FileDesc::from_raw_fd(self.retry(|| check_ret(libc::accept(self.as_raw_fd(), PeerAddress::as_sockaddr_mut(&mut address), &mut len)))?);
+				fd.set_close_on_exec(true).context(Operation::SetCloseOnExec)?;
+				fd
+			};
+
+			let socket = Self::wrap(fd)?;
+			socket.set_retry_interrupted(self.retry_interrupted());
+			let address = PeerAddress::finalize(address, len).context(Operation::FinalizeAddress)?;
 			Ok((socket, address))
 		}
 	}
@@ -295,7 +901,7 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	pub fn send(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
 		unsafe {
 			let data_ptr = data.as_ptr() as *const c_void;
-			let transferred = check_ret_isize(libc::send(self.as_raw_fd(), data_ptr, data.len(), flags | extra_flags::SENDMSG))?;
+			let transferred = self.retry(|| check_ret_isize(libc::send(self.as_raw_fd(), data_ptr, data.len(), flags | extra_flags::SENDMSG)))?;
 			Ok(transferred as usize)
 		}
 	}
@@ -308,15 +914,25 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	///
 	/// See `man sendto` for more information.
 	pub fn send_to(&self, data: &[u8], address: &Address, flags: c_int) -> std::io::Result<usize> {
+		self.send_to_generic(data, address, flags)
+	}
+
+	/// Send data over the socket to an address of any type.
+	///
+	/// This is the same as [`send_to()`](Socket::send_to),
+	/// but the address does not need to match the address type of the socket.
+	/// This is useful for sockets where the address of the peer has a different family than the nominal address type,
+	/// or for sockets with a generic address type.
+	pub fn send_to_generic<PeerAddress: AsSocketAddress>(&self, data: &[u8], address: &PeerAddress, flags: c_int) -> std::io::Result<usize> {
 		unsafe {
 			let data_ptr = data.as_ptr() as *const c_void;
-			let transferred = check_ret_isize(libc::sendto(
+			let transferred = self.retry(|| check_ret_isize(libc::sendto(
 				self.as_raw_fd(),
 				data_ptr,
 				data.len(),
 				flags | extra_flags::SENDMSG,
 				address.as_sockaddr(), address.len()
-			))?;
+			)))?;
 			Ok(transferred as usize)
 		}
 	}
@@ -330,11 +946,11 @@ impl<Address: AsSocketAddress> Socket<Address> {
 		unsafe {
 			let mut header = std::mem::zeroed::<libc::msghdr>();
 			header.msg_iov = data.as_ptr() as *mut libc::iovec;
-			header.msg_iovlen = data.len();
+			header.msg_iovlen = data.len() as _;
 			header.msg_control = cdata.map(|x| x.as_ptr()).unwrap_or(std::ptr::null()) as *mut c_void;
-			header.msg_controllen = cdata.map(|x| x.len()).unwrap_or(0);
+			header.msg_controllen = cdata.map(|x| x.len()).unwrap_or(0) as _;
 
-			let ret = check_ret_isize(libc::sendmsg(self.as_raw_fd(), &header, flags | extra_flags::SENDMSG))?;
+			let ret = self.retry(|| check_ret_isize(libc::sendmsg(self.as_raw_fd(), &header, flags | extra_flags::SENDMSG)))?;
 			Ok(ret as usize)
 		}
 	}
@@ -352,11 +968,11 @@ impl<Address: AsSocketAddress> Socket<Address> {
 			header.msg_name = address.as_sockaddr() as *mut c_void;
 			header.msg_namelen = address.len();
 			header.msg_iov = data.as_ptr() as *mut libc::iovec;
-			header.msg_iovlen = data.len();
+			header.msg_iovlen = data.len() as _;
 			header.msg_control = cdata.map(|x| x.as_ptr()).unwrap_or(std::ptr::null()) as *mut c_void;
-			header.msg_controllen = cdata.map(|x| x.len()).unwrap_or(0);
+			header.msg_controllen = cdata.map(|x| x.len()).unwrap_or(0) as _;
 
-			let ret = check_ret_isize(libc::sendmsg(self.as_raw_fd(), &header, flags | extra_flags::SENDMSG))?;
+			let ret = self.retry(|| check_ret_isize(libc::sendmsg(self.as_raw_fd(), &header, flags | extra_flags::SENDMSG)))?;
 			Ok(ret as usize)
 		}
 	}
@@ -367,9 +983,36 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	///
 	/// See `man recv` for more information.
 	pub fn recv(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<usize> {
+		self.recv_uninit(as_uninit_slice(buffer), flags)
+	}
+
+	/// Receive data from the connected peer until the buffer is full with the `MSG_WAITALL` flag.
+	///
+	/// The call blocks until the whole buffer is filled, but it can still return less data if:
+	///   * the connection is closed by the peer,
+	///   * a receive timeout set with `SO_RCVTIMEO` expires,
+	///   * the call is interrupted by a signal,
+	///   * an error occurs on the socket,
+	///   * the next message has a different type, for example for out-of-band data.
+	///
+	/// In non-blocking mode, the `MSG_WAITALL` flag has no effect and the call returns immediately.
+	/// For datagram sockets, the flag has no effect either: only a single datagram is received.
+	///
+	/// Returns the number of received bytes.
+	pub fn recv_full(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<usize> {
+		self.recv(buffer, flags | libc::MSG_WAITALL)
+	}
+
+	/// Receive a data on the socket from the connected peer into a possibly uninitialized buffer.
+	///
+	/// This avoids the need to zero-initialize large buffers before receiving.
+	/// If the call succeeds, the first `n` bytes of the buffer are initialized, where `n` is the returned number of bytes.
+	///
+	/// See [`recv()`](Socket::recv) for more information.
+	pub fn recv_uninit(&self, buffer: &mut [MaybeUninit<u8>], flags: c_int) -> std::io::Result<usize> {
 		unsafe {
 			let buffer_ptr = buffer.as_mut_ptr() as *mut c_void;
-			let transferred = check_ret_isize(libc::recv(self.as_raw_fd(), buffer_ptr, buffer.len(), flags | extra_flags::RECVMSG))?;
+			let transferred = self.retry(|| check_ret_isize(libc::recv(self.as_raw_fd(), buffer_ptr, buffer.len(), flags | extra_flags::RECVMSG)))?;
 			Ok(transferred as usize)
 		}
 	}
@@ -377,36 +1020,295 @@ impl<Address: AsSocketAddress> Socket<Address> {
 	/// Receive a data on the socket.
 	///
 	/// Returns the address of the sender and the number of transferred bytes, or an error.
+	/// The address is [`None`] if the kernel did not report an address,
+	/// which is the case for connected stream sockets and some other socket types.
 	///
 	/// See `man recvfrom` for more information.
-	pub fn recv_from(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<(Address, usize)> {
+	pub fn recv_from(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<(Option<Address>, usize)> {
+		self.recv_from_uninit(as_uninit_slice(buffer), flags)
+	}
+
+	/// Receive a data on the socket into a possibly uninitialized buffer.
+	///
+	/// If the call succeeds, the first `n` bytes of the buffer are initialized, where `n` is the returned number of bytes.
+	///
+	/// See [`recv_from()`](Socket::recv_from) for more information.
+	pub fn recv_from_uninit(&self, buffer: &mut [MaybeUninit<u8>], flags: c_int) -> std::io::Result<(Option<Address>, usize)> {
+		self.recv_from_as(buffer, flags)
+	}
+
+	/// Receive data on the socket, and get the address of the sender as a generic [`SocketAddress`](crate::SocketAddress).
+	///
+	/// This is the same as [`recv_from()`](Socket::recv_from),
+	/// but the address of the sender is not checked against the address type of the socket.
+	pub fn recv_from_generic(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<(Option<crate::SocketAddress>, usize)> {
+		self.recv_from_as(as_uninit_slice(buffer), flags)
+	}
+
+	/// Receive data on the socket, and get the address of the sender as the given address type.
+	fn recv_from_as<PeerAddress: AsSocketAddress>(&self, buffer: &mut [MaybeUninit<u8>], flags: c_int) -> std::io::Result<(Option<PeerAddress>, usize)> {
 		unsafe {
 			let buffer_ptr = buffer.as_mut_ptr() as *mut c_void;
 			let mut address = std::mem::MaybeUninit::zeroed();
-			let mut address_len = Address::max_len();
-			let transferred = check_ret_isize(libc::recvfrom(
+			let mut address_len = PeerAddress::max_len();
+			let transferred = self.retry(|| check_ret_isize(libc::recvfrom(
 				self.as_raw_fd(),
 				buffer_ptr,
 				buffer.len(),
 				flags,
-				Address::as_sockaddr_mut(&mut address),
+				PeerAddress::as_sockaddr_mut(&mut address),
 				&mut address_len
-			))?;
+			)))?;
 
-			let address = Address::finalize(address, address_len)?;
+			let address = if address_len == 0 {
+				None
+			} else {
+				Some(PeerAddress::finalize(address, address_len).context(Operation::FinalizeAddress)?)
+			};
 			Ok((address, transferred as usize))
 		}
 	}
 
+	/// Send data on the socket to the connected peer without blocking, using the `MSG_DONTWAIT` flag.
+	///
+	/// Returns `Ok(None)` if the operation would block.
+	/// This does not change the blocking mode of the socket.
+	pub fn try_send(&self, data: &[u8], flags: c_int) -> std::io::Result<Option<usize>> {
+		would_block_to_none(self.send(data, flags | libc::MSG_DONTWAIT))
+	}
+
+	/// Send data on the socket to a specific address without blocking, using the `MSG_DONTWAIT` flag.
+	///
+	/// Returns `Ok(None)` if the operation would block.
+	/// This does not change the blocking mode of the socket.
+	pub fn try_send_to(&self, data: &[u8], address: &Address, flags: c_int) -> std::io::Result<Option<usize>> {
+		would_block_to_none(self.send_to(data, address, flags | libc::MSG_DONTWAIT))
+	}
+
+	/// Receive data on the socket from the connected peer without blocking, using the `MSG_DONTWAIT` flag.
+	///
+	/// Returns `Ok(None)` if no data is available.
+	/// This does not change the blocking mode of the socket.
+	pub fn try_recv(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<Option<usize>> {
+		would_block_to_none(self.recv(buffer, flags | libc::MSG_DONTWAIT))
+	}
+
+	/// Receive data and the address of the sender without blocking, using the `MSG_DONTWAIT` flag.
+	///
+	/// Returns `Ok(None)` if no data is available.
+	/// This does not change the blocking mode of the socket.
+	pub fn try_recv_from(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<Option<(Option<Address>, usize)>> {
+		would_block_to_none(self.recv_from(buffer, flags | libc::MSG_DONTWAIT))
+	}
+
+	/// Send data on the socket to the connected peer, waiting at most until the deadline.
+	///
+	/// The deadline is enforced by waiting for the socket with `poll`, independent of the `SO_SNDTIMEO` option.
+	/// Returns an error of kind [`TimedOut`](std::io::ErrorKind::TimedOut) if the deadline passes before any data could be sent.
+	pub fn send_deadline(&self, data: &[u8], flags: c_int, deadline: Instant) -> std::io::Result<usize> {
+		self.with_deadline(PollFlags::WRITABLE, deadline, || self.try_send(data, flags))
+	}
+
+	/// Send data on the socket to a specific address, waiting at most until the deadline.
+	///
+	/// See [`Self::send_deadline()`] for more information.
+	pub fn send_to_deadline(&self, data: &[u8], address: &Address, flags: c_int, deadline: Instant) -> std::io::Result<usize> {
+		self.with_deadline(PollFlags::WRITABLE, deadline, || self.try_send_to(data, address, flags))
+	}
+
+	/// Receive data on the socket from the connected peer, waiting at most until the deadline.
+	///
+	/// The deadline is enforced by waiting for the socket with `poll`, independent of the `SO_RCVTIMEO` option.
+	/// Returns an error of kind [`TimedOut`](std::io::ErrorKind::TimedOut) if the deadline passes before data is available.
+	pub fn recv_deadline(&self, buffer: &mut [u8], flags: c_int, deadline: Instant) -> std::io::Result<usize> {
+		self.with_deadline(PollFlags::READABLE, deadline, || self.try_recv(buffer, flags))
+	}
+
+	/// Receive data and the address of the sender, waiting at most until the deadline.
+	///
+	/// See [`Self::recv_deadline()`] for more information.
+	pub fn recv_from_deadline(&self, buffer: &mut [u8], flags: c_int, deadline: Instant) -> std::io::Result<(Option<Address>, usize)> {
+		self.with_deadline(PollFlags::READABLE, deadline, || self.try_recv_from(buffer, flags))
+	}
+
+	/// Accept a new connection, waiting at most until the deadline.
+	///
+	/// Returns an error of kind [`TimedOut`](std::io::ErrorKind::TimedOut) if the deadline passes before a connection is available.
+	///
+	/// If the socket is in blocking mode and another thread accepts the pending connection first,
+	/// this function may block until the next connection arrives.
+	/// Put the socket in non-blocking mode to prevent this.
+	pub fn accept_deadline(&self, deadline: Instant) -> std::io::Result<(Self, Address)> {
+		self.with_deadline(PollFlags::READABLE, deadline, || would_block_to_none(self.accept()))
+	}
+
+	/// Run an operation, and retry it if it is interrupted by a signal and [`Self::retry_interrupted()`] is enabled.
+	pub(crate) fn retry<T>(&self, mut operation: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+		loop {
+			match operation() {
+				Err(e) if e.kind() == std::io::ErrorKind::Interrupted && self.retry_interrupted() => continue,
+				result => return result,
+			}
+		}
+	}
+
+	/// Wait for `events` with `poll` and run a non-blocking operation until it completes or the deadline passes.
+	fn with_deadline<T>(
+		&self,
+		events: PollFlags,
+		deadline: Instant,
+		mut operation: impl FnMut() -> std::io::Result<Option<T>>,
+	) -> std::io::Result<T> {
+		loop {
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			let ready = match poll_many(&mut [PollEntry::new(self, events)], Some(remaining)) {
+				Err(e) if e.kind() == std::io::ErrorKind::Interrupted && self.retry_interrupted() => continue,
+				result => result?,
+			};
+			if ready == 0 {
+				return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "deadline expired"));
+			}
+			if let Some(result) = operation()? {
+				return Ok(result);
+			}
+		}
+	}
+
+	/// Get the number of bytes that can be read from the socket without blocking with the `FIONREAD` ioctl.
+	///
+	/// For stream sockets, this is the total number of bytes in the receive queue.
+	/// For datagram sockets on Linux, this is the size of the next datagram.
+	pub fn pending_input(&self) -> std::io::Result<usize> {
+		unsafe {
+			let mut pending: c_int = 0;
+			check_ret(libc::ioctl(self.as_raw_fd(), libc::FIONREAD as _, &mut pending))?;
+			Ok(pending as usize)
+		}
+	}
+
+	/// Set the peek offset of the socket with the `SO_PEEK_OFF` option.
+	///
+	/// When the peek offset is enabled, receiving with the `MSG_PEEK` flag starts at the peek offset,
+	/// and the offset is advanced by the number of bytes peeked.
+	/// Normal reads decrease the offset by the number of bytes removed from the receive queue.
+	/// A negative value disables the peek offset, which is the default.
+	///
+	/// This option is supported for Unix sockets, and for TCP and UDP sockets on recent kernels.
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn set_peek_offset(&self, offset: c_int) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_PEEK_OFF, offset)
+	}
+
+	/// Get the peek offset of the socket with the `SO_PEEK_OFF` option.
+	///
+	/// A negative value means the peek offset is disabled.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn peek_offset(&self) -> std::io::Result<c_int> {
+		self.get_option(libc::SOL_SOCKET, libc::SO_PEEK_OFF)
+	}
+
+	/// Peek at data in the receive queue at an offset, without removing it from the queue.
+	///
+	/// This sets the peek offset with [`set_peek_offset()`](Socket::set_peek_offset) and receives with the `MSG_PEEK` flag.
+	/// Afterwards, the peek offset points just past the peeked data.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn peek_at(&self, offset: usize, buffer: &mut [u8]) -> std::io::Result<usize> {
+		if offset > c_int::MAX as usize {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "peek offset too large"));
+		}
+		self.set_peek_offset(offset as c_int)?;
+		self.recv(buffer, libc::MSG_PEEK)
+	}
+
+	/// Send out-of-band data on a stream socket with the `MSG_OOB` flag.
+	///
+	/// For TCP sockets, this sets the urgent pointer.
+	/// Only the last byte of the data is marked as urgent.
+	pub fn send_oob(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
+		self.send(data, flags | libc::MSG_OOB)
+	}
+
+	/// Receive out-of-band data with the `MSG_OOB` flag.
+	///
+	/// For TCP sockets, this receives the urgent byte, unless the `SO_OOBINLINE` option is enabled.
+	/// On Linux, `Socket::at_mark()` can be used to find the position of the urgent byte in the normal data stream.
+	pub fn recv_oob(&self, buffer: &mut [u8], flags: c_int) -> std::io::Result<usize> {
+		self.recv(buffer, flags | libc::MSG_OOB)
+	}
+
+	/// Get the size of the next datagram in the receive queue without removing it.
+	///
+	/// This uses `recv` with the `MSG_PEEK` and `MSG_TRUNC` flags,
+	/// which makes the kernel report the full size of the datagram even though no data is copied.
+	/// The call blocks until a datagram is available, unless the socket is in non-blocking mode or `flags` contains `MSG_DONTWAIT`.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn next_datagram_size(&self, flags: c_int) -> std::io::Result<usize> {
+		unsafe {
+			let size = self.retry(|| check_ret_isize(libc::recv(self.as_raw_fd(), std::ptr::null_mut(), 0, flags | libc::MSG_PEEK | libc::MSG_TRUNC)))?;
+			Ok(size as usize)
+		}
+	}
+
+	/// Get the size of the next datagram in the receive queue, so a buffer of exactly the right size can be allocated.
+	///
+	/// This is a shorthand for [`next_datagram_size(0)`](Socket::next_datagram_size).
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn peek_size(&self) -> std::io::Result<usize> {
+		self.next_datagram_size(0)
+	}
+
+	/// Receive a datagram into a newly allocated [`Vec`] that is large enough to hold the whole datagram.
+	///
+	/// The size of the datagram is determined with [`next_datagram_size()`](Socket::next_datagram_size) first,
+	/// so the datagram is never truncated, unless another thread receives from the same socket concurrently.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn recv_to_vec(&self, flags: c_int) -> std::io::Result<Vec<u8>> {
+		let size = self.next_datagram_size(flags)?;
+		let mut buffer = Vec::with_capacity(size);
+		let len = self.recv_uninit(buffer.spare_capacity_mut(), flags)?;
+		unsafe {
+			buffer.set_len(len.min(size));
+		}
+		Ok(buffer)
+	}
+
+	/// Receive a datagram and the address of the sender into a newly allocated [`Vec`].
+	///
+	/// See [`recv_to_vec()`](Socket::recv_to_vec) for more information.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "linux", target_os = "android"))]
+	pub fn recv_from_to_vec(&self, flags: c_int) -> std::io::Result<(Option<Address>, Vec<u8>)> {
+		let size = self.next_datagram_size(flags)?;
+		let mut buffer = Vec::with_capacity(size);
+		let (address, len) = self.recv_from_uninit(buffer.spare_capacity_mut(), flags)?;
+		unsafe {
+			buffer.set_len(len.min(size));
+		}
+		Ok((address, buffer))
+	}
+
 	/// Receive a message on the socket from the connected peer.
 	///
-	/// If the call succeeds, the function returns a tuple with:
-	///   * the number of transferred bytes
-	///   * the number of transferred control message bytes
-	///   * the reception flags
+	/// If the call succeeds, the function returns a [`RecvMessage`] with the number of transferred bytes and the reception flags.
+	/// The address of the sender is not requested, so [`RecvMessage::address`] is always [`None`].
+	///
+	/// The control data buffer is cleared before receiving, which closes any unclaimed pidfds in it.
 	///
 	/// See `man recvmsg` for more information.
-	pub fn recv_msg(&self, data: &[IoSliceMut], cdata: &mut SocketAncillary, flags: c_int) -> std::io::Result<(usize, c_int)> {
+	pub fn recv_msg(&self, data: &[IoSliceMut], cdata: &mut SocketAncillary, flags: c_int) -> std::io::Result<RecvMessage<Address>> {
+		cdata.clear();
 		let (cdata_buf, cdata_len) = if cdata.capacity() == 0 {
 			(std::ptr::null_mut(), 0)
 		} else {
@@ -416,28 +1318,62 @@ impl<Address: AsSocketAddress> Socket<Address> {
 		unsafe {
 			let mut header = std::mem::zeroed::<libc::msghdr>();
 			header.msg_iov = data.as_ptr() as *mut libc::iovec;
-			header.msg_iovlen = data.len();
+			header.msg_iovlen = data.len() as _;
 			header.msg_control = cdata_buf as *mut c_void;
-			header.msg_controllen = cdata_len;
+			header.msg_controllen = cdata_len as _;
 
-			let ret = check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut header, flags | extra_flags::RECVMSG))?;
+			let ret = self.retry(|| check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut header, flags | extra_flags::RECVMSG)))?;
 
 			cdata.length = header.msg_controllen as usize;
 			cdata.truncated = header.msg_flags & libc::MSG_CTRUNC != 0;
-			Ok((ret as usize, header.msg_flags))
+			Ok(RecvMessage::new(ret as usize, header.msg_flags, None))
+		}
+	}
+
+	/// Receive a message on the socket from the connected peer into a single, possibly uninitialized buffer.
+	///
+	/// If the call succeeds, the first `n` bytes of the buffer are initialized, where `n` is the returned number of bytes.
+	///
+	/// See [`recv_msg()`](Socket::recv_msg) for more information.
+	pub fn recv_msg_uninit(&self, buffer: &mut [MaybeUninit<u8>], cdata: &mut SocketAncillary, flags: c_int) -> std::io::Result<RecvMessage<Address>> {
+		cdata.clear();
+		let (cdata_buf, cdata_len) = if cdata.capacity() == 0 {
+			(std::ptr::null_mut(), 0)
+		} else {
+			(cdata.buffer.as_mut_ptr(), cdata.capacity())
+		};
+
+		unsafe {
+			let mut iovec = libc::iovec {
+				iov_base: buffer.as_mut_ptr() as *mut c_void,
+				iov_len: buffer.len(),
+			};
+			let mut header = std::mem::zeroed::<libc::msghdr>();
+			header.msg_iov = &mut iovec;
+			header.msg_iovlen = 1;
+			header.msg_control = cdata_buf as *mut c_void;
+			header.msg_controllen = cdata_len as _;
+
+			let ret = self.retry(|| check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut header, flags | extra_flags::RECVMSG)))?;
+
+			cdata.length = header.msg_controllen as usize;
+			cdata.truncated = header.msg_flags & libc::MSG_CTRUNC != 0;
+			Ok(RecvMessage::new(ret as usize, header.msg_flags, None))
 		}
 	}
 
 	/// Receive a message on the socket from any address.
 	///
-	/// If the call succeeds, the function returns a tuple with:
-	///   * the address of the sender
-	///   * the number of transferred bytes
-	///   * the number of transferred control message bytes
-	///   * the reception flags
+	/// If the call succeeds, the function returns a [`RecvMessage`] with the number of transferred bytes,
+	/// the reception flags and the address of the sender.
+	/// The address is [`None`] if the kernel did not report an address,
+	/// which is the case for connected stream sockets and some other socket types.
+	///
+	/// The control data buffer is cleared before receiving, which closes any unclaimed pidfds in it.
 	///
 	/// See `man recvmsg` for more information.
-	pub fn recv_msg_from(&self, data: &[IoSliceMut], cdata: &mut SocketAncillary, flags: c_int) -> std::io::Result<(Address, usize, c_int)> {
+	pub fn recv_msg_from(&self, data: &[IoSliceMut], cdata: &mut SocketAncillary, flags: c_int) -> std::io::Result<RecvMessage<Address>> {
+		cdata.clear();
 		let (cdata_buf, cdata_len) = if cdata.capacity() == 0 {
 			(std::ptr::null_mut(), 0)
 		} else {
@@ -450,19 +1386,356 @@ impl<Address: AsSocketAddress> Socket<Address> {
 			header.msg_name = Address::as_sockaddr_mut(&mut address) as *mut c_void;
 			header.msg_namelen = Address::max_len();
 			header.msg_iov = data.as_ptr() as *mut libc::iovec;
-			header.msg_iovlen = data.len();
+			header.msg_iovlen = data.len() as _;
 			header.msg_control = cdata_buf as *mut c_void;
-			header.msg_controllen = cdata_len;
+			header.msg_controllen = cdata_len as _;
 
-			let ret = check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut header, flags | extra_flags::RECVMSG))?;
-			let address = Address::finalize(address, header.msg_namelen)?;
+			let ret = self.retry(|| check_ret_isize(libc::recvmsg(self.as_raw_fd(), &mut header, flags | extra_flags::RECVMSG)))?;
+			let address = if header.msg_namelen == 0 {
+				None
+			} else {
+				Some(Address::finalize(address, header.msg_namelen).context(Operation::FinalizeAddress)?)
+			};
 			cdata.length = header.msg_controllen as usize;
 			cdata.truncated = header.msg_flags & libc::MSG_CTRUNC != 0;
-			Ok((address, ret as usize, header.msg_flags))
+			Ok(RecvMessage::new(ret as usize, header.msg_flags, address))
 		}
 	}
 }
 
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Send multiple messages over the socket with a single system call.
+	///
+	/// Each message can have its own destination address and control data.
+	/// Messages without an address are sent to the connected peer.
+	///
+	/// Returns the number of transferred bytes for each message that was sent, or an error.
+	/// If not all messages could be sent, the returned list is shorter than the list of messages.
+	///
+	/// See `man sendmmsg` for more information.
+	pub fn send_multiple(&self, messages: &[SendMessage<Address>], flags: c_int) -> std::io::Result<Vec<usize>> {
+		unsafe {
+			let mut headers: Vec<libc::mmsghdr> = messages.iter()
+				.map(|message| {
+					let mut header = std::mem::zeroed::<libc::mmsghdr>();
+					if let Some(address) = message.address {
+						header.msg_hdr.msg_name = address.as_sockaddr() as *mut c_void;
+						header.msg_hdr.msg_namelen = address.len();
+					}
+					header.msg_hdr.msg_iov = message.data.as_ptr() as *mut libc::iovec;
+					header.msg_hdr.msg_iovlen = message.data.len() as _;
+					header.msg_hdr.msg_control = message.cdata.map(|x| x.as_ptr()).unwrap_or(std::ptr::null()) as *mut c_void;
+					header.msg_hdr.msg_controllen = message.cdata.map(|x| x.len()).unwrap_or(0) as _;
+					header
+				})
+				.collect();
+
+			let count = self.retry(|| check_ret(libc::sendmmsg(
+				self.as_raw_fd(),
+				headers.as_mut_ptr(),
+				headers.len().min(libc::c_uint::MAX as usize) as libc::c_uint,
+				(flags | extra_flags::SENDMSG) as _,
+			)))?;
+			Ok(headers[..count as usize].iter().map(|x| x.msg_len as usize).collect())
+		}
+	}
+
+	/// Receive multiple messages on the socket with a single system call.
+	///
+	/// The data, control data, sender address and reception flags of each received message
+	/// are stored in the corresponding entry of `messages`.
+	/// If the sender address of a message can not be converted to `Address`,
+	/// the message is still returned, but [`MmsgBuffer::address()`] returns `None`.
+	///
+	/// Returns the number of received messages, or an error.
+	///
+	/// See `man recvmmsg` for more information.
+	pub fn recv_multiple(&self, messages: &mut [MmsgBuffer<Address>], flags: c_int) -> std::io::Result<usize> {
+		let mut addresses: Vec<std::mem::MaybeUninit<Address>> = messages.iter()
+			.map(|_| std::mem::MaybeUninit::zeroed())
+			.collect();
+
+		unsafe {
+			let mut headers: Vec<libc::mmsghdr> = messages.iter_mut()
+				.zip(addresses.iter_mut())
+				.map(|(message, address)| {
+					message.cdata.clear();
+					let (cdata_buf, cdata_len) = if message.cdata.capacity() == 0 {
+						(std::ptr::null_mut(), 0)
+					} else {
+						(message.cdata.buffer.as_mut_ptr(), message.cdata.capacity())
+					};
+
+					let mut header = std::mem::zeroed::<libc::mmsghdr>();
+					header.msg_hdr.msg_name = Address::as_sockaddr_mut(address) as *mut c_void;
+					header.msg_hdr.msg_namelen = Address::max_len();
+					header.msg_hdr.msg_iov = message.data.as_ptr() as *mut libc::iovec;
+					header.msg_hdr.msg_iovlen = message.data.len() as _;
+					header.msg_hdr.msg_control = cdata_buf as *mut c_void;
+					header.msg_hdr.msg_controllen = cdata_len as _;
+					header
+				})
+				.collect();
+
+			let count = self.retry(|| check_ret(libc::recvmmsg(
+				self.as_raw_fd(),
+				headers.as_mut_ptr(),
+				headers.len().min(libc::c_uint::MAX as usize) as libc::c_uint,
+				(flags | extra_flags::RECVMSG) as _,
+				std::ptr::null_mut(),
+			)))? as usize;
+
+			for ((message, header), address) in messages.iter_mut().zip(&headers).zip(addresses).take(count) {
+				message.len = header.msg_len as usize;
+				message.flags = RecvFlags(header.msg_hdr.msg_flags);
+				message.cdata.length = header.msg_hdr.msg_controllen as _;
+				message.cdata.truncated = header.msg_hdr.msg_flags & libc::MSG_CTRUNC != 0;
+				// The messages are already taken off the socket, so don't discard all of them for one bad address.
+				message.address = if header.msg_hdr.msg_namelen == 0 {
+					None
+				} else {
+					Address::finalize(address, header.msg_hdr.msg_namelen).ok()
+				};
+			}
+			Ok(count)
+		}
+	}
+}
+
+impl Socket<crate::SocketAddress> {
+	/// Convert a socket with a generic address type into a socket with a specific address type.
+	///
+	/// The domain of the socket is checked against the address family of the `Address` type.
+	/// This is useful for sockets received over a unix socket or inherited from a service manager.
+	///
+	/// If the domain does not match or can not be retrieved, the original socket is returned together with the error.
+	pub fn try_into_specific<Address: crate::SpecificSocketAddress>(self) -> Result<Socket<Address>, (Self, std::io::Error)> {
+		match self.domain() {
+			Ok(domain) if domain == Address::static_family() as c_int => (),
+			Ok(_) => return Err((self, std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket has the wrong domain"))),
+			Err(e) => return Err((self, e)),
+		}
+		Ok(Socket {
+			fd: self.fd,
+			retry_interrupted: self.retry_interrupted,
+			_address: std::marker::PhantomData,
+		})
+	}
+}
+
+/// Flags reported by the kernel for a received message, like `MSG_TRUNC` or `MSG_CTRUNC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RecvFlags(c_int);
+
+impl RecvFlags {
+	/// The message was truncated because the buffer was too small (`MSG_TRUNC`).
+	pub const TRUNC: Self = Self(libc::MSG_TRUNC);
+
+	/// The control data was truncated because the control buffer was too small (`MSG_CTRUNC`).
+	pub const CTRUNC: Self = Self(libc::MSG_CTRUNC);
+
+	/// The end of a record was received, for `SOCK_SEQPACKET` sockets (`MSG_EOR`).
+	pub const EOR: Self = Self(libc::MSG_EOR);
+
+	/// Out-of-band data was received (`MSG_OOB`).
+	pub const OOB: Self = Self(libc::MSG_OOB);
+
+	/// The message was received from the error queue (`MSG_ERRQUEUE`).
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub const ERRQUEUE: Self = Self(libc::MSG_ERRQUEUE);
+
+	/// Create reception flags from the raw `MSG_*` value.
+	pub const fn from_raw(raw: c_int) -> Self {
+		Self(raw)
+	}
+
+	/// Get the raw `MSG_*` value.
+	pub const fn as_raw(self) -> c_int {
+		self.0
+	}
+
+	/// Check if all flags in `other` are set.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+/// The result of [`Socket::recv_msg()`] and [`Socket::recv_msg_from()`].
+#[derive(Debug, Clone)]
+pub struct RecvMessage<Address> {
+	/// The number of bytes received.
+	///
+	/// For datagram sockets, this may be less than the size of the datagram if it was truncated.
+	pub byte_count: usize,
+
+	/// The reception flags reported by the kernel.
+	pub flags: RecvFlags,
+
+	/// The address of the sender, if it was requested.
+	pub address: Option<Address>,
+
+	/// The message was truncated because the buffer was too small (`MSG_TRUNC`).
+	pub truncated: bool,
+
+	/// The control data was truncated because the control buffer was too small (`MSG_CTRUNC`).
+	pub ctruncated: bool,
+}
+
+impl<Address> RecvMessage<Address> {
+	/// Create a received message from the result of `recvmsg`.
+	fn new(byte_count: usize, flags: c_int, address: Option<Address>) -> Self {
+		Self {
+			byte_count,
+			flags: RecvFlags(flags),
+			address,
+			truncated: flags & libc::MSG_TRUNC != 0,
+			ctruncated: flags & libc::MSG_CTRUNC != 0,
+		}
+	}
+}
+
+/// A message to send with [`Socket::send_multiple`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub struct SendMessage<'a, Address> {
+	/// The destination address, or `None` to send the message to the connected peer.
+	pub address: Option<&'a Address>,
+
+	/// The data to send.
+	pub data: &'a [IoSlice<'a>],
+
+	/// The control data to send.
+	pub cdata: Option<&'a [u8]>,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl<'a, Address> SendMessage<'a, Address> {
+	/// Create a message for the connected peer without control data.
+	pub fn new(data: &'a [IoSlice<'a>]) -> Self {
+		Self { address: None, data, cdata: None }
+	}
+
+	/// Create a message for the specified address without control data.
+	pub fn new_to(address: &'a Address, data: &'a [IoSlice<'a>]) -> Self {
+		Self { address: Some(address), data, cdata: None }
+	}
+}
+
+/// A buffer for a message received with [`Socket::recv_multiple`].
+#[cfg(any(target_os = "android", target_os = "linux"))]
+pub struct MmsgBuffer<'a, 'b, Address> {
+	/// The buffers to receive the data in.
+	data: &'a [IoSliceMut<'b>],
+
+	/// The buffer to receive the control data in.
+	cdata: SocketAncillary<'a>,
+
+	/// The address of the sender, if the message has been received.
+	address: Option<Address>,
+
+	/// The number of received bytes.
+	len: usize,
+
+	/// The reception flags.
+	flags: RecvFlags,
+}
+
+#[cfg(any(target_os = "android", target_os = "linux"))]
+impl<'a, 'b, Address> MmsgBuffer<'a, 'b, Address> {
+	/// Create a message buffer from data buffers and a control data buffer.
+	pub fn new(data: &'a [IoSliceMut<'b>], cdata: SocketAncillary<'a>) -> Self {
+		Self { data, cdata, address: None, len: 0, flags: RecvFlags::default() }
+	}
+
+	/// Get the address of the sender of the received message.
+	///
+	/// Returns `None` if no message has been received yet, if the sender address is not known
+	/// or if the address reported by the kernel is not valid for the `Address` type.
+	pub fn address(&self) -> Option<&Address> {
+		self.address.as_ref()
+	}
+
+	/// Get the number of received bytes.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Check if the received message is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Get the reception flags of the received message.
+	pub fn flags(&self) -> RecvFlags {
+		self.flags
+	}
+
+	/// Get the received control data.
+	pub fn cdata(&self) -> &SocketAncillary<'a> {
+		&self.cdata
+	}
+}
+
+/// Guard that restores the blocking mode of a socket when dropped.
+///
+/// Created by [`Socket::nonblocking_scope()`] and [`Socket::blocking_scope()`].
+/// The guard dereferences to the socket, so it can be used in place of the socket while it is alive.
+pub struct NonblockingScope<'a, Address: AsSocketAddress> {
+	socket: &'a Socket<Address>,
+	previous: Option<bool>,
+}
+
+impl<'a, Address: AsSocketAddress> NonblockingScope<'a, Address> {
+	fn new(socket: &'a Socket<Address>, non_blocking: bool) -> std::io::Result<Self> {
+		let previous = socket.get_nonblocking()?;
+		if previous == non_blocking {
+			return Ok(Self { socket, previous: None });
+		}
+		socket.set_nonblocking(non_blocking)?;
+		Ok(Self { socket, previous: Some(previous) })
+	}
+
+	/// Restore the previous mode of the socket and report any error.
+	///
+	/// Dropping the guard also restores the previous mode, but ignores errors.
+	pub fn restore(mut self) -> std::io::Result<()> {
+		match self.previous.take() {
+			Some(previous) => self.socket.set_nonblocking(previous),
+			None => Ok(()),
+		}
+	}
+}
+
+impl<Address: AsSocketAddress> std::ops::Deref for NonblockingScope<'_, Address> {
+	type Target = Socket<Address>;
+
+	fn deref(&self) -> &Self::Target {
+		self.socket
+	}
+}
+
+impl<Address: AsSocketAddress> Drop for NonblockingScope<'_, Address> {
+	fn drop(&mut self) {
+		if let Some(previous) = self.previous.take() {
+			let _ = self.socket.set_nonblocking(previous);
+		}
+	}
+}
+
+impl<Address: AsSocketAddress + std::fmt::Debug> std::fmt::Debug for Socket<Address> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let mut debug = f.debug_struct("Socket");
+		debug.field("fd", &self.as_raw_fd());
+		if let Ok(address) = self.local_addr() {
+			debug.field("local_addr", &address);
+		}
+		if let Ok(address) = self.peer_addr() {
+			debug.field("peer_addr", &address);
+		}
+		debug.finish()
+	}
+}
+
 impl<Address: AsSocketAddress> FromRawFd for Socket<Address> {
 	unsafe fn from_raw_fd(fd: RawFd) -> Self {
 		Self::from_raw_fd(fd)
@@ -487,11 +1760,29 @@ impl<Address: AsSocketAddress> IntoRawFd for Socket<Address> {
 	}
 }
 
+impl<Address: AsSocketAddress> AsFd for Socket<Address> {
+	fn as_fd(&self) -> BorrowedFd<'_> {
+		unsafe { BorrowedFd::borrow_raw(self.as_raw_fd()) }
+	}
+}
+
+impl<Address: AsSocketAddress> From<OwnedFd> for Socket<Address> {
+	fn from(fd: OwnedFd) -> Self {
+		unsafe { Self::from_raw_fd(fd.into_raw_fd()) }
+	}
+}
+
+impl<Address: AsSocketAddress> From<Socket<Address>> for OwnedFd {
+	fn from(socket: Socket<Address>) -> Self {
+		unsafe { Self::from_raw_fd(socket.into_raw_fd()) }
+	}
+}
+
 /// Wrap the return value of a libc function in an [`std::io::Result`].
 ///
 /// If the return value is -1, [`last_os_error()`](std::io::Error::last_os_error) is returned.
 /// Otherwise, the return value is returned wrapped as [`Ok`].
-fn check_ret(ret: c_int) -> std::io::Result<c_int> {
+pub(crate) fn check_ret(ret: c_int) -> std::io::Result<c_int> {
 	if ret == -1 {
 		Err(std::io::Error::last_os_error())
 	} else {
@@ -503,7 +1794,7 @@ fn check_ret(ret: c_int) -> std::io::Result<c_int> {
 ///
 /// If the return value is -1, [`last_os_error()`](std::io::Error::last_os_error) is returned.
 /// Otherwise, the return value is returned wrapped as [`Ok`].
-fn check_ret_isize(ret: isize) -> std::io::Result<isize> {
+pub(crate) fn check_ret_isize(ret: isize) -> std::io::Result<isize> {
 	if ret == -1 {
 		Err(std::io::Error::last_os_error())
 	} else {
@@ -511,8 +1802,17 @@ fn check_ret_isize(ret: isize) -> std::io::Result<isize> {
 	}
 }
 
+/// Put a file descriptor in non-blocking mode with `fcntl`.
+pub(crate) fn set_fd_nonblocking(fd: RawFd) -> std::io::Result<()> {
+	unsafe {
+		let flags = check_ret(libc::fcntl(fd, libc::F_GETFL))?;
+		check_ret(libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK))?;
+	}
+	Ok(())
+}
+
 /// Create a socket and wrap the created file descriptor.
-fn socket(domain: c_int, kind: c_int, protocol: c_int) -> std::io::Result<FileDesc> {
+pub(crate) fn socket(domain: c_int, kind: c_int, protocol: c_int) -> std::io::Result<FileDesc> {
 	unsafe {
 		let fd = check_ret(libc::socket(domain, kind, protocol))?;
 		Ok(FileDesc::from_raw_fd(fd))
@@ -531,6 +1831,15 @@ fn socketpair(domain: c_int, kind: c_int, protocol: c_int) -> std::io::Result<(F
 	}
 }
 
+/// Convert a [`WouldBlock`](std::io::ErrorKind::WouldBlock) error into `Ok(None)`.
+fn would_block_to_none<T>(result: std::io::Result<T>) -> std::io::Result<Option<T>> {
+	match result {
+		Ok(x) => Ok(Some(x)),
+		Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+		Err(e) => Err(e),
+	}
+}
+
 fn bool_to_c_int(value: bool) -> c_int {
 	if value {
 		1
@@ -538,3 +1847,10 @@ fn bool_to_c_int(value: bool) -> c_int {
 		0
 	}
 }
+
+/// Reinterpret an initialized buffer as a possibly uninitialized buffer.
+///
+/// This is safe because the receive functions only ever write initialized bytes to the buffer.
+fn as_uninit_slice(buffer: &mut [u8]) -> &mut [MaybeUninit<u8>] {
+	unsafe { &mut *(buffer as *mut [u8] as *mut [MaybeUninit<u8>]) }
+}
@@ -0,0 +1,111 @@
+//! `socket2` support.
+//!
+//! This module implements conversions between [`Socket`] and [`socket2::Socket`],
+//! and between the socket address types of this crate and [`socket2::SockAddr`].
+//!
+//! Converting a [`socket2::Socket`] to a [`Socket`] with a specific address type checks the domain of the socket.
+//! Converting a [`socket2::SockAddr`] to a specific address type checks the address family and length.
+
+use std::convert::TryFrom;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+use crate::{AsSocketAddress, Inet4SocketAddress, Inet6SocketAddress, Socket, SocketAddress, UnixSocketAddress};
+
+impl<Address: AsSocketAddress> From<Socket<Address>> for socket2::Socket {
+	fn from(other: Socket<Address>) -> Self {
+		unsafe { Self::from_raw_fd(other.into_raw_fd()) }
+	}
+}
+
+impl From<socket2::Socket> for Socket<SocketAddress> {
+	fn from(other: socket2::Socket) -> Self {
+		unsafe { Self::from_raw_fd(other.into_raw_fd()) }
+	}
+}
+
+/// Copy a [`socket2::SockAddr`] into a socket address of this crate.
+fn from_sock_addr<Address: AsSocketAddress>(other: &socket2::SockAddr) -> std::io::Result<Address> {
+	if other.len() > Address::max_len() {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "socket address too large"));
+	}
+	unsafe {
+		let mut output = std::mem::MaybeUninit::zeroed();
+		std::ptr::copy(
+			other.as_ptr() as *const u8,
+			Address::as_sockaddr_mut(&mut output) as *mut u8,
+			other.len() as usize,
+		);
+		Address::finalize(output, other.len())
+	}
+}
+
+/// Copy a socket address of this crate into a [`socket2::SockAddr`].
+fn to_sock_addr<Address: AsSocketAddress>(other: &Address) -> socket2::SockAddr {
+	let (storage, len) = SocketAddress::from_other(other).into_raw();
+	unsafe { socket2::SockAddr::new(storage, len) }
+}
+
+/// Implement conversions between a socket address type and [`socket2::SockAddr`].
+macro_rules! impl_sock_addr_conversion {
+	($address:ty) => {
+		impl From<$address> for socket2::SockAddr {
+			fn from(other: $address) -> Self {
+				Self::from(&other)
+			}
+		}
+
+		impl From<&$address> for socket2::SockAddr {
+			fn from(other: &$address) -> Self {
+				to_sock_addr(other)
+			}
+		}
+
+		impl TryFrom<socket2::SockAddr> for $address {
+			type Error = std::io::Error;
+
+			fn try_from(other: socket2::SockAddr) -> std::io::Result<Self> {
+				Self::try_from(&other)
+			}
+		}
+
+		impl TryFrom<&socket2::SockAddr> for $address {
+			type Error = std::io::Error;
+
+			fn try_from(other: &socket2::SockAddr) -> std::io::Result<Self> {
+				from_sock_addr(other)
+			}
+		}
+	};
+}
+
+/// Implement a checked conversion from [`socket2::Socket`] to a [`Socket`] with a specific address type.
+macro_rules! impl_socket_conversion {
+	($address:ty, $domain:expr) => {
+		impl TryFrom<socket2::Socket> for Socket<$address> {
+			type Error = std::io::Error;
+
+			fn try_from(other: socket2::Socket) -> std::io::Result<Self> {
+				let socket = unsafe { Self::from_raw_fd(other.into_raw_fd()) };
+				if socket.domain()? != $domain {
+					return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "socket has the wrong domain"));
+				}
+				Ok(socket)
+			}
+		}
+	};
+}
+
+impl_sock_addr_conversion!(SocketAddress);
+impl_sock_addr_conversion!(Inet4SocketAddress);
+impl_sock_addr_conversion!(Inet6SocketAddress);
+impl_sock_addr_conversion!(UnixSocketAddress);
+
+impl_socket_conversion!(Inet4SocketAddress, libc::AF_INET);
+impl_socket_conversion!(Inet6SocketAddress, libc::AF_INET6);
+impl_socket_conversion!(UnixSocketAddress, libc::AF_UNIX);
+
+#[cfg(target_os = "linux")]
+impl_sock_addr_conversion!(crate::CanSocketAddress);
+
+#[cfg(target_os = "linux")]
+impl_socket_conversion!(crate::CanSocketAddress, libc::AF_CAN);
@@ -0,0 +1,114 @@
+use std::os::raw::c_int;
+
+/// The type of a socket.
+///
+/// This can be passed to [`Socket::new()`](crate::Socket::new) and similar functions instead of a raw `SOCK_*` constant.
+/// To pass additional flags like `SOCK_NONBLOCK`, use the raw `c_int` value instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketType {
+	/// A reliable, connection based byte stream (`SOCK_STREAM`).
+	Stream,
+
+	/// Connectionless, unreliable messages of a fixed maximum length (`SOCK_DGRAM`).
+	Datagram,
+
+	/// A reliable, connection based stream of messages with preserved boundaries (`SOCK_SEQPACKET`).
+	SeqPacket,
+
+	/// Raw network protocol access (`SOCK_RAW`).
+	Raw,
+
+	/// Reliable datagrams without ordering guarantees (`SOCK_RDM`).
+	Rdm,
+}
+
+impl SocketType {
+	/// Get the raw `SOCK_*` value of the socket type.
+	pub fn as_raw(self) -> c_int {
+		match self {
+			Self::Stream => libc::SOCK_STREAM,
+			Self::Datagram => libc::SOCK_DGRAM,
+			Self::SeqPacket => libc::SOCK_SEQPACKET,
+			Self::Raw => libc::SOCK_RAW,
+			Self::Rdm => libc::SOCK_RDM,
+		}
+	}
+
+	/// Get the socket type from a raw `SOCK_*` value.
+	///
+	/// Returns [`None`] if the value is not a known socket type.
+	pub fn from_raw(raw: c_int) -> Option<Self> {
+		match raw {
+			libc::SOCK_STREAM => Some(Self::Stream),
+			libc::SOCK_DGRAM => Some(Self::Datagram),
+			libc::SOCK_SEQPACKET => Some(Self::SeqPacket),
+			libc::SOCK_RAW => Some(Self::Raw),
+			libc::SOCK_RDM => Some(Self::Rdm),
+			_ => None,
+		}
+	}
+}
+
+impl From<SocketType> for c_int {
+	fn from(other: SocketType) -> Self {
+		other.as_raw()
+	}
+}
+
+/// The protocol of a socket.
+///
+/// This can be passed to [`Socket::new()`](crate::Socket::new) and similar functions instead of a raw protocol number.
+/// Protocols without a constant can be created with [`Protocol::from_raw()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Protocol(c_int);
+
+impl Protocol {
+	/// The default protocol for the socket domain and type.
+	pub const DEFAULT: Self = Self(0);
+
+	/// The Internet Control Message Protocol for IPv4.
+	pub const ICMP: Self = Self(libc::IPPROTO_ICMP);
+
+	/// The Internet Control Message Protocol for IPv6.
+	pub const ICMPV6: Self = Self(libc::IPPROTO_ICMPV6);
+
+	/// The Transmission Control Protocol.
+	pub const TCP: Self = Self(libc::IPPROTO_TCP);
+
+	/// The User Datagram Protocol.
+	pub const UDP: Self = Self(libc::IPPROTO_UDP);
+
+	/// The Stream Control Transmission Protocol.
+	#[cfg(any(
+		target_os = "android",
+		target_vendor = "apple",
+		target_os = "freebsd",
+		target_os = "linux",
+		target_os = "netbsd",
+	))]
+	pub const SCTP: Self = Self(libc::IPPROTO_SCTP);
+
+	/// The Multipath Transmission Control Protocol.
+	#[cfg(target_os = "linux")]
+	pub const MPTCP: Self = Self(libc::IPPROTO_MPTCP);
+
+	/// Lightweight User Datagram Protocol.
+	#[cfg(target_os = "linux")]
+	pub const UDPLITE: Self = Self(libc::IPPROTO_UDPLITE);
+
+	/// Create a protocol from a raw protocol number.
+	pub const fn from_raw(raw: c_int) -> Self {
+		Self(raw)
+	}
+
+	/// Get the raw protocol number.
+	pub const fn as_raw(self) -> c_int {
+		self.0
+	}
+}
+
+impl From<Protocol> for c_int {
+	fn from(other: Protocol) -> Self {
+		other.as_raw()
+	}
+}
@@ -0,0 +1,115 @@
+//! Zero-copy data transfer between sockets and pipes with `splice` and `tee`.
+//!
+//! The `splice` system call moves data between a file descriptor and a pipe without copying it through userspace.
+//! To move data between two sockets, splice the data from the first socket into a pipe,
+//! and then from the pipe into the second socket.
+//!
+//! The `tee` system call duplicates data from one pipe into another pipe without consuming it.
+//!
+//! Splicing is a non-portable Linux extension.
+//! See `man 2 splice` and `man 2 tee` for more information.
+
+use std::os::unix::io::RawFd;
+
+use crate::{AsSocketAddress, Socket};
+
+/// Flags for the `splice` and `tee` system calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct SpliceFlags(u32);
+
+impl SpliceFlags {
+	/// No flags.
+	pub const NONE: Self = Self(0);
+
+	/// Attempt to move pages instead of copying them (`SPLICE_F_MOVE`).
+	pub const MOVE: Self = Self(libc::SPLICE_F_MOVE);
+
+	/// Do not block on pipe operations (`SPLICE_F_NONBLOCK`).
+	///
+	/// The socket itself may still block unless it is in non-blocking mode.
+	pub const NONBLOCK: Self = Self(libc::SPLICE_F_NONBLOCK);
+
+	/// More data will follow in a subsequent splice (`SPLICE_F_MORE`).
+	///
+	/// This is a hint similar to `MSG_MORE` when splicing into a socket.
+	pub const MORE: Self = Self(libc::SPLICE_F_MORE);
+
+	/// Create a set of flags from the raw bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the flags.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if all flags in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for SpliceFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for SpliceFlags {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Move up to `len` bytes of received data from the socket into a pipe.
+	///
+	/// Returns the number of bytes moved.
+	/// A return value of 0 means the peer closed the connection.
+	///
+	/// This function is only available on Linux.
+	pub fn splice_to(&self, pipe: RawFd, len: usize, flags: SpliceFlags) -> std::io::Result<usize> {
+		splice(self.as_raw_fd(), pipe, len, flags)
+	}
+
+	/// Move up to `len` bytes of data from a pipe into the socket to send it.
+	///
+	/// Returns the number of bytes moved.
+	///
+	/// This function is only available on Linux.
+	pub fn splice_from(&self, pipe: RawFd, len: usize, flags: SpliceFlags) -> std::io::Result<usize> {
+		splice(pipe, self.as_raw_fd(), len, flags)
+	}
+}
+
+/// Duplicate up to `len` bytes of data from one pipe into another pipe without consuming it.
+///
+/// Returns the number of bytes duplicated.
+///
+/// This function is only available on Linux.
+pub fn tee(pipe_in: RawFd, pipe_out: RawFd, len: usize, flags: SpliceFlags) -> std::io::Result<usize> {
+	unsafe {
+		let ret = libc::tee(pipe_in, pipe_out, len, flags.bits());
+		if ret < 0 {
+			Err(std::io::Error::last_os_error())
+		} else {
+			Ok(ret as usize)
+		}
+	}
+}
+
+/// Move data between two file descriptors, at least one of which must be a pipe.
+fn splice(fd_in: RawFd, fd_out: RawFd, len: usize, flags: SpliceFlags) -> std::io::Result<usize> {
+	unsafe {
+		let ret = libc::splice(fd_in, std::ptr::null_mut(), fd_out, std::ptr::null_mut(), len, flags.bits());
+		if ret < 0 {
+			Err(std::io::Error::last_os_error())
+		} else {
+			Ok(ret as usize)
+		}
+	}
+}
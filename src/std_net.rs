@@ -0,0 +1,58 @@
+//! Conversions between [`Socket`] and the socket types of the standard library.
+
+use std::convert::TryFrom;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+use crate::{Inet4SocketAddress, Inet6SocketAddress, Socket, UnixSocketAddress};
+
+/// Implement conversions between a [`Socket`] and a standard library socket type.
+///
+/// The conversion from [`Socket`] checks the domain and type of the socket.
+/// The conversion to [`Socket`] is fallible if the standard library type can have different domains,
+/// and infallible otherwise.
+macro_rules! impl_std_conversion {
+	($address:ty, $domain:expr, $kind:expr, $listening:expr, $std:ty, fallible) => {
+		impl_std_conversion!(@from_socket $address, $domain, $kind, $listening, $std);
+
+		impl TryFrom<$std> for Socket<$address> {
+			type Error = std::io::Error;
+
+			fn try_from(other: $std) -> std::io::Result<Self> {
+				let socket = unsafe { Self::from_raw_fd(other.into_raw_fd()) };
+				socket.check_domain_and_type($domain, $kind, $listening)?;
+				Ok(socket)
+			}
+		}
+	};
+
+	($address:ty, $domain:expr, $kind:expr, $listening:expr, $std:ty, infallible) => {
+		impl_std_conversion!(@from_socket $address, $domain, $kind, $listening, $std);
+
+		impl From<$std> for Socket<$address> {
+			fn from(other: $std) -> Self {
+				unsafe { Self::from_raw_fd(other.into_raw_fd()) }
+			}
+		}
+	};
+
+	(@from_socket $address:ty, $domain:expr, $kind:expr, $listening:expr, $std:ty) => {
+		impl TryFrom<Socket<$address>> for $std {
+			type Error = std::io::Error;
+
+			fn try_from(socket: Socket<$address>) -> std::io::Result<Self> {
+				socket.check_domain_and_type($domain, $kind, $listening)?;
+				Ok(unsafe { Self::from_raw_fd(socket.into_raw_fd()) })
+			}
+		}
+	};
+}
+
+impl_std_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_STREAM, false, std::net::TcpStream, fallible);
+impl_std_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_STREAM, false, std::net::TcpStream, fallible);
+impl_std_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_STREAM, true, std::net::TcpListener, fallible);
+impl_std_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_STREAM, true, std::net::TcpListener, fallible);
+impl_std_conversion!(Inet4SocketAddress, libc::AF_INET, libc::SOCK_DGRAM, false, std::net::UdpSocket, fallible);
+impl_std_conversion!(Inet6SocketAddress, libc::AF_INET6, libc::SOCK_DGRAM, false, std::net::UdpSocket, fallible);
+impl_std_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_STREAM, false, std::os::unix::net::UnixStream, infallible);
+impl_std_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_STREAM, true, std::os::unix::net::UnixListener, infallible);
+impl_std_conversion!(UnixSocketAddress, libc::AF_UNIX, libc::SOCK_DGRAM, false, std::os::unix::net::UnixDatagram, infallible);
@@ -0,0 +1,1130 @@
+//! TCP socket options.
+//!
+//! The socket options in this module are only valid for TCP sockets.
+//! Options that are not portable are only available on the platforms that support them.
+
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+/// The maximum length of a congestion control algorithm name, including the terminating null byte.
+#[cfg(any(target_os = "android", target_os = "linux"))]
+const TCP_CA_NAME_MAX: usize = 16;
+
+/// Flag for `struct tcp_md5sig` indicating that the prefix length is valid.
+#[cfg(target_os = "linux")]
+const TCP_MD5SIG_FLAG_PREFIX: u8 = 1;
+
+/// The argument for the `TCP_MD5SIG` and `TCP_MD5SIG_EXT` options (`struct tcp_md5sig`).
+#[cfg(target_os = "linux")]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct TcpMd5Sig {
+	addr: libc::sockaddr_storage,
+	flags: u8,
+	prefix_len: u8,
+	key_len: u16,
+	ifindex: c_int,
+	key: [u8; libc::TCP_MD5SIG_MAXKEYLEN],
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Enable or disable the Nagle algorithm with the `TCP_NODELAY` option.
+	///
+	/// When enabled, small segments are sent as soon as possible instead of being combined into larger segments.
+	pub fn set_tcp_nodelay(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_NODELAY, c_int::from(enable))
+	}
+
+	/// Check if the Nagle algorithm is disabled with the `TCP_NODELAY` option.
+	pub fn tcp_nodelay(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_NODELAY)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable corking with the `TCP_CORK` option.
+	///
+	/// When enabled, partial segments are not sent until the option is disabled again,
+	/// or until a timeout of 200 milliseconds expires.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_cork(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_CORK, c_int::from(enable))
+	}
+
+	/// Check if corking is enabled with the `TCP_CORK` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_cork(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_CORK)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable the `TCP_NOPUSH` option, the BSD equivalent of `TCP_CORK`.
+	///
+	/// When enabled, partial segments are not sent until the option is disabled again or the connection is closed.
+	///
+	/// This function is only available on FreeBSD, DragonFly BSD, macOS and iOS.
+	#[cfg(any(target_os = "freebsd", target_os = "dragonfly", target_vendor = "apple"))]
+	pub fn set_tcp_nopush(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_NOPUSH, c_int::from(enable))
+	}
+
+	/// Check if the `TCP_NOPUSH` option is enabled.
+	///
+	/// This function is only available on FreeBSD, DragonFly BSD, macOS and iOS.
+	#[cfg(any(target_os = "freebsd", target_os = "dragonfly", target_vendor = "apple"))]
+	pub fn tcp_nopush(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_NOPUSH)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable quick acknowledgements with the `TCP_QUICKACK` option.
+	///
+	/// When enabled, acknowledgements are sent immediately instead of being delayed.
+	/// The option is not permanent: the kernel may switch back to delayed acknowledgements on its own.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_quickack(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_QUICKACK, c_int::from(enable))
+	}
+
+	/// Check if quick acknowledgements are enabled with the `TCP_QUICKACK` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_quickack(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_QUICKACK)?;
+		Ok(value != 0)
+	}
+
+	/// Set the maximum time that sent data may remain unacknowledged with the `TCP_USER_TIMEOUT` option.
+	///
+	/// When the timeout expires, the connection is forcibly closed and an `ETIMEDOUT` error is reported.
+	/// Pass `None` to use the system default.
+	///
+	/// The timeout has millisecond resolution and is rounded up to the next millisecond.
+	/// Returns an error if the timeout is too large.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 tcp` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_user_timeout(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+		let millis = match timeout {
+			None => 0,
+			Some(timeout) => {
+				let millis = timeout.as_nanos().div_ceil(1_000_000);
+				if millis > c_int::MAX as u128 {
+					return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "user timeout is too large"));
+				}
+				millis as std::os::raw::c_uint
+			},
+		};
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT, millis)
+	}
+
+	/// Get the maximum time that sent data may remain unacknowledged with the `TCP_USER_TIMEOUT` option.
+	///
+	/// Returns `None` if the system default is used.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_user_timeout(&self) -> std::io::Result<Option<std::time::Duration>> {
+		let millis: std::os::raw::c_uint = self.get_option(libc::IPPROTO_TCP, libc::TCP_USER_TIMEOUT)?;
+		if millis == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(std::time::Duration::from_millis(millis.into())))
+		}
+	}
+
+	/// Only wake up `accept()` when data has arrived with the `TCP_DEFER_ACCEPT` option.
+	///
+	/// The option is used on listening sockets.
+	/// Connections without data are only accepted when the timeout expires.
+	/// Pass `None` to disable the option.
+	///
+	/// The timeout has a resolution of one second and is rounded up to the next second.
+	/// The kernel further rounds the timeout up to a number of SYN-ACK retransmissions.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 tcp` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_defer_accept(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+		let seconds = match timeout {
+			None => 0,
+			Some(timeout) => {
+				let seconds = timeout.as_nanos().div_ceil(1_000_000_000);
+				if seconds > c_int::MAX as u128 {
+					return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "defer accept timeout is too large"));
+				}
+				seconds as c_int
+			},
+		};
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT, seconds)
+	}
+
+	/// Get the timeout of the `TCP_DEFER_ACCEPT` option.
+	///
+	/// Returns `None` if the option is disabled.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_defer_accept(&self) -> std::io::Result<Option<std::time::Duration>> {
+		let seconds: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_DEFER_ACCEPT)?;
+		if seconds <= 0 {
+			Ok(None)
+		} else {
+			Ok(Some(std::time::Duration::from_secs(seconds as u64)))
+		}
+	}
+
+	/// Limit the amount of unsent data in the send buffer with the `TCP_NOTSENT_LOWAT` option.
+	///
+	/// The socket is only reported as writable when the amount of unsent data is below the limit.
+	/// This reduces the amount of data queued in the kernel, so the application can prioritize data itself.
+	/// Use `u32::MAX` to remove the limit.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 tcp` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_notsent_lowat(&self, bytes: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_NOTSENT_LOWAT, bytes)
+	}
+
+	/// Get the limit for unsent data in the send buffer with the `TCP_NOTSENT_LOWAT` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_notsent_lowat(&self) -> std::io::Result<u32> {
+		self.get_option(libc::IPPROTO_TCP, libc::TCP_NOTSENT_LOWAT)
+	}
+
+	/// Set the maximum segment size for sent packets with the `TCP_MAXSEG` option.
+	///
+	/// The option must be set before the connection is established.
+	/// The kernel may ignore values outside of the range supported by the interface.
+	///
+	/// See `man 7 tcp` for more information.
+	pub fn set_tcp_maxseg(&self, bytes: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_MAXSEG, bytes as c_int)
+	}
+
+	/// Get the maximum segment size for sent packets with the `TCP_MAXSEG` option.
+	pub fn tcp_maxseg(&self) -> std::io::Result<u32> {
+		let bytes: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_MAXSEG)?;
+		Ok(bytes as u32)
+	}
+
+	/// Set the number of SYN retransmissions before giving up on a connection attempt with the `TCP_SYNCNT` option.
+	///
+	/// The kernel does not accept more than 127 retransmissions.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_syncnt(&self, count: u8) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_SYNCNT, c_int::from(count))
+	}
+
+	/// Get the number of SYN retransmissions before giving up on a connection attempt with the `TCP_SYNCNT` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_syncnt(&self) -> std::io::Result<u8> {
+		let count: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_SYNCNT)?;
+		Ok(count as u8)
+	}
+
+	/// Limit the size of the advertised receive window with the `TCP_WINDOW_CLAMP` option.
+	///
+	/// The kernel does not use a window smaller than half of the minimum receive buffer size.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_window_clamp(&self, bytes: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_WINDOW_CLAMP, bytes as c_int)
+	}
+
+	/// Get the limit for the size of the advertised receive window with the `TCP_WINDOW_CLAMP` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_window_clamp(&self) -> std::io::Result<u32> {
+		let bytes: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_WINDOW_CLAMP)?;
+		Ok(bytes as u32)
+	}
+
+	/// Set the lifetime of orphaned sockets in the `FIN_WAIT2` state with the `TCP_LINGER2` option.
+	///
+	/// A zero duration selects the system default.
+	/// Pass `None` to skip the `FIN_WAIT2` state and close orphaned sockets immediately.
+	///
+	/// The timeout has a resolution of one second and is rounded up to the next second.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 tcp` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_linger2(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+		let seconds = match timeout {
+			None => -1,
+			Some(timeout) => {
+				let seconds = timeout.as_nanos().div_ceil(1_000_000_000);
+				if seconds > c_int::MAX as u128 {
+					return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "linger timeout is too large"));
+				}
+				seconds as c_int
+			},
+		};
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_LINGER2, seconds)
+	}
+
+	/// Get the lifetime of orphaned sockets in the `FIN_WAIT2` state with the `TCP_LINGER2` option.
+	///
+	/// Returns `None` if orphaned sockets skip the `FIN_WAIT2` state.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_linger2(&self) -> std::io::Result<Option<std::time::Duration>> {
+		let seconds: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_LINGER2)?;
+		if seconds < 0 {
+			Ok(None)
+		} else {
+			Ok(Some(std::time::Duration::from_secs(seconds as u64)))
+		}
+	}
+
+	/// Save the SYN packet of accepted connections with the `TCP_SAVE_SYN` option.
+	///
+	/// The option is used on listening sockets.
+	/// The saved IP and TCP headers can be retrieved from accepted sockets with [`Self::tcp_saved_syn()`].
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_save_syn(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_SAVE_SYN, c_int::from(enable))
+	}
+
+	/// Check if the SYN packet of accepted connections is saved with the `TCP_SAVE_SYN` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_save_syn(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_SAVE_SYN)?;
+		Ok(value != 0)
+	}
+
+	/// Get the saved SYN packet of an accepted connection with the `TCP_SAVED_SYN` option.
+	///
+	/// The returned data contains the raw IP and TCP headers of the SYN packet.
+	/// The kernel only returns the saved SYN packet once: subsequent calls return `None`.
+	///
+	/// Returns `None` if no SYN packet was saved for the connection.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_saved_syn(&self) -> std::io::Result<Option<Vec<u8>>> {
+		let mut buffer = vec![0u8; 512];
+		loop {
+			let mut len = buffer.len() as libc::socklen_t;
+			let ret = unsafe {
+				libc::getsockopt(self.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_SAVED_SYN, buffer.as_mut_ptr() as *mut _, &mut len)
+			};
+			if ret == 0 {
+				if len == 0 {
+					return Ok(None);
+				}
+				buffer.truncate(len as usize);
+				return Ok(Some(buffer));
+			}
+			let error = std::io::Error::last_os_error();
+			if error.raw_os_error() == Some(libc::EINVAL) && len as usize > buffer.len() {
+				buffer.resize(len as usize, 0);
+			} else {
+				return Err(error);
+			}
+		}
+	}
+
+	/// Enable or disable the `TCP_CM_INQ` control message with the `TCP_INQ` option.
+	///
+	/// When enabled, received messages include a [`TcpInq`](crate::ancillary::AncillaryData::TcpInq) control message
+	/// with the number of bytes remaining in the receive queue.
+	///
+	/// This function is only available on Linux.
+	#[cfg(target_os = "linux")]
+	pub fn set_tcp_inq(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_INQ, c_int::from(enable))
+	}
+
+	/// Check if the `TCP_CM_INQ` control message is enabled with the `TCP_INQ` option.
+	///
+	/// This function is only available on Linux.
+	#[cfg(target_os = "linux")]
+	pub fn tcp_inq(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_INQ)?;
+		Ok(value != 0)
+	}
+
+	/// Select the congestion control algorithm with the `TCP_CONGESTION` option.
+	///
+	/// The algorithm must be available in the kernel, for example `"cubic"` or `"bbr"`.
+	/// Unprivileged processes can only select algorithms listed in `net.ipv4.tcp_allowed_congestion_control`.
+	///
+	/// Returns an error if the name is too long or contains a null byte.
+	///
+	/// This function is only available on Linux and Android.
+	/// See `man 7 tcp` for more information.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn set_tcp_congestion(&self, algorithm: &str) -> std::io::Result<()> {
+		let algorithm = algorithm.as_bytes();
+		if algorithm.len() >= TCP_CA_NAME_MAX {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "congestion control algorithm name is too long"));
+		}
+		if algorithm.contains(&0) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "congestion control algorithm name contains a null byte"));
+		}
+		self.set_option_slice(libc::IPPROTO_TCP, libc::TCP_CONGESTION, algorithm)
+	}
+
+	/// Get the name of the congestion control algorithm with the `TCP_CONGESTION` option.
+	///
+	/// This function is only available on Linux and Android.
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	pub fn tcp_congestion(&self) -> std::io::Result<String> {
+		let mut buffer = [0u8; TCP_CA_NAME_MAX];
+		let len = self.get_option_slice(libc::IPPROTO_TCP, libc::TCP_CONGESTION, &mut buffer)?;
+		let len = buffer[..len].iter().position(|&c| c == 0).unwrap_or(len);
+		String::from_utf8(buffer[..len].to_vec())
+			.map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "congestion control algorithm name is not valid UTF-8"))
+	}
+}
+
+#[cfg(target_os = "linux")]
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Set the TCP MD5 signature key for a peer with the `TCP_MD5SIG` option (RFC 2385).
+	///
+	/// All segments exchanged with the peer are signed and verified with the key.
+	/// The port of the peer address is ignored.
+	/// To accept signed connections, the key must be set on the listening socket before the connection is made.
+	///
+	/// Returns an error if the key is longer than `TCP_MD5SIG_MAXKEYLEN` (80) bytes.
+	///
+	/// This function is only available on Linux.
+	/// See `man 7 tcp` for more information.
+	pub fn set_tcp_md5sig(&self, peer: &Address, key: &[u8]) -> std::io::Result<()> {
+		let option = tcp_md5sig(peer, 0, 0, key)?;
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_MD5SIG, option)
+	}
+
+	/// Set the TCP MD5 signature key for all peers in an address prefix with the `TCP_MD5SIG_EXT` option.
+	///
+	/// This works like [`Self::set_tcp_md5sig()`],
+	/// but the key is used for all peers that match the first `prefix_len` bits of the peer address.
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_md5sig_prefix(&self, peer: &Address, prefix_len: u8, key: &[u8]) -> std::io::Result<()> {
+		let option = tcp_md5sig(peer, TCP_MD5SIG_FLAG_PREFIX, prefix_len, key)?;
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_MD5SIG_EXT, option)
+	}
+
+	/// Remove the TCP MD5 signature key for a peer.
+	///
+	/// This function is only available on Linux.
+	pub fn remove_tcp_md5sig(&self, peer: &Address) -> std::io::Result<()> {
+		self.set_tcp_md5sig(peer, &[])
+	}
+
+	/// Remove the TCP MD5 signature key for an address prefix.
+	///
+	/// This function is only available on Linux.
+	pub fn remove_tcp_md5sig_prefix(&self, peer: &Address, prefix_len: u8) -> std::io::Result<()> {
+		self.set_tcp_md5sig_prefix(peer, prefix_len, &[])
+	}
+}
+
+/// Create the argument for the `TCP_MD5SIG` and `TCP_MD5SIG_EXT` options.
+#[cfg(target_os = "linux")]
+fn tcp_md5sig<Address: AsSocketAddress>(peer: &Address, flags: u8, prefix_len: u8, key: &[u8]) -> std::io::Result<TcpMd5Sig> {
+	if key.len() > libc::TCP_MD5SIG_MAXKEYLEN {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "TCP MD5 signature key is too long"));
+	}
+	unsafe {
+		let mut option: TcpMd5Sig = std::mem::zeroed();
+		option.addr = sockaddr_storage(peer)?;
+		option.flags = flags;
+		option.prefix_len = prefix_len;
+		option.key_len = key.len() as u16;
+		option.key[..key.len()].copy_from_slice(key);
+		Ok(option)
+	}
+}
+
+/// Copy a socket address into a `struct sockaddr_storage`.
+#[cfg(target_os = "linux")]
+fn sockaddr_storage<Address: AsSocketAddress>(address: &Address) -> std::io::Result<libc::sockaddr_storage> {
+	let len = address.len() as usize;
+	if len > Address::max_len() as usize || len > std::mem::size_of::<libc::sockaddr_storage>() {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "peer address is too large"));
+	}
+	unsafe {
+		let mut output: libc::sockaddr_storage = std::mem::zeroed();
+		std::ptr::copy_nonoverlapping(address.as_sockaddr() as *const u8, &mut output as *mut _ as *mut u8, len);
+		Ok(output)
+	}
+}
+
+/// The maximum length of a TCP-AO key.
+#[cfg(target_os = "linux")]
+pub const TCP_AO_MAXKEYLEN: usize = 80;
+
+#[cfg(target_os = "linux")]
+const TCP_AO_ADD_KEY: c_int = 38;
+#[cfg(target_os = "linux")]
+const TCP_AO_DEL_KEY: c_int = 39;
+#[cfg(target_os = "linux")]
+const TCP_AO_INFO: c_int = 40;
+
+/// The argument for the `TCP_AO_ADD_KEY` option (`struct tcp_ao_add`).
+#[cfg(target_os = "linux")]
+#[repr(C, align(8))]
+#[derive(Copy, Clone)]
+struct TcpAoAdd {
+	addr: libc::sockaddr_storage,
+	alg_name: [u8; 64],
+	ifindex: i32,
+	bits: u32,
+	reserved2: u16,
+	prefix: u8,
+	sndid: u8,
+	rcvid: u8,
+	maclen: u8,
+	keyflags: u8,
+	keylen: u8,
+	key: [u8; TCP_AO_MAXKEYLEN],
+}
+
+/// The argument for the `TCP_AO_DEL_KEY` option (`struct tcp_ao_del`).
+#[cfg(target_os = "linux")]
+#[repr(C, align(8))]
+#[derive(Copy, Clone)]
+struct TcpAoDel {
+	addr: libc::sockaddr_storage,
+	ifindex: i32,
+	bits: u32,
+	reserved2: u16,
+	prefix: u8,
+	sndid: u8,
+	rcvid: u8,
+	current_key: u8,
+	rnext: u8,
+	keyflags: u8,
+}
+
+/// The argument for the `TCP_AO_INFO` option (`struct tcp_ao_info_opt`).
+#[cfg(target_os = "linux")]
+#[repr(C, align(8))]
+#[derive(Copy, Clone)]
+struct TcpAoInfoOpt {
+	bits: u32,
+	reserved2: u16,
+	current_key: u8,
+	rnext: u8,
+	pkt_good: u64,
+	pkt_bad: u64,
+	pkt_key_not_found: u64,
+	pkt_ao_required: u64,
+	pkt_dropped_icmp: u64,
+}
+
+/// Get the mask for a one bit bitfield member at the given position in the TCP-AO structs.
+#[cfg(target_os = "linux")]
+fn tcp_ao_bit(index: u32) -> u32 {
+	if cfg!(target_endian = "little") {
+		1 << index
+	} else {
+		1 << (31 - index)
+	}
+}
+
+#[cfg(target_os = "linux")]
+const TCP_AO_BIT_SET_CURRENT: u32 = 0;
+#[cfg(target_os = "linux")]
+const TCP_AO_BIT_SET_RNEXT: u32 = 1;
+#[cfg(target_os = "linux")]
+const TCP_AO_BIT_AO_REQUIRED: u32 = 2;
+#[cfg(target_os = "linux")]
+const TCP_AO_BIT_ACCEPT_ICMPS: u32 = 4;
+
+/// A TCP Authentication Option (TCP-AO) key for a peer or address prefix (RFC 5925).
+///
+/// Add the key to a socket with [`Socket::add_tcp_ao_key()`].
+#[derive(Debug, Clone, Copy)]
+#[cfg(target_os = "linux")]
+pub struct TcpAoKey<'a, Address> {
+	/// The address of the peer, the port is ignored.
+	pub peer: &'a Address,
+
+	/// The number of bits of the peer address that must match to use the key.
+	pub prefix_len: u8,
+
+	/// The name of the MAC algorithm in the kernel crypto API, for example `"hmac(sha1)"` or `"cmac(aes128)"`.
+	pub algorithm: &'a str,
+
+	/// The key ID put in sent segments (SendID).
+	pub send_id: u8,
+
+	/// The key ID expected in received segments (RecvID).
+	pub recv_id: u8,
+
+	/// The length of the message authentication code, or 0 to use the default of the algorithm.
+	pub mac_len: u8,
+
+	/// The secret key.
+	pub key: &'a [u8],
+
+	/// Immediately use the key for sent segments.
+	pub set_current: bool,
+
+	/// Immediately ask the peer to use the key.
+	pub set_rnext: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl<'a, Address: AsSocketAddress> TcpAoKey<'a, Address> {
+	/// Create a new key for all peers matching an address prefix.
+	pub fn new(peer: &'a Address, prefix_len: u8, algorithm: &'a str, send_id: u8, recv_id: u8, key: &'a [u8]) -> Self {
+		Self {
+			peer,
+			prefix_len,
+			algorithm,
+			send_id,
+			recv_id,
+			mac_len: 0,
+			key,
+			set_current: false,
+			set_rnext: false,
+		}
+	}
+}
+
+/// The TCP-AO state of a socket, as reported by the `TCP_AO_INFO` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg(target_os = "linux")]
+pub struct TcpAoInfo {
+	/// The SendID of the key used for sent segments (Current_key).
+	///
+	/// When setting the options, `None` leaves the current key unchanged.
+	pub current_key: Option<u8>,
+
+	/// The RecvID of the key the peer is asked to use (RNext_key).
+	///
+	/// When setting the options, `None` leaves the requested key unchanged.
+	pub rnext: Option<u8>,
+
+	/// Reject segments without TCP-AO signature.
+	pub ao_required: bool,
+
+	/// Accept ICMP errors for the connection.
+	pub accept_icmps: bool,
+
+	/// The number of segments that were successfully verified.
+	pub packets_good: u64,
+
+	/// The number of segments that failed verification.
+	pub packets_bad: u64,
+
+	/// The number of segments with an unknown key ID.
+	pub packets_key_not_found: u64,
+
+	/// The number of segments that were dropped because they had no TCP-AO signature.
+	pub packets_ao_required: u64,
+
+	/// The number of ICMP errors that were ignored.
+	pub packets_dropped_icmp: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Add a TCP Authentication Option key with the `TCP_AO_ADD_KEY` option (RFC 5925).
+	///
+	/// To accept authenticated connections, the key must be added to the listening socket before the connection is made.
+	///
+	/// Returns an error if the key or the algorithm name is too long, or if the algorithm name contains a null byte.
+	///
+	/// This function is only available on Linux.
+	/// It requires a kernel with TCP-AO support (Linux 6.7 and later).
+	pub fn add_tcp_ao_key(&self, key: &TcpAoKey<Address>) -> std::io::Result<()> {
+		if key.key.len() > TCP_AO_MAXKEYLEN {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "TCP-AO key is too long"));
+		}
+		let algorithm = key.algorithm.as_bytes();
+		if algorithm.len() >= 64 {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "TCP-AO algorithm name is too long"));
+		}
+		if algorithm.contains(&0) {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "TCP-AO algorithm name contains a null byte"));
+		}
+
+		let mut option: TcpAoAdd = unsafe { std::mem::zeroed() };
+		option.addr = sockaddr_storage(key.peer)?;
+		option.alg_name[..algorithm.len()].copy_from_slice(algorithm);
+		if key.set_current {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_SET_CURRENT);
+		}
+		if key.set_rnext {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_SET_RNEXT);
+		}
+		option.prefix = key.prefix_len;
+		option.sndid = key.send_id;
+		option.rcvid = key.recv_id;
+		option.maclen = key.mac_len;
+		option.keylen = key.key.len() as u8;
+		option.key[..key.key.len()].copy_from_slice(key.key);
+		self.set_option(libc::IPPROTO_TCP, TCP_AO_ADD_KEY, option)
+	}
+
+	/// Remove a TCP Authentication Option key with the `TCP_AO_DEL_KEY` option.
+	///
+	/// The key is identified by the peer address, prefix length, SendID and RecvID.
+	///
+	/// This function is only available on Linux.
+	pub fn remove_tcp_ao_key(&self, peer: &Address, prefix_len: u8, send_id: u8, recv_id: u8) -> std::io::Result<()> {
+		let mut option: TcpAoDel = unsafe { std::mem::zeroed() };
+		option.addr = sockaddr_storage(peer)?;
+		option.prefix = prefix_len;
+		option.sndid = send_id;
+		option.rcvid = recv_id;
+		self.set_option(libc::IPPROTO_TCP, TCP_AO_DEL_KEY, option)
+	}
+
+	/// Get the TCP-AO state of the socket with the `TCP_AO_INFO` option.
+	///
+	/// This function is only available on Linux.
+	pub fn tcp_ao_info(&self) -> std::io::Result<TcpAoInfo> {
+		let option: TcpAoInfoOpt = self.get_option(libc::IPPROTO_TCP, TCP_AO_INFO)?;
+		let is_set = |index| option.bits & tcp_ao_bit(index) != 0;
+		Ok(TcpAoInfo {
+			current_key: Some(option.current_key).filter(|_| is_set(TCP_AO_BIT_SET_CURRENT)),
+			rnext: Some(option.rnext).filter(|_| is_set(TCP_AO_BIT_SET_RNEXT)),
+			ao_required: is_set(TCP_AO_BIT_AO_REQUIRED),
+			accept_icmps: is_set(TCP_AO_BIT_ACCEPT_ICMPS),
+			packets_good: option.pkt_good,
+			packets_bad: option.pkt_bad,
+			packets_key_not_found: option.pkt_key_not_found,
+			packets_ao_required: option.pkt_ao_required,
+			packets_dropped_icmp: option.pkt_dropped_icmp,
+		})
+	}
+
+	/// Set the TCP-AO state of the socket with the `TCP_AO_INFO` option.
+	///
+	/// The packet counters are not modified.
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_ao_info(&self, info: &TcpAoInfo) -> std::io::Result<()> {
+		let mut option: TcpAoInfoOpt = unsafe { std::mem::zeroed() };
+		if let Some(current_key) = info.current_key {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_SET_CURRENT);
+			option.current_key = current_key;
+		}
+		if let Some(rnext) = info.rnext {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_SET_RNEXT);
+			option.rnext = rnext;
+		}
+		if info.ao_required {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_AO_REQUIRED);
+		}
+		if info.accept_icmps {
+			option.bits |= tcp_ao_bit(TCP_AO_BIT_ACCEPT_ICMPS);
+		}
+		self.set_option(libc::IPPROTO_TCP, TCP_AO_INFO, option)
+	}
+}
+
+/// A queue of a TCP socket in repair mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg(target_os = "linux")]
+pub enum TcpRepairQueue {
+	/// No queue is selected.
+	None,
+
+	/// The receive queue.
+	Recv,
+
+	/// The send queue.
+	Send,
+}
+
+#[cfg(target_os = "linux")]
+impl TcpRepairQueue {
+	/// Get the queue from the raw `TCP_*_QUEUE` value used by the kernel.
+	pub fn from_raw(queue: c_int) -> Option<Self> {
+		match queue {
+			0 => Some(Self::None),
+			1 => Some(Self::Recv),
+			2 => Some(Self::Send),
+			_ => None,
+		}
+	}
+
+	/// Get the raw `TCP_*_QUEUE` value used by the kernel.
+	pub fn to_raw(self) -> c_int {
+		match self {
+			Self::None => 0,
+			Self::Recv => 1,
+			Self::Send => 2,
+		}
+	}
+}
+
+/// A TCP option negotiated during the handshake, restored with [`Socket::set_tcp_repair_options()`] (`struct tcp_repair_opt`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+#[cfg(target_os = "linux")]
+pub struct TcpRepairOption {
+	/// The TCP option code.
+	pub code: u32,
+
+	/// The value of the option.
+	pub value: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl TcpRepairOption {
+	/// Create a maximum segment size option.
+	pub fn mss(mss: u16) -> Self {
+		Self { code: 2, value: mss.into() }
+	}
+
+	/// Create a window scale option with the send and receive window scale.
+	pub fn window_scale(send: u8, recv: u8) -> Self {
+		Self { code: 3, value: u32::from(send) | u32::from(recv) << 16 }
+	}
+
+	/// Create a selective acknowledgements permitted option.
+	pub fn sack_permitted() -> Self {
+		Self { code: 4, value: 0 }
+	}
+
+	/// Create a timestamps option.
+	pub fn timestamp() -> Self {
+		Self { code: 8, value: 0 }
+	}
+}
+
+#[cfg(target_os = "linux")]
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Enable or disable repair mode with the `TCP_REPAIR` option.
+	///
+	/// In repair mode, the state of a connection can be restored without exchanging packets with the peer.
+	/// For example, [`Self::connect()`] immediately puts the socket in the established state.
+	/// This is used by checkpoint/restore tools to migrate connections.
+	///
+	/// This requires the `CAP_NET_ADMIN` capability.
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_repair(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_REPAIR, c_int::from(enable))
+	}
+
+	/// Check if repair mode is enabled with the `TCP_REPAIR` option.
+	///
+	/// This function is only available on Linux.
+	pub fn tcp_repair(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_REPAIR)?;
+		Ok(value > 0)
+	}
+
+	/// Select the queue for [`Self::set_tcp_queue_seq()`] and for sending and receiving with the `TCP_REPAIR_QUEUE` option.
+	///
+	/// In repair mode, sent data is added to the selected queue instead of being transmitted,
+	/// and received data is read from the selected queue.
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_repair_queue(&self, queue: TcpRepairQueue) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE, queue.to_raw())
+	}
+
+	/// Get the selected queue with the `TCP_REPAIR_QUEUE` option.
+	///
+	/// This function is only available on Linux.
+	pub fn tcp_repair_queue(&self) -> std::io::Result<TcpRepairQueue> {
+		let queue: c_int = self.get_option(libc::IPPROTO_TCP, libc::TCP_REPAIR_QUEUE)?;
+		TcpRepairQueue::from_raw(queue)
+			.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown TCP repair queue"))
+	}
+
+	/// Set the sequence number of the selected queue with the `TCP_QUEUE_SEQ` option.
+	///
+	/// The sequence number can only be set before the connection is restored with [`Self::connect()`].
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_queue_seq(&self, seq: u32) -> std::io::Result<()> {
+		self.set_option(libc::IPPROTO_TCP, libc::TCP_QUEUE_SEQ, seq)
+	}
+
+	/// Get the sequence number of the selected queue with the `TCP_QUEUE_SEQ` option.
+	///
+	/// This function is only available on Linux.
+	pub fn tcp_queue_seq(&self) -> std::io::Result<u32> {
+		self.get_option(libc::IPPROTO_TCP, libc::TCP_QUEUE_SEQ)
+	}
+
+	/// Restore the options negotiated during the handshake with the `TCP_REPAIR_OPTIONS` option.
+	///
+	/// The options can only be set on a restored connection that is still in repair mode.
+	///
+	/// This function is only available on Linux.
+	pub fn set_tcp_repair_options(&self, options: &[TcpRepairOption]) -> std::io::Result<()> {
+		self.set_option_slice(libc::IPPROTO_TCP, libc::TCP_REPAIR_OPTIONS, options)
+	}
+}
+
+/// The state of a TCP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg(target_os = "linux")]
+pub enum TcpState {
+	/// The connection is open and data can be transferred in both directions (`TCP_ESTABLISHED`).
+	Established,
+	/// A connection request was sent and the socket is waiting for a reply (`TCP_SYN_SENT`).
+	SynSent,
+	/// A connection request was received and answered, waiting for the final acknowledgement (`TCP_SYN_RECV`).
+	SynRecv,
+	/// The socket was shut down locally and is waiting for the peer to acknowledge it (`TCP_FIN_WAIT1`).
+	FinWait1,
+	/// The local shutdown was acknowledged and the socket is waiting for the peer to shut down (`TCP_FIN_WAIT2`).
+	FinWait2,
+	/// Both sides shut down and the socket is waiting for stray packets to expire (`TCP_TIME_WAIT`).
+	TimeWait,
+	/// The socket is not connected (`TCP_CLOSE`).
+	Close,
+	/// The peer shut down and the socket is waiting for the local side to shut down (`TCP_CLOSE_WAIT`).
+	CloseWait,
+	/// Both sides shut down and the socket is waiting for the peer to acknowledge the local shutdown (`TCP_LAST_ACK`).
+	LastAck,
+	/// The socket is listening for incoming connections (`TCP_LISTEN`).
+	Listen,
+	/// Both sides shut down simultaneously and the socket is waiting for an acknowledgement (`TCP_CLOSING`).
+	Closing,
+	/// A connection request was received by a listening socket, but no full socket was created yet (`TCP_NEW_SYN_RECV`).
+	NewSynRecv,
+	/// An unknown state.
+	Other(u8),
+}
+
+#[cfg(target_os = "linux")]
+impl TcpState {
+	/// Get the state from the raw `TCP_*` state value used by the kernel.
+	pub fn from_raw(state: u8) -> Self {
+		match state {
+			1 => Self::Established,
+			2 => Self::SynSent,
+			3 => Self::SynRecv,
+			4 => Self::FinWait1,
+			5 => Self::FinWait2,
+			6 => Self::TimeWait,
+			7 => Self::Close,
+			8 => Self::CloseWait,
+			9 => Self::LastAck,
+			10 => Self::Listen,
+			11 => Self::Closing,
+			12 => Self::NewSynRecv,
+			other => Self::Other(other),
+		}
+	}
+}
+
+/// Information about a TCP connection, as reported by the `TCP_INFO` option.
+///
+/// Older kernels report a shorter struct.
+/// Accessors for fields that were not reported by the kernel return `None`.
+///
+/// This struct is only available on Linux.
+#[derive(Clone)]
+#[cfg(target_os = "linux")]
+pub struct TcpInfo {
+	inner: libc::tcp_info,
+	len: usize,
+}
+
+/// Get a field of a `TcpInfo` if the kernel reported it.
+#[cfg(target_os = "linux")]
+macro_rules! tcp_info_field {
+	($self:ident . $field:ident) => {{
+		let offset = &$self.inner.$field as *const _ as usize - &$self.inner as *const _ as usize;
+		if offset + std::mem::size_of_val(&$self.inner.$field) <= $self.len {
+			Some($self.inner.$field)
+		} else {
+			None
+		}
+	}};
+}
+
+#[cfg(target_os = "linux")]
+impl TcpInfo {
+	/// Get the raw `struct tcp_info`.
+	///
+	/// Fields that were not reported by the kernel are zero.
+	pub fn as_raw(&self) -> &libc::tcp_info {
+		&self.inner
+	}
+
+	/// Get the number of bytes reported by the kernel.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Check if the kernel reported no information at all.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Get the state of the connection.
+	pub fn state(&self) -> TcpState {
+		TcpState::from_raw(self.inner.tcpi_state)
+	}
+
+	/// Get the state of the congestion control algorithm.
+	pub fn ca_state(&self) -> u8 {
+		self.inner.tcpi_ca_state
+	}
+
+	/// Get the number of unrecovered retransmission timeouts.
+	pub fn retransmits(&self) -> u8 {
+		self.inner.tcpi_retransmits
+	}
+
+	/// Get the retransmission timeout.
+	pub fn rto(&self) -> std::time::Duration {
+		std::time::Duration::from_micros(self.inner.tcpi_rto.into())
+	}
+
+	/// Get the smoothed round trip time.
+	pub fn rtt(&self) -> std::time::Duration {
+		std::time::Duration::from_micros(self.inner.tcpi_rtt.into())
+	}
+
+	/// Get the round trip time variance.
+	pub fn rttvar(&self) -> std::time::Duration {
+		std::time::Duration::from_micros(self.inner.tcpi_rttvar.into())
+	}
+
+	/// Get the minimum observed round trip time.
+	pub fn min_rtt(&self) -> Option<std::time::Duration> {
+		tcp_info_field!(self.tcpi_min_rtt).map(|x| std::time::Duration::from_micros(x.into()))
+	}
+
+	/// Get the maximum segment size for sending.
+	pub fn snd_mss(&self) -> u32 {
+		self.inner.tcpi_snd_mss
+	}
+
+	/// Get the maximum segment size for receiving.
+	pub fn rcv_mss(&self) -> u32 {
+		self.inner.tcpi_rcv_mss
+	}
+
+	/// Get the congestion window in segments.
+	pub fn snd_cwnd(&self) -> u32 {
+		self.inner.tcpi_snd_cwnd
+	}
+
+	/// Get the slow start threshold in segments.
+	pub fn snd_ssthresh(&self) -> u32 {
+		self.inner.tcpi_snd_ssthresh
+	}
+
+	/// Get the number of unacknowledged segments.
+	pub fn unacked(&self) -> u32 {
+		self.inner.tcpi_unacked
+	}
+
+	/// Get the number of segments that are considered lost.
+	pub fn lost(&self) -> u32 {
+		self.inner.tcpi_lost
+	}
+
+	/// Get the total number of retransmitted segments.
+	pub fn total_retrans(&self) -> u32 {
+		self.inner.tcpi_total_retrans
+	}
+
+	/// Get the path MTU.
+	pub fn pmtu(&self) -> u32 {
+		self.inner.tcpi_pmtu
+	}
+
+	/// Get the pacing rate in bytes per second.
+	pub fn pacing_rate(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_pacing_rate)
+	}
+
+	/// Get the number of bytes acknowledged by the peer.
+	pub fn bytes_acked(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_bytes_acked)
+	}
+
+	/// Get the number of bytes received from the peer.
+	pub fn bytes_received(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_bytes_received)
+	}
+
+	/// Get the number of bytes in the send queue that have not been sent yet.
+	pub fn notsent_bytes(&self) -> Option<u32> {
+		tcp_info_field!(self.tcpi_notsent_bytes)
+	}
+
+	/// Get the most recent delivery rate in bytes per second.
+	pub fn delivery_rate(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_delivery_rate)
+	}
+
+	/// Get the number of bytes sent, including retransmissions.
+	pub fn bytes_sent(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_bytes_sent)
+	}
+
+	/// Get the number of bytes retransmitted.
+	pub fn bytes_retrans(&self) -> Option<u64> {
+		tcp_info_field!(self.tcpi_bytes_retrans)
+	}
+}
+
+#[cfg(target_os = "linux")]
+impl std::fmt::Debug for TcpInfo {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_struct("TcpInfo")
+			.field("state", &self.state())
+			.field("rtt", &self.rtt())
+			.field("rttvar", &self.rttvar())
+			.field("snd_cwnd", &self.snd_cwnd())
+			.field("retransmits", &self.retransmits())
+			.field("total_retrans", &self.total_retrans())
+			.field("delivery_rate", &self.delivery_rate())
+			.finish()
+	}
+}
+
+#[cfg(target_os = "linux")]
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Get information about the TCP connection with the `TCP_INFO` option.
+	///
+	/// This function is only available on Linux.
+	pub fn tcp_info(&self) -> std::io::Result<TcpInfo> {
+		unsafe {
+			let mut inner = std::mem::zeroed::<libc::tcp_info>();
+			let mut len = std::mem::size_of_val(&inner) as libc::socklen_t;
+			crate::socket::check_ret(libc::getsockopt(
+				self.as_raw_fd(),
+				libc::IPPROTO_TCP,
+				libc::TCP_INFO,
+				&mut inner as *mut _ as *mut _,
+				&mut len,
+			))?;
+			Ok(TcpInfo { inner, len: len as usize })
+		}
+	}
+}
@@ -0,0 +1,278 @@
+//! Packet timestamping support (`SO_TIMESTAMPING`).
+//!
+//! With `SO_TIMESTAMPING`, the kernel can report software and hardware timestamps
+//! for received and transmitted packets.
+//!
+//! Receive timestamps are delivered as `SCM_TIMESTAMPING` control message with the received data.
+//! They are parsed as [`AncillaryData::Timestamping`](crate::ancillary::AncillaryData::Timestamping).
+//!
+//! Transmit timestamps are delivered on the socket error queue,
+//! and can be received with [`Socket::recv_tx_timestamp()`].
+//!
+//! Timestamping is a non-portable Linux extension.
+//! See <https://www.kernel.org/doc/html/latest/networking/timestamping.html> for more information.
+
+use std::time::Duration;
+
+use crate::ancillary::{AncillaryData, SocketAncillary};
+use crate::errqueue::ExtendedErrorOrigin;
+use crate::socket::check_ret;
+use crate::{AsSocketAddress, Socket};
+
+/// The ioctl to get the receive timestamp of the last packet as `struct timespec`.
+const SIOCGSTAMPNS: u32 = 0x8907;
+
+/// Flags for the `SO_TIMESTAMPING` socket option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct TimestampingFlags(u32);
+
+impl TimestampingFlags {
+	/// No flags.
+	pub const NONE: Self = Self(0);
+
+	/// Generate hardware timestamps for transmitted packets (`SOF_TIMESTAMPING_TX_HARDWARE`).
+	pub const TX_HARDWARE: Self = Self(libc::SOF_TIMESTAMPING_TX_HARDWARE);
+
+	/// Generate software timestamps for transmitted packets (`SOF_TIMESTAMPING_TX_SOFTWARE`).
+	pub const TX_SOFTWARE: Self = Self(libc::SOF_TIMESTAMPING_TX_SOFTWARE);
+
+	/// Generate hardware timestamps for received packets (`SOF_TIMESTAMPING_RX_HARDWARE`).
+	pub const RX_HARDWARE: Self = Self(libc::SOF_TIMESTAMPING_RX_HARDWARE);
+
+	/// Generate software timestamps for received packets (`SOF_TIMESTAMPING_RX_SOFTWARE`).
+	pub const RX_SOFTWARE: Self = Self(libc::SOF_TIMESTAMPING_RX_SOFTWARE);
+
+	/// Report software timestamps (`SOF_TIMESTAMPING_SOFTWARE`).
+	pub const SOFTWARE: Self = Self(libc::SOF_TIMESTAMPING_SOFTWARE);
+
+	/// Report hardware timestamps (`SOF_TIMESTAMPING_RAW_HARDWARE`).
+	pub const RAW_HARDWARE: Self = Self(libc::SOF_TIMESTAMPING_RAW_HARDWARE);
+
+	/// Include an identifier in transmit timestamps to match them with send calls (`SOF_TIMESTAMPING_OPT_ID`).
+	pub const OPT_ID: Self = Self(libc::SOF_TIMESTAMPING_OPT_ID);
+
+	/// Generate transmit timestamps when packets enter the packet scheduler (`SOF_TIMESTAMPING_TX_SCHED`).
+	pub const TX_SCHED: Self = Self(libc::SOF_TIMESTAMPING_TX_SCHED);
+
+	/// Generate transmit timestamps when all data has been acknowledged by the peer (`SOF_TIMESTAMPING_TX_ACK`).
+	pub const TX_ACK: Self = Self(libc::SOF_TIMESTAMPING_TX_ACK);
+
+	/// Also report the packet info with transmit timestamps (`SOF_TIMESTAMPING_OPT_CMSG`).
+	pub const OPT_CMSG: Self = Self(libc::SOF_TIMESTAMPING_OPT_CMSG);
+
+	/// Do not loop the packet data back with transmit timestamps (`SOF_TIMESTAMPING_OPT_TSONLY`).
+	pub const OPT_TSONLY: Self = Self(libc::SOF_TIMESTAMPING_OPT_TSONLY);
+
+	/// Report transmit statistics together with transmit timestamps (`SOF_TIMESTAMPING_OPT_STATS`).
+	pub const OPT_STATS: Self = Self(libc::SOF_TIMESTAMPING_OPT_STATS);
+
+	/// Report the receiving interface with hardware receive timestamps (`SOF_TIMESTAMPING_OPT_PKTINFO`).
+	pub const OPT_PKTINFO: Self = Self(libc::SOF_TIMESTAMPING_OPT_PKTINFO);
+
+	/// Report software and hardware transmit timestamps separately (`SOF_TIMESTAMPING_OPT_TX_SWHW`).
+	pub const OPT_TX_SWHW: Self = Self(libc::SOF_TIMESTAMPING_OPT_TX_SWHW);
+
+	/// Create a set of flags from the raw bits.
+	pub fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	/// Get the raw bits of the flags.
+	pub fn bits(self) -> u32 {
+		self.0
+	}
+
+	/// Check if all flags in `other` are also set in `self`.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+}
+
+impl std::ops::BitOr for TimestampingFlags {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl std::ops::BitOrAssign for TimestampingFlags {
+	fn bitor_assign(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+}
+
+/// The timestamps of an `SCM_TIMESTAMPING` control message (`struct scm_timestamping`).
+///
+/// The timestamps are relative to the clock that generated them.
+/// Software timestamps use `CLOCK_REALTIME`, hardware timestamps use the clock of the network device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timestamps {
+	software: Option<Duration>,
+	hardware: Option<Duration>,
+}
+
+impl Timestamps {
+	/// Parse the timestamps from the data of an `SCM_TIMESTAMPING` control message.
+	///
+	/// Returns an error if the data is too short.
+	pub fn parse(data: &[u8]) -> std::io::Result<Self> {
+		if data.len() < 3 * std::mem::size_of::<libc::timespec>() {
+			return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "timestamping control message too short"));
+		}
+
+		// The second timestamp is deprecated and always zero.
+		let timestamps: [libc::timespec; 3] = unsafe { std::ptr::read_unaligned(data.as_ptr() as *const _) };
+		Ok(Self {
+			software: timespec_to_duration(&timestamps[0]),
+			hardware: timespec_to_duration(&timestamps[2]),
+		})
+	}
+
+	/// Get the software timestamp, if the kernel reported one.
+	pub fn software(&self) -> Option<Duration> {
+		self.software
+	}
+
+	/// Get the hardware timestamp, if the kernel reported one.
+	pub fn hardware(&self) -> Option<Duration> {
+		self.hardware
+	}
+}
+
+/// The point in the transmit path where a transmit timestamp was generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxTimestampKind {
+	/// The packet entered the packet scheduler (`SCM_TSTAMP_SCHED`).
+	Scheduled,
+
+	/// The packet was passed to the network device or transmitted by the hardware (`SCM_TSTAMP_SND`).
+	Sent,
+
+	/// All data was acknowledged by the peer (`SCM_TSTAMP_ACK`).
+	Acknowledged,
+
+	/// An unknown timestamp type.
+	Other(u32),
+}
+
+impl TxTimestampKind {
+	/// Get the kind from the raw `SCM_TSTAMP_*` value.
+	pub fn from_raw(kind: u32) -> Self {
+		match kind {
+			0 => Self::Sent,
+			1 => Self::Scheduled,
+			2 => Self::Acknowledged,
+			other => Self::Other(other),
+		}
+	}
+}
+
+/// A transmit timestamp received from the socket error queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxTimestamp {
+	kind: TxTimestampKind,
+	id: u32,
+	timestamps: Timestamps,
+}
+
+impl TxTimestamp {
+	/// Get the point in the transmit path where the timestamp was generated.
+	pub fn kind(&self) -> TxTimestampKind {
+		self.kind
+	}
+
+	/// Get the identifier of the send call that generated the timestamp.
+	///
+	/// This is only meaningful if [`TimestampingFlags::OPT_ID`] is enabled.
+	/// For datagram sockets, the identifier is a counter of send calls starting at 0.
+	/// For stream sockets, it is the byte offset of the last byte of the send call.
+	pub fn id(&self) -> u32 {
+		self.id
+	}
+
+	/// Get the timestamps.
+	pub fn timestamps(&self) -> Timestamps {
+		self.timestamps
+	}
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Set the timestamping flags of the socket.
+	pub fn set_timestamping(&self, flags: TimestampingFlags) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_TIMESTAMPING, flags.bits())
+	}
+
+	/// Get the timestamping flags of the socket.
+	pub fn timestamping(&self) -> std::io::Result<TimestampingFlags> {
+		let bits: u32 = self.get_option(libc::SOL_SOCKET, libc::SO_TIMESTAMPING)?;
+		Ok(TimestampingFlags::from_bits(bits))
+	}
+
+	/// Get the receive timestamp of the last packet received on the socket.
+	///
+	/// This uses the `SIOCGSTAMPNS` ioctl, which works without enabling timestamping on the socket.
+	/// The timestamp is a software timestamp relative to `CLOCK_REALTIME`.
+	///
+	/// Returns an error if no packet has been received yet.
+	pub fn last_rx_timestamp(&self) -> std::io::Result<Duration> {
+		unsafe {
+			let mut timestamp = std::mem::zeroed::<libc::timespec>();
+			check_ret(libc::ioctl(self.as_raw_fd(), SIOCGSTAMPNS as _, &mut timestamp))?;
+			Ok(Duration::new(timestamp.tv_sec as u64, timestamp.tv_nsec as u32))
+		}
+	}
+
+	/// Receive a transmit timestamp from the socket error queue.
+	///
+	/// This never blocks: if the error queue is empty, `None` is returned.
+	///
+	/// If the error queue holds a different error, that error is returned instead.
+	/// Any looped back packet data is discarded.
+	pub fn recv_tx_timestamp(&self) -> std::io::Result<Option<TxTimestamp>> {
+		// Use u64 for the buffer to get a properly aligned control message header.
+		let mut buffer = [0u64; 32];
+		let cdata: &mut [u8] = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, std::mem::size_of_val(&buffer)) };
+		let mut ancillary = SocketAncillary::new(cdata);
+		match self.recv_msg(&[], &mut ancillary, libc::MSG_ERRQUEUE) {
+			Ok(_) => (),
+			Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(None),
+			Err(e) => return Err(e),
+		}
+
+		let mut timestamps = None;
+		let mut error = None;
+		for message in ancillary.messages() {
+			match message {
+				Ok(AncillaryData::Timestamping(x)) => timestamps = Some(x),
+				Ok(AncillaryData::ExtendedError(x)) => error = Some(x),
+				_ => (),
+			}
+		}
+
+		let error = error.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "no extended error in socket error queue message"))?;
+		if error.origin() != ExtendedErrorOrigin::TxStatus {
+			return match error.error() {
+				Some(e) => Err(e),
+				None => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected message in socket error queue")),
+			};
+		}
+		let timestamps = timestamps.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "no timestamps in socket error queue message"))?;
+
+		Ok(Some(TxTimestamp {
+			kind: TxTimestampKind::from_raw(error.info()),
+			id: error.data(),
+			timestamps,
+		}))
+	}
+}
+
+/// Convert a timestamp to a duration, or `None` if the timestamp is zero.
+fn timespec_to_duration(timestamp: &libc::timespec) -> Option<Duration> {
+	if timestamp.tv_sec == 0 && timestamp.tv_nsec == 0 {
+		None
+	} else {
+		Some(Duration::new(timestamp.tv_sec as u64, timestamp.tv_nsec as u32))
+	}
+}
@@ -0,0 +1,64 @@
+//! UDP socket options.
+//!
+//! The socket options in this module are only valid for UDP sockets.
+//!
+//! UDP segmentation offload (GSO) allows sending many datagrams of the same size with a single system call,
+//! and UDP receive offload (GRO) allows receiving many datagrams from the same flow as a single large buffer.
+//! This is used by high performance datagram protocols such as QUIC.
+
+use std::os::raw::c_int;
+
+use crate::{AsSocketAddress, Socket};
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Set the segment size for UDP segmentation offload with the `UDP_SEGMENT` option.
+	///
+	/// When set, sent buffers larger than the segment size are split into multiple datagrams of the segment size.
+	/// The last datagram may be smaller.
+	/// Use 0 to disable segmentation.
+	///
+	/// The segment size can also be set per message with [`SocketAncillary::add_udp_segment()`](crate::ancillary::SocketAncillary::add_udp_segment).
+	///
+	/// See `man 7 udp` for more information.
+	pub fn set_udp_segment(&self, segment_size: u16) -> std::io::Result<()> {
+		self.set_option(libc::SOL_UDP, libc::UDP_SEGMENT, c_int::from(segment_size))
+	}
+
+	/// Get the segment size for UDP segmentation offload with the `UDP_SEGMENT` option.
+	pub fn udp_segment(&self) -> std::io::Result<u16> {
+		let segment_size: c_int = self.get_option(libc::SOL_UDP, libc::UDP_SEGMENT)?;
+		Ok(segment_size as u16)
+	}
+
+	/// Enable or disable UDP receive offload with the `UDP_GRO` option.
+	///
+	/// When enabled, the kernel may coalesce multiple received datagrams into a single buffer.
+	/// The size of the original datagrams is reported with a [`UdpGroSegmentSize`](crate::ancillary::AncillaryData::UdpGroSegmentSize) control message.
+	/// Applications that enable this option must always read the control messages to split the received buffer.
+	pub fn set_udp_gro(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_UDP, libc::UDP_GRO, c_int::from(enable))
+	}
+
+	/// Check if UDP receive offload is enabled with the `UDP_GRO` option.
+	pub fn udp_gro(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::SOL_UDP, libc::UDP_GRO)?;
+		Ok(value != 0)
+	}
+
+	/// Enable or disable corking with the `UDP_CORK` option.
+	///
+	/// While corked, all data written to the socket is accumulated into a single datagram.
+	/// The datagram is sent when the option is disabled again.
+	/// This is similar to passing `MSG_MORE` to every send call.
+	///
+	/// See `man 7 udp` for more information.
+	pub fn set_udp_cork(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_UDP, libc::UDP_CORK, c_int::from(enable))
+	}
+
+	/// Check if corking is enabled with the `UDP_CORK` option.
+	pub fn udp_cork(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::SOL_UDP, libc::UDP_CORK)?;
+		Ok(value != 0)
+	}
+}
@@ -0,0 +1,119 @@
+//! Zero-copy transmission support (`MSG_ZEROCOPY`).
+//!
+//! With zero-copy transmission, the kernel transmits data directly from the pages of the user buffer.
+//! The buffer of a send call must not be modified until the kernel reports that it is done with it.
+//! The kernel reports this through completion notifications on the socket error queue.
+//!
+//! Every successful send call with [`MSG_ZEROCOPY`](libc::MSG_ZEROCOPY) is assigned a sequence number,
+//! starting at 0 for the first call on the socket.
+//! A single completion notification covers a range of consecutive sequence numbers.
+//!
+//! Zero-copy transmission is a non-portable Linux extension.
+//! See <https://www.kernel.org/doc/html/latest/networking/msg_zerocopy.html> for more information.
+
+use std::os::raw::c_int;
+
+use crate::errqueue::ExtendedErrorOrigin;
+use crate::{AsSocketAddress, Socket};
+
+/// The origin of extended errors that report zero-copy completions.
+pub const SO_EE_ORIGIN_ZEROCOPY: u8 = 5;
+
+/// Flag set in the code of a zero-copy completion if the kernel copied the data anyway.
+pub const SO_EE_CODE_ZEROCOPY_COPIED: u8 = 1;
+
+/// A zero-copy completion notification.
+///
+/// The notification covers all send calls with sequence numbers in the range `first..=last`.
+/// The range may wrap around at `u32::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZerocopyCompletion {
+	first: u32,
+	last: u32,
+	copied: bool,
+}
+
+impl ZerocopyCompletion {
+	/// Create a completion notification for a range of sequence numbers.
+	pub fn new(first: u32, last: u32, copied: bool) -> Self {
+		Self { first, last, copied }
+	}
+
+	/// Get the sequence number of the first send call covered by the notification.
+	pub fn first(&self) -> u32 {
+		self.first
+	}
+
+	/// Get the sequence number of the last send call covered by the notification.
+	pub fn last(&self) -> u32 {
+		self.last
+	}
+
+	/// Get the number of send calls covered by the notification.
+	pub fn count(&self) -> u64 {
+		u64::from(self.last.wrapping_sub(self.first)) + 1
+	}
+
+	/// Check if the notification covers the send call with the given sequence number.
+	pub fn contains(&self, sequence: u32) -> bool {
+		sequence.wrapping_sub(self.first) <= self.last.wrapping_sub(self.first)
+	}
+
+	/// Check if the kernel copied the data instead of transmitting it from the user buffer.
+	///
+	/// The buffers can be reused either way,
+	/// but if this happens often, zero-copy transmission is only adding overhead for the socket.
+	pub fn copied(&self) -> bool {
+		self.copied
+	}
+}
+
+impl<Address: AsSocketAddress> Socket<Address> {
+	/// Enable or disable zero-copy transmission for the socket.
+	///
+	/// This must be enabled before sending with [`MSG_ZEROCOPY`](libc::MSG_ZEROCOPY).
+	pub fn set_zerocopy(&self, enable: bool) -> std::io::Result<()> {
+		self.set_option(libc::SOL_SOCKET, libc::SO_ZEROCOPY, c_int::from(enable))
+	}
+
+	/// Check if zero-copy transmission is enabled for the socket.
+	pub fn zerocopy(&self) -> std::io::Result<bool> {
+		let value: c_int = self.get_option(libc::SOL_SOCKET, libc::SO_ZEROCOPY)?;
+		Ok(value != 0)
+	}
+
+	/// Send data over the socket to the connected peer without copying it into the kernel.
+	///
+	/// The data must not be modified until a completion notification has been received for the call.
+	/// Zero-copy transmission must first be enabled with [`Self::set_zerocopy()`].
+	///
+	/// Returns the number of transferred bytes, or an error.
+	pub fn send_zerocopy(&self, data: &[u8], flags: c_int) -> std::io::Result<usize> {
+		self.send(data, flags | libc::MSG_ZEROCOPY)
+	}
+
+	/// Receive a zero-copy completion notification from the socket error queue.
+	///
+	/// This never blocks: if the error queue is empty, `None` is returned.
+	///
+	/// If the error queue holds a different error, that error is returned instead.
+	pub fn recv_zerocopy_completion(&self) -> std::io::Result<Option<ZerocopyCompletion>> {
+		let error = match self.recv_error_queue()? {
+			None => return Ok(None),
+			Some(x) => x,
+		};
+
+		if error.origin() != ExtendedErrorOrigin::Zerocopy {
+			return match error.error() {
+				Some(e) => Err(e),
+				None => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected message in socket error queue")),
+			};
+		}
+
+		Ok(Some(ZerocopyCompletion {
+			first: error.info(),
+			last: error.data(),
+			copied: error.as_raw().ee_code & SO_EE_CODE_ZEROCOPY_COPIED != 0,
+		}))
+	}
+}
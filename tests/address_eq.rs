@@ -0,0 +1,86 @@
+use assert2::assert;
+use posix_socket::{Inet4SocketAddress, Inet6SocketAddress, SocketAddress, UnixSocketAddress};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_inet_eq() {
+	let a = Inet4SocketAddress::new(&Ipv4Addr::new(1, 2, 3, 4), 80);
+	let b = Inet4SocketAddress::new(&Ipv4Addr::new(1, 2, 3, 4), 81);
+	assert!(a == a.clone());
+	assert!(a != b);
+	assert!(SocketAddress::from(&a) == SocketAddress::from(&a));
+	assert!(SocketAddress::from(&a) != SocketAddress::from(&b));
+
+	let a = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0);
+	let b = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 1);
+	assert!(a == a.clone());
+	assert!(a != b);
+}
+
+#[test]
+fn test_unix_eq() {
+	let a = UnixSocketAddress::new("/tmp/a").unwrap();
+	let b = UnixSocketAddress::new("/tmp/b").unwrap();
+	assert!(a == UnixSocketAddress::new("/tmp/a").unwrap());
+	assert!(a != b);
+	assert!(UnixSocketAddress::new("").unwrap() == UnixSocketAddress::new_unnamed());
+}
+
+#[test]
+fn test_hash_map_key() {
+	let mut map = HashMap::new();
+	map.insert(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 1), 1);
+	map.insert(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 2), 2);
+	map.insert(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 1), 3);
+	assert!(map.len() == 2);
+	assert!(map[&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 1)] == 3);
+}
+
+#[test]
+fn test_unix_bytes() {
+	let address = UnixSocketAddress::new("/tmp/a").unwrap();
+	assert!(address.as_bytes() == b"/tmp/a\0");
+	assert!(UnixSocketAddress::from_path_bytes(address.as_bytes()).unwrap() == address);
+	assert!(address.as_abstract_bytes().is_none());
+
+	let unnamed = UnixSocketAddress::from_path_bytes(b"").unwrap();
+	assert!(unnamed.is_unnamed());
+	assert!(unnamed.as_bytes().is_empty());
+
+	let name = b"\0name\0with\xffbytes";
+	let address = UnixSocketAddress::from_path_bytes(name).unwrap();
+	assert!(address.as_bytes() == name);
+	assert!(address.as_abstract_bytes() == Some(&name[1..]));
+	assert!(address.as_path().is_none());
+
+	assert!(let Err(_) = UnixSocketAddress::from_path_bytes(&[b'a'; 1024]));
+}
+
+#[test]
+fn test_from_other() {
+	let inet6 = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 1, 2);
+	let generic = SocketAddress::from_other(&inet6);
+	assert!(generic.family() == libc::AF_INET6);
+	assert!(generic.as_inet6() == Some(inet6));
+
+	let unix = UnixSocketAddress::new("/tmp/a").unwrap();
+	let generic = SocketAddress::from_other(&unix);
+	assert!(generic.family() == libc::AF_UNIX);
+	assert!(generic.as_unix() == Some(unix));
+}
+
+#[test]
+fn test_oversized_len() {
+	let unix = UnixSocketAddress::from_raw(unsafe { std::mem::zeroed() }, 50_000_000);
+	assert!(unix == unix.clone());
+	assert!(unix.as_bytes().len() <= std::mem::size_of::<libc::sockaddr_un>());
+
+	let generic = SocketAddress::from_raw(unsafe { std::mem::zeroed() }, 50_000_000);
+	assert!(generic == generic.clone());
+	assert!(SocketAddress::from_other(&unix) == SocketAddress::from_other(&unix.clone()));
+
+	let mut map = HashMap::new();
+	map.insert(generic.clone(), 1);
+	assert!(map[&generic] == 1);
+}
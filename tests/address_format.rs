@@ -0,0 +1,84 @@
+use assert2::assert;
+use posix_socket::{Inet4SocketAddress, Inet6SocketAddress, SocketAddress, UnixSocket, UnixSocketAddress};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_debug_inet() {
+	let address = Inet4SocketAddress::new(&Ipv4Addr::new(1, 2, 3, 4), 80);
+	assert!(format!("{:?}", address) == "Inet4SocketAddress(1.2.3.4:80)");
+	assert!(format!("{:?}", SocketAddress::from(&address)) == "Inet4SocketAddress(1.2.3.4:80)");
+
+	let address = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0);
+	assert!(format!("{:?}", address) == "Inet6SocketAddress([::1]:80)");
+	assert!(format!("{:?}", SocketAddress::from(&address)) == "Inet6SocketAddress([::1]:80)");
+}
+
+#[test]
+fn test_debug_unix() {
+	let address = UnixSocketAddress::new("/tmp/socket").unwrap();
+	assert!(format!("{:?}", address) == "UnixSocketAddress(\"/tmp/socket\")");
+	assert!(format!("{:?}", SocketAddress::from(&address)) == "UnixSocketAddress(\"/tmp/socket\")");
+	assert!(format!("{:?}", UnixSocketAddress::new_unnamed()) == "UnixSocketAddress(unnamed)");
+
+	let address = UnixSocketAddress::from_path_bytes(b"\0foobar").unwrap();
+	assert!(format!("{:?}", address) == "UnixSocketAddress(abstract \"foobar\")");
+	assert!(format!("{:?}", SocketAddress::from(&address)) == "UnixSocketAddress(abstract \"foobar\")");
+}
+
+#[test]
+fn test_debug_socket() {
+	let (a, _b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let debug = format!("{:?}", a);
+	assert!(debug.starts_with(&format!("Socket {{ fd: {}", a.as_raw_fd())));
+	assert!(debug.contains("local_addr: UnixSocketAddress(unnamed)"));
+}
+
+#[test]
+fn test_display_and_parse_inet() {
+	let address: Inet4SocketAddress = "1.2.3.4:80".parse().unwrap();
+	assert!(address.ip() == Ipv4Addr::new(1, 2, 3, 4));
+	assert!(address.port() == 80);
+	assert!(address.to_string() == "1.2.3.4:80");
+	assert!(SocketAddress::from(&address).to_string() == "1.2.3.4:80");
+	let error = "1.2.3.4".parse::<Inet4SocketAddress>().unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::InvalidInput);
+	assert!(let Err(_) = "[::1]:80".parse::<Inet4SocketAddress>());
+
+	let address: Inet6SocketAddress = "[::1]:80".parse().unwrap();
+	assert!(address.ip() == Ipv6Addr::LOCALHOST);
+	assert!(address.port() == 80);
+	assert!(address.to_string() == "[::1]:80");
+	assert!(SocketAddress::from(&address).to_string() == "[::1]:80");
+	let error = "1.2.3.4:80".parse::<Inet6SocketAddress>().unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_display_unix() {
+	let address = UnixSocketAddress::new("/tmp/socket").unwrap();
+	assert!(SocketAddress::from(&address).to_string() == "/tmp/socket");
+	assert!(SocketAddress::from(&UnixSocketAddress::new_unnamed()).to_string() == "(unnamed)");
+
+	let address = UnixSocketAddress::from_path_bytes(b"\0foobar").unwrap();
+	assert!(SocketAddress::from(&address).to_string() == "@foobar");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_display_and_parse_inet6_zone() {
+	let address: Inet6SocketAddress = "[fe80::1%lo]:80".parse().unwrap();
+	assert!(address.ip() == "fe80::1".parse::<Ipv6Addr>().unwrap());
+	assert!(address.port() == 80);
+	assert!(address.scope_id() != 0);
+	assert!(address.scope_interface_name().unwrap() == "lo");
+
+	let index = address.scope_id();
+	assert!(address.to_string() == format!("[fe80::1%{}]:80", index));
+	assert!(format!("{:?}", address) == format!("Inet6SocketAddress([fe80::1%{}]:80)", index));
+	let address: Inet6SocketAddress = format!("[fe80::1%{}]:80", index).parse().unwrap();
+	assert!(address.scope_id() == index);
+
+	assert!(let Err(_) = "[fe80::1%no-such-interface]:80".parse::<Inet6SocketAddress>());
+	assert!(Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0).scope_id() == 0);
+	assert!(let Err(_) = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0).scope_interface_name());
+}
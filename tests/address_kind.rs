@@ -0,0 +1,66 @@
+use assert2::assert;
+use posix_socket::{AddressKind, Inet4SocketAddress, Inet6SocketAddress, SocketAddress, UnixSocketAddress};
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_kind() {
+	let inet4 = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 80);
+	assert!(SocketAddress::from(&inet4).kind() == AddressKind::Inet4(inet4));
+
+	let inet6 = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0);
+	assert!(SocketAddress::from(&inet6).kind() == AddressKind::Inet6(inet6));
+
+	let unix = UnixSocketAddress::new("/tmp/a").unwrap();
+	assert!(SocketAddress::from(&unix).kind() == AddressKind::Unix(unix));
+
+	let mut raw: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+	raw.ss_family = libc::AF_APPLETALK as libc::sa_family_t;
+	let other = SocketAddress::from_raw(raw, std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t);
+	assert!(other.kind() == AddressKind::Other(libc::AF_APPLETALK));
+}
+
+#[test]
+fn test_try_from() {
+	let inet4 = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 80);
+	let generic = SocketAddress::from(&inet4);
+	assert!(let Ok(_) = Inet4SocketAddress::try_from(&generic));
+	assert!(Inet4SocketAddress::try_from(generic.clone()).unwrap() == inet4);
+	assert!(let Err(_) = Inet6SocketAddress::try_from(&generic));
+	assert!(let Err(_) = UnixSocketAddress::try_from(generic));
+}
+
+#[test]
+fn test_std_socket_addr() {
+	let std_address: std::net::SocketAddr = "127.0.0.1:80".parse().unwrap();
+	let address = SocketAddress::from(std_address);
+	assert!(address.family() == libc::AF_INET);
+	assert!(std::net::SocketAddr::try_from(&address).unwrap() == std_address);
+
+	let std_address: std::net::SocketAddr = "[::1]:80".parse().unwrap();
+	let address = SocketAddress::from(&std_address);
+	assert!(address.family() == libc::AF_INET6);
+	assert!(std::net::SocketAddr::try_from(address).unwrap() == std_address);
+
+	let unix = SocketAddress::from(UnixSocketAddress::new("/tmp/a").unwrap());
+	assert!(let Err(_) = std::net::SocketAddr::try_from(unix));
+}
+
+#[test]
+fn test_short_address() {
+	let inet4 = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 80);
+	let (raw, len) = SocketAddress::from(&inet4).into_raw();
+	assert!(SocketAddress::from_raw(raw, len).as_inet4() == Some(inet4));
+	assert!(SocketAddress::from_raw(raw, len - 1).as_inet4().is_none());
+	assert!(SocketAddress::from_raw(raw, 0).kind() == AddressKind::Other(libc::AF_INET));
+
+	let inet6 = Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 80, 0, 0);
+	let (raw, len) = SocketAddress::from(&inet6).into_raw();
+	assert!(SocketAddress::from_raw(raw, len - 4).as_inet6().is_none());
+
+	let unix = UnixSocketAddress::new("/tmp/a").unwrap();
+	let (raw, len) = SocketAddress::from(&unix).into_raw();
+	assert!(SocketAddress::from_raw(raw, len).as_unix() == Some(unix));
+	assert!(SocketAddress::from_raw(raw, 1).as_unix().is_none());
+	assert!(SocketAddress::from_raw(raw, 4096).as_unix().is_none());
+}
@@ -0,0 +1,55 @@
+#![cfg(all(feature = "ax25", target_os = "linux"))]
+
+use assert2::assert;
+use posix_socket::{AsSocketAddress, Ax25Callsign, Ax25SocketAddress, RoseAddress, RoseSocketAddress};
+
+#[test]
+fn test_parse_format_callsign() {
+	let callsign: Ax25Callsign = "pe1abc-7".parse().unwrap();
+	assert!(callsign.callsign() == "PE1ABC");
+	assert!(callsign.ssid() == 7);
+	assert!(callsign.to_string() == "PE1ABC-7");
+	assert!(callsign.into_raw() == [b'P' << 1, b'E' << 1, b'1' << 1, b'A' << 1, b'B' << 1, b'C' << 1, 7 << 1]);
+
+	let callsign: Ax25Callsign = "N0CALL".parse().unwrap();
+	assert!(callsign.ssid() == 0);
+	assert!(callsign.to_string() == "N0CALL");
+
+	let callsign: Ax25Callsign = "AB1".parse().unwrap();
+	assert!(callsign.callsign() == "AB1");
+
+	assert!(let Err(_) = "".parse::<Ax25Callsign>());
+	assert!(let Err(_) = "TOOLONG".parse::<Ax25Callsign>());
+	assert!(let Err(_) = "N0CALL-16".parse::<Ax25Callsign>());
+	assert!(let Err(_) = "N0CALL-".parse::<Ax25Callsign>());
+	assert!(let Err(_) = "N0 CALL".parse::<Ax25Callsign>());
+}
+
+#[test]
+fn test_parse_format_rose_address() {
+	let address: RoseAddress = "2041123456".parse().unwrap();
+	assert!(address.digits() == [2, 0, 4, 1, 1, 2, 3, 4, 5, 6]);
+	assert!(address.to_string() == "2041123456");
+
+	assert!(let Err(_) = "204112345".parse::<RoseAddress>());
+	assert!(let Err(_) = "204112345x".parse::<RoseAddress>());
+}
+
+#[test]
+fn test_socket_address_digipeaters() {
+	let callsign: Ax25Callsign = "N0CALL".parse().unwrap();
+	let digi: Ax25Callsign = "DIGI-1".parse().unwrap();
+
+	let address = Ax25SocketAddress::new(callsign);
+	assert!(address.len() == 16);
+	assert!(address.digipeaters().is_empty());
+
+	let address = Ax25SocketAddress::with_digipeaters(callsign, &[digi, digi]).unwrap();
+	assert!(address.len() == 72);
+	assert!(address.digipeaters() == &[digi, digi]);
+	assert!(let Err(_) = Ax25SocketAddress::with_digipeaters(callsign, &[digi; 9]));
+
+	let rose = RoseSocketAddress::with_digipeaters("2041123456".parse().unwrap(), callsign, &[digi]).unwrap();
+	assert!(rose.len() == 28);
+	assert!(rose.digipeaters() == &[digi]);
+}
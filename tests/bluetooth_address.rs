@@ -0,0 +1,32 @@
+#![cfg(all(feature = "bluetooth", target_os = "linux"))]
+
+use assert2::assert;
+use posix_socket::{AsSocketAddress, BdAddr, L2capSocketAddress, RfcommSocketAddress};
+
+#[test]
+fn test_parse_format_bdaddr() {
+	let address: BdAddr = "00:1a:7D:DA:71:13".parse().unwrap();
+	assert!(address.bytes() == [0x00, 0x1A, 0x7D, 0xDA, 0x71, 0x13]);
+	assert!(address.to_string() == "00:1A:7D:DA:71:13");
+
+	assert!(let Err(_) = "00:1A:7D:DA:71".parse::<BdAddr>());
+	assert!(let Err(_) = "00:1A:7D:DA:71:13:00".parse::<BdAddr>());
+	assert!(let Err(_) = "00:1A:7D:DA:71:1".parse::<BdAddr>());
+	assert!(let Err(_) = "00:1A:7D:DA:71:XX".parse::<BdAddr>());
+}
+
+#[test]
+fn test_address_accessors() {
+	let device = BdAddr::new([1, 2, 3, 4, 5, 6]);
+
+	let l2cap = L2capSocketAddress::new(device, 0x1001);
+	assert!(l2cap.address() == device);
+	assert!(l2cap.psm() == 0x1001);
+	assert!(l2cap.family() == libc::AF_BLUETOOTH as libc::sa_family_t);
+	assert!(l2cap.len() == 14);
+
+	let rfcomm = RfcommSocketAddress::new(device, 3);
+	assert!(rfcomm.address() == device);
+	assert!(rfcomm.channel() == 3);
+	assert!(rfcomm.len() == 10);
+}
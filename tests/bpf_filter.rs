@@ -0,0 +1,112 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use filedesc::FileDesc;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+const BPF_PROG_LOAD: libc::c_long = 5;
+const BPF_PROG_TYPE_SOCKET_FILTER: u32 = 1;
+
+/// A single eBPF instruction (`struct bpf_insn`).
+#[repr(C)]
+struct Instruction {
+	code: u8,
+	registers: u8,
+	offset: i16,
+	immediate: i32,
+}
+
+/// The fields of `union bpf_attr` used by `BPF_PROG_LOAD`, padded to the size of the union.
+#[repr(C)]
+struct ProgramLoadAttr {
+	program_type: u32,
+	instruction_count: u32,
+	instructions: u64,
+	license: u64,
+	padding: [u64; 13],
+}
+
+/// Load an eBPF socket filter that drops all packets, or `None` if loading programs is not permitted.
+fn load_drop_all() -> Option<FileDesc> {
+	// r0 = 0; exit
+	let instructions = [
+		Instruction { code: 0xb7, registers: 0, offset: 0, immediate: 0 },
+		Instruction { code: 0x95, registers: 0, offset: 0, immediate: 0 },
+	];
+	let license = b"GPL\0";
+	let mut attr = ProgramLoadAttr {
+		program_type: BPF_PROG_TYPE_SOCKET_FILTER,
+		instruction_count: instructions.len() as u32,
+		instructions: instructions.as_ptr() as u64,
+		license: license.as_ptr() as u64,
+		padding: [0; 13],
+	};
+
+	let fd = unsafe { libc::syscall(libc::SYS_bpf, BPF_PROG_LOAD, &mut attr as *mut ProgramLoadAttr, std::mem::size_of_val(&attr)) };
+	if fd < 0 {
+		let error = std::io::Error::last_os_error();
+		match error.raw_os_error() {
+			Some(libc::EPERM) | Some(libc::EACCES) | Some(libc::ENOSYS) => return None,
+			_ => panic!("failed to load BPF program: {}", error),
+		}
+	}
+	Some(unsafe { FileDesc::from_raw_fd(fd as i32) })
+}
+
+fn udp_pair() -> (Inet4Socket, Inet4Socket) {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.connect(&server.local_addr().unwrap()).unwrap();
+	(client, server)
+}
+
+#[test]
+fn test_bpf_filter_errors() {
+	let (_client, server) = udp_pair();
+	let error = server.detach_bpf_filter().unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::ENOENT));
+	let error = server.attach_bpf_filter(-1).unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::EBADF));
+	let error = server.attach_bpf_filter(server.as_raw_fd()).unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::EINVAL));
+	let error = server.attach_reuseport_bpf(server.as_raw_fd()).unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::EINVAL));
+}
+
+#[test]
+fn test_bpf_filter_drop_all() {
+	let program = match load_drop_all() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let (client, server) = udp_pair();
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(()) = server.attach_bpf_filter(program.as_raw_fd()));
+	drop(program);
+	client.send(b"dropped", 0).unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+	let error = server.recv(&mut buffer, libc::MSG_DONTWAIT).unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::WouldBlock);
+
+	assert!(let Ok(()) = server.detach_bpf_filter());
+	client.send(b"hello!", 0).unwrap();
+	assert!(let Ok(6) = server.recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+}
+
+#[test]
+fn test_reuseport_bpf() {
+	let program = match load_drop_all() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	socket.set_reuse_port(true).unwrap();
+	socket.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Ok(()) = socket.attach_reuseport_bpf(program.as_raw_fd()));
+	assert!(let Ok(()) = socket.detach_reuseport_bpf());
+}
@@ -0,0 +1,66 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, SocketBuilder};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_build_listener() {
+	let listener = SocketBuilder::new(libc::SOCK_STREAM, 0)
+		.reuse_address(true)
+		.recv_buffer_size(16 * 1024)
+		.bind(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0))
+		.listen(4)
+		.build()
+		.unwrap();
+	assert!(let Ok(true) = listener.reuse_address());
+	assert!(listener.recv_buffer_size().unwrap() >= 16 * 1024);
+	assert!(unsafe { libc::fcntl(listener.as_raw_fd(), libc::F_GETFD) } & libc::FD_CLOEXEC != 0);
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&listener.local_addr().unwrap()).unwrap();
+	assert!(let Ok(_) = listener.accept());
+}
+
+#[test]
+fn test_build_nonblocking() {
+	let socket: Inet4Socket = SocketBuilder::new(libc::SOCK_DGRAM, 0)
+		.nonblocking(true)
+		.close_on_exec(false)
+		.bind(Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0))
+		.build()
+		.unwrap();
+	assert!(unsafe { libc::fcntl(socket.as_raw_fd(), libc::F_GETFL) } & libc::O_NONBLOCK != 0);
+	assert!(unsafe { libc::fcntl(socket.as_raw_fd(), libc::F_GETFD) } & libc::FD_CLOEXEC == 0);
+
+	let error = socket.recv(&mut [0; 8], 0).unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::WouldBlock);
+}
+
+#[test]
+fn test_build_error_context() {
+	let first = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	first.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	first.listen(1).unwrap();
+	let address = first.local_addr().unwrap();
+
+	let error = SocketBuilder::new(libc::SOCK_STREAM, 0)
+		.bind(address.clone())
+		.build()
+		.unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::AddrInUse);
+
+	let context = posix_socket::Error::get(&error).unwrap();
+	assert!(context.operation() == posix_socket::Operation::Bind);
+	assert!(context.raw_os_error() == Some(libc::EADDRINUSE));
+	assert!(context.address().unwrap().as_inet4() == Some(address.clone()));
+
+	// Wrapped errors do not expose the OS error code directly.
+	assert!(error.raw_os_error() == None);
+	assert!(posix_socket::Error::raw_os_error_of(&error) == Some(libc::EADDRINUSE));
+
+	// Errors from a single system call are returned unchanged.
+	let second = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	let error = second.bind(&address).unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::EADDRINUSE));
+	assert!(let None = posix_socket::Error::get(&error));
+	assert!(posix_socket::Error::raw_os_error_of(&error) == Some(libc::EADDRINUSE));
+}
@@ -0,0 +1,165 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::can::{CanErrorMask, CanFilter, IsoTpFlags, IsoTpFlowControlOptions, IsoTpLinkLayerOptions, IsoTpOptions};
+use posix_socket::{AsSocketAddress, CanSocket, CanSocketAddress, SocketAddress};
+use std::mem::MaybeUninit;
+use std::time::Duration;
+
+/// Create a CAN socket, or `None` if CAN sockets are not supported by the kernel.
+fn can_socket(kind: libc::c_int, protocol: libc::c_int) -> Option<CanSocket> {
+	match CanSocket::new(kind, protocol) {
+		Ok(socket) => Some(socket),
+		Err(e) if matches!(e.raw_os_error(), Some(libc::EAFNOSUPPORT) | Some(libc::EPROTONOSUPPORT)) => None,
+		Err(e) => panic!("failed to create CAN socket: {}", e),
+	}
+}
+
+#[test]
+fn test_can_filter_encoding() {
+	let filter = CanFilter::new(0x123 | libc::CAN_INV_FILTER, libc::CAN_SFF_MASK);
+	assert!(filter.id() == 0x123);
+	assert!(filter.mask() == libc::CAN_SFF_MASK);
+	assert!(!filter.is_inverted());
+
+	let inverted = CanFilter::new_inverted(0x123, libc::CAN_SFF_MASK);
+	assert!(inverted.id() == 0x123);
+	assert!(inverted.is_inverted());
+	assert!(inverted != filter);
+
+	// The filter must have the layout of `struct can_filter`.
+	assert!(std::mem::size_of::<CanFilter>() == std::mem::size_of::<libc::can_filter>());
+	let raw: libc::can_filter = unsafe { std::mem::transmute(inverted) };
+	assert!(raw.can_id == 0x123 | libc::CAN_INV_FILTER);
+	assert!(raw.can_mask == libc::CAN_SFF_MASK);
+}
+
+#[test]
+fn test_can_error_mask() {
+	let mask = CanErrorMask::BUS_OFF | CanErrorMask::NO_ACK;
+	assert!(mask.bits() == libc::CAN_ERR_BUSOFF | libc::CAN_ERR_ACK);
+	assert!(mask.contains(CanErrorMask::BUS_OFF));
+	assert!(!mask.contains(CanErrorMask::RESTARTED));
+	assert!(CanErrorMask::ALL.contains(mask));
+	assert!(CanErrorMask::from_bits(mask.bits()) == mask);
+
+	let mut mask = CanErrorMask::NONE;
+	mask |= CanErrorMask::TX_TIMEOUT;
+	assert!(mask == CanErrorMask::TX_TIMEOUT);
+}
+
+#[test]
+fn test_can_raw_filters() {
+	let socket = match can_socket(libc::SOCK_RAW, libc::CAN_RAW) {
+		Some(x) => x,
+		None => return,
+	};
+
+	let filters = [
+		CanFilter::new(0x100, 0x700),
+		CanFilter::new_inverted(0x123, libc::CAN_SFF_MASK),
+	];
+	assert!(let Ok(()) = socket.set_can_filters(&filters));
+	assert!(socket.can_filters().unwrap() == filters);
+	assert!(let Ok(()) = socket.set_can_filters(&[]));
+	assert!(socket.can_filters().unwrap().is_empty());
+
+	assert!(let Ok(()) = socket.set_can_error_filter(CanErrorMask::BUS_OFF));
+	assert!(let Ok(CanErrorMask::BUS_OFF) = socket.can_error_filter());
+}
+
+#[test]
+fn test_can_address() {
+	let address = CanSocketAddress::new(3);
+	assert!(address.family() == libc::AF_CAN as libc::sa_family_t);
+	assert!(address.interface_index() == 3);
+	assert!(address.len() as usize == std::mem::size_of::<libc::sockaddr_can>());
+
+	let isotp = CanSocketAddress::new_isotp(3, 0x7e8, 0x7e0 | libc::CAN_EFF_FLAG);
+	assert!(isotp.interface_index() == 3);
+	assert!(isotp.isotp_rx_id() == 0x7e8);
+	assert!(isotp.isotp_tx_id() == 0x7e0 | libc::CAN_EFF_FLAG);
+	assert!(isotp != address);
+
+	let raw = isotp.clone().into_raw();
+	assert!(raw.can_family == libc::AF_CAN as libc::sa_family_t);
+	assert!(raw.can_ifindex == 3);
+	assert!(unsafe { raw.can_addr.tp.rx_id } == 0x7e8);
+	assert!(CanSocketAddress::from_raw(raw) == isotp);
+
+	let generic = SocketAddress::from(&isotp);
+	assert!(generic.as_can() == Some(isotp));
+	assert!(let None = SocketAddress::from(&address).as_inet4());
+}
+
+#[test]
+fn test_can_address_finalize() {
+	fn finalize(address: &CanSocketAddress, len: usize) -> std::io::Result<CanSocketAddress> {
+		let mut output = MaybeUninit::<CanSocketAddress>::zeroed();
+		unsafe {
+			std::ptr::copy_nonoverlapping(address.as_sockaddr() as *const u8, CanSocketAddress::as_sockaddr_mut(&mut output) as *mut u8, len);
+		}
+		CanSocketAddress::finalize(output, len as libc::socklen_t)
+	}
+
+	// The kernel reports only the family and interface index for raw CAN sockets.
+	let address = CanSocketAddress::new_isotp(3, 0x7e8, 0x7e0);
+	assert!(finalize(&address, 8).unwrap() == CanSocketAddress::new(3));
+	assert!(finalize(&address, 16).unwrap() == address);
+	assert!(let Err(_) = finalize(&address, 4));
+	assert!(let Err(_) = finalize(&address, 0));
+
+	let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+	storage.ss_family = libc::AF_CAN as libc::sa_family_t;
+	assert!(SocketAddress::from_raw(storage, 8).as_can() == Some(CanSocketAddress::new(0)));
+	assert!(let None = SocketAddress::from_raw(storage, 4).as_can());
+}
+
+#[test]
+fn test_isotp_encoding() {
+	// The option structs must have the layout of the kernel structs.
+	assert!(std::mem::size_of::<IsoTpOptions>() == 12);
+	assert!(std::mem::size_of::<IsoTpFlowControlOptions>() == 3);
+	assert!(std::mem::size_of::<IsoTpLinkLayerOptions>() == 3);
+
+	let options = IsoTpOptions {
+		flags: IsoTpFlags::TX_PADDING | IsoTpFlags::EXTEND_ADDR,
+		frame_txtime: 1000,
+		ext_address: 1,
+		txpad_content: 0xcc,
+		rxpad_content: 0xaa,
+		rx_ext_address: 2,
+	};
+	let raw: [u8; 12] = unsafe { std::mem::transmute(options) };
+	assert!(&raw[..4] == &0x0006u32.to_ne_bytes());
+	assert!(&raw[4..8] == &1000u32.to_ne_bytes());
+	assert!(&raw[8..] == &[1, 0xcc, 0xaa, 2]);
+
+	assert!(options.flags.contains(IsoTpFlags::TX_PADDING));
+	assert!(!options.flags.contains(IsoTpFlags::LISTEN_MODE));
+	assert!(IsoTpFlags::from_bits(0x0006) == options.flags);
+}
+
+#[test]
+fn test_isotp_options() {
+	let socket = match can_socket(libc::SOCK_DGRAM, libc::CAN_ISOTP) {
+		Some(x) => x,
+		None => return,
+	};
+
+	let options = IsoTpOptions {
+		flags: IsoTpFlags::TX_PADDING,
+		txpad_content: 0xcc,
+		..IsoTpOptions::default()
+	};
+	assert!(let Ok(()) = socket.set_isotp_options(&options));
+	assert!(socket.isotp_options().unwrap() == options);
+
+	let flow_control = IsoTpFlowControlOptions { block_size: 8, stmin: 5, wftmax: 0 };
+	assert!(let Ok(()) = socket.set_isotp_flow_control(&flow_control));
+	assert!(socket.isotp_flow_control().unwrap() == flow_control);
+
+	assert!(let Ok(()) = socket.set_isotp_tx_stmin(Duration::from_micros(500)));
+	assert!(socket.isotp_tx_stmin().unwrap() == Duration::from_micros(500));
+	assert!(let Err(_) = socket.set_isotp_tx_stmin(Duration::from_secs(10)));
+}
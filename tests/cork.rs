@@ -0,0 +1,40 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::cork::CorkedWriter;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::io::Write;
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_corked_writer_udp() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.connect(&server.local_addr().unwrap()).unwrap();
+
+	let mut writer = CorkedWriter::new(&client);
+	writer.write_all(b"hello").unwrap();
+	writer.write_all(b" ").unwrap();
+	writer.finish(b"world!").unwrap();
+
+	let mut buffer = [0u8; 32];
+	let len = server.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello world!");
+}
+
+#[test]
+fn test_send_more_empty_finish() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.connect(&server.local_addr().unwrap()).unwrap();
+
+	assert!(let Ok(3) = client.send_more(b"abc", 0));
+	assert!(let Ok(3) = client.send_more(b"def", 0));
+	CorkedWriter::new(&client).finish(b"").unwrap();
+
+	let mut buffer = [0u8; 32];
+	let len = server.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"abcdef");
+}
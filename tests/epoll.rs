@@ -0,0 +1,36 @@
+#![cfg(all(feature = "epoll", target_os = "linux"))]
+
+use assert2::assert;
+use posix_socket::epoll::{Epoll, Events, Interest};
+use posix_socket::UnixSocket;
+use std::time::Duration;
+
+#[test]
+fn test_epoll() {
+	let epoll = Epoll::new().unwrap();
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = epoll.add(&b, 7, Interest::READABLE));
+
+	// Nothing to read yet.
+	let mut events = Events::with_capacity(4);
+	assert!(let Ok(0) = epoll.wait(&mut events, Some(Duration::from_millis(10))));
+	assert!(events.is_empty());
+
+	assert!(let Ok(5) = a.send(b"Hello", 0));
+	assert!(let Ok(1) = epoll.wait(&mut events, Some(Duration::from_secs(1))));
+	let event = events.iter().next().unwrap();
+	assert!(event.token == 7);
+	assert!(event.readiness.is_readable());
+	assert!(!event.readiness.is_writable());
+
+	// Switch the interest to writability.
+	assert!(let Ok(()) = epoll.modify(&b, 8, Interest::WRITABLE));
+	assert!(let Ok(1) = epoll.wait(&mut events, Some(Duration::from_secs(1))));
+	let event = events.iter().next().unwrap();
+	assert!(event.token == 8);
+	assert!(event.readiness.is_writable());
+
+	// No more events after removing the socket.
+	assert!(let Ok(()) = epoll.remove(&b));
+	assert!(let Ok(0) = epoll.wait(&mut events, Some(Duration::ZERO)));
+}
@@ -0,0 +1,50 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::errqueue::{ExtendedError, ExtendedErrorOrigin};
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_origin_raw() {
+	for origin in 0..=255 {
+		assert!(ExtendedErrorOrigin::from_raw(origin).to_raw() == origin);
+	}
+	assert!(ExtendedErrorOrigin::from_raw(2) == ExtendedErrorOrigin::Icmp);
+}
+
+#[test]
+fn test_parse_too_short() {
+	assert!(let Err(_) = ExtendedError::parse(&[0; 8]));
+}
+
+#[test]
+fn test_icmp_port_unreachable() {
+	// Find a closed port by binding a socket and closing it again.
+	let closed = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	closed.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let address = closed.local_addr().unwrap();
+	drop(closed);
+
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_ip_recv_error(true));
+	assert!(let Ok(true) = socket.ip_recv_error());
+	assert!(let Ok(None) = socket.recv_error_queue());
+	socket.send_to(b"hello!", &address, 0).unwrap();
+
+	let error = (0..100)
+		.find_map(|_| {
+			let error = socket.recv_error_queue().unwrap();
+			if error.is_none() {
+				std::thread::sleep(std::time::Duration::from_millis(10));
+			}
+			error
+		})
+		.unwrap();
+	assert!(error.origin() == ExtendedErrorOrigin::Icmp);
+	assert!(error.errno() == libc::ECONNREFUSED);
+	assert!(error.icmp_type() == 3);
+	assert!(error.icmp_code() == 3);
+	let offender = error.offender().unwrap().as_inet4().unwrap();
+	assert!(offender.ip() == Ipv4Addr::LOCALHOST);
+}
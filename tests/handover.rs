@@ -0,0 +1,45 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6SocketAddress, UnixSocket};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_send_recv_listeners() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_SEQPACKET, 0).unwrap();
+
+	let first = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	first.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	first.listen(1).unwrap();
+	let second = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	second.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	second.listen(1).unwrap();
+
+	a.send_listeners(&[&first, &second]).unwrap();
+	let received: Vec<Inet4Socket> = b.recv_listeners(4).unwrap();
+	assert!(received.len() == 2);
+	assert!(received[0].local_addr().unwrap().port() == first.local_addr().unwrap().port());
+	assert!(received[1].local_addr().unwrap().port() == second.local_addr().unwrap().port());
+
+	// The received listener accepts connections for the original socket.
+	drop(first);
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&received[0].local_addr().unwrap()).unwrap();
+	assert!(let Ok(_) = received[0].accept());
+}
+
+#[test]
+fn test_send_non_listener() {
+	let (a, _b) = UnixSocket::pair(libc::SOCK_SEQPACKET, 0).unwrap();
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Err(_) = a.send_listeners(&[&socket]));
+}
+
+#[test]
+fn test_recv_wrong_address_type() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_SEQPACKET, 0).unwrap();
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	listener.listen(1).unwrap();
+
+	a.send_listeners(&[&listener]).unwrap();
+	assert!(let Err(_) = b.recv_listeners::<Inet6SocketAddress>(1));
+}
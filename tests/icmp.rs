@@ -0,0 +1,79 @@
+use assert2::assert;
+use posix_socket::icmp::{self, EchoHeader};
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_echo_header_round_trip() {
+	let header = EchoHeader::request_v4(0x1234, 7);
+	let message = header.encode(b"ping!");
+	assert!(message.len() == EchoHeader::LEN + 5);
+	assert!(icmp::checksum(&message) == 0);
+
+	let (decoded, payload) = EchoHeader::decode(&message).unwrap();
+	assert!(decoded.kind == icmp::ECHO_REQUEST_V4);
+	assert!(decoded.identifier == 0x1234);
+	assert!(decoded.sequence == 7);
+	assert!(payload == b"ping!");
+	assert!(!decoded.is_reply());
+
+	assert!(EchoHeader::from_bytes(&message[..7]).is_none());
+}
+
+#[test]
+fn test_checksum() {
+	// Example from RFC 1071 section 3.
+	let data = [0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7];
+	assert!(icmp::checksum(&data) == !0xddf2);
+}
+
+#[test]
+fn test_ping_localhost() {
+	let socket = match Inet4Socket::new_ping() {
+		Ok(x) => x,
+		// Unprivileged ICMP sockets may be disabled with `ping_group_range`.
+		Err(e) if e.raw_os_error() == Some(libc::EACCES) || e.raw_os_error() == Some(libc::EPERM) => return,
+		Err(e) => panic!("failed to create ping socket: {}", e),
+	};
+
+	let localhost = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0);
+	let request = EchoHeader::request_v4(0, 1).encode(b"hello");
+	socket.send_to(&request, &localhost, 0).unwrap();
+
+	let mut buffer = [0u8; 64];
+	let (_, len) = socket.recv_from(&mut buffer, 0).unwrap();
+	let (header, payload) = EchoHeader::decode(&buffer[..len]).unwrap();
+	assert!(header.is_reply());
+	assert!(header.sequence == 1);
+	assert!(payload == b"hello");
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn test_icmp6_filter() {
+	let filter = icmp::Icmp6Filter::block_all().pass(icmp::ECHO_REPLY_V6);
+	assert!(filter.will_pass(icmp::ECHO_REPLY_V6));
+	assert!(!filter.will_pass(icmp::ECHO_REQUEST_V6));
+	assert!(!filter.will_pass(1));
+
+	let filter = icmp::Icmp6Filter::pass_all().block(135);
+	assert!(!filter.will_pass(135));
+	assert!(filter.will_pass(136));
+	assert!(filter.will_pass(0));
+	assert!(filter.will_pass(255));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn test_icmp6_filter_option() {
+	// Raw sockets require CAP_NET_RAW.
+	let socket = match posix_socket::Inet6Socket::new(libc::SOCK_RAW, libc::IPPROTO_ICMPV6) {
+		Ok(x) => x,
+		Err(e) if e.raw_os_error() == Some(libc::EPERM) || e.raw_os_error() == Some(libc::EACCES) => return,
+		Err(e) => panic!("failed to create raw ICMPv6 socket: {}", e),
+	};
+
+	let filter = icmp::Icmp6Filter::block_all().pass(icmp::ECHO_REPLY_V6);
+	socket.set_icmp6_filter(&filter).unwrap();
+	assert!(socket.icmp6_filter().unwrap() == filter);
+}
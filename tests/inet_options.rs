@@ -0,0 +1,113 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_broadcast() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(false) = socket.broadcast());
+	assert!(let Ok(()) = socket.set_broadcast(true));
+	assert!(let Ok(true) = socket.broadcast());
+	assert!(let Ok(()) = socket.set_broadcast(false));
+	assert!(let Ok(false) = socket.broadcast());
+}
+
+#[test]
+fn test_reuse_address() {
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(false) = socket.reuse_address());
+	assert!(let Ok(()) = socket.set_reuse_address(true));
+	assert!(let Ok(true) = socket.reuse_address());
+}
+
+#[test]
+fn test_reuse_port() {
+	let a = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let b = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(false) = a.reuse_port());
+	assert!(let Ok(()) = a.set_reuse_port(true));
+	assert!(let Ok(()) = b.set_reuse_port(true));
+	assert!(let Ok(true) = a.reuse_port());
+
+	a.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let address = a.local_addr().unwrap();
+	assert!(let Ok(()) = b.bind(&address));
+
+	let c = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = c.bind(&address));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_bind_device() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(None) = socket.bound_device());
+	assert!(let Err(_) = socket.bind_device(Some("this-name-is-too-long")));
+	assert!(let Err(_) = socket.bind_device(Some("lo\0")));
+
+	// Binding to a device may require privileges, so only check the result if it succeeded.
+	if socket.bind_device(Some("lo")).is_ok() {
+		assert!(socket.bound_device().unwrap().unwrap() == "lo");
+		assert!(let Ok(()) = socket.bind_device(None));
+		assert!(let Ok(None) = socket.bound_device());
+	}
+}
+
+#[test]
+fn test_ipv6_only() {
+	let socket = match posix_socket::Inet6Socket::new(libc::SOCK_STREAM, 0) {
+		Ok(x) => x,
+		Err(e) if e.raw_os_error() == Some(libc::EAFNOSUPPORT) => return,
+		Err(e) => panic!("failed to create IPv6 socket: {}", e),
+	};
+	assert!(let Ok(()) = socket.set_ipv6_only(true));
+	assert!(let Ok(true) = socket.ipv6_only());
+	assert!(let Ok(()) = socket.set_ipv6_only(false));
+	assert!(let Ok(false) = socket.ipv6_only());
+}
+
+#[test]
+fn test_ttl_and_tos_v4() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_ttl_v4(12));
+	assert!(let Ok(12) = socket.ttl_v4());
+	assert!(let Ok(()) = socket.set_tos_v4(0xb8));
+	assert!(let Ok(0xb8) = socket.tos_v4());
+}
+
+#[test]
+fn test_hops_and_traffic_class_v6() {
+	let socket = match posix_socket::Inet6Socket::new(libc::SOCK_DGRAM, 0) {
+		Ok(x) => x,
+		Err(e) if e.raw_os_error() == Some(libc::EAFNOSUPPORT) => return,
+		Err(e) => panic!("failed to create IPv6 socket: {}", e),
+	};
+	assert!(let Ok(()) = socket.set_unicast_hops_v6(7));
+	assert!(let Ok(7) = socket.unicast_hops_v6());
+	assert!(let Ok(()) = socket.set_traffic_class_v6(0x20));
+	assert!(let Ok(0x20) = socket.traffic_class_v6());
+}
+
+#[test]
+fn test_buffer_size() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_send_buffer_size(8 * 1024));
+	assert!(socket.send_buffer_size().unwrap() >= 8 * 1024);
+	assert!(let Ok(()) = socket.set_recv_buffer_size(8 * 1024));
+	assert!(socket.recv_buffer_size().unwrap() >= 8 * 1024);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_buffer_size_force() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	match socket.set_recv_buffer_size_force(64 * 1024) {
+		Ok(()) => assert!(socket.recv_buffer_size().unwrap() == 2 * 64 * 1024),
+		// Setting the option requires CAP_NET_ADMIN.
+		Err(e) => assert!(e.raw_os_error() == Some(libc::EPERM)),
+	}
+	match socket.set_send_buffer_size_force(64 * 1024) {
+		Ok(()) => assert!(socket.send_buffer_size().unwrap() == 2 * 64 * 1024),
+		Err(e) => assert!(e.raw_os_error() == Some(libc::EPERM)),
+	}
+}
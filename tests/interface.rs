@@ -0,0 +1,20 @@
+use assert2::assert;
+use posix_socket::interface;
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_loopback() {
+	let index = interface::name_to_index("lo").unwrap();
+	assert!(index != 0);
+	assert!(let Ok("lo") = interface::index_to_name(index).as_deref());
+
+	let interfaces = interface::list().unwrap();
+	assert!(interfaces.iter().any(|x| x.index == index && x.name == "lo"));
+}
+
+#[test]
+fn test_unknown_interface() {
+	assert!(let Err(_) = interface::name_to_index("no-such-interface"));
+	assert!(let Err(_) = interface::name_to_index("nul\0byte"));
+	assert!(let Err(_) = interface::index_to_name(0));
+}
@@ -0,0 +1,32 @@
+use assert2::assert;
+use posix_socket::UnixSocket;
+use std::os::unix::io::{AsFd, AsRawFd, OwnedFd};
+
+#[test]
+fn test_owned_fd() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let raw = a.as_raw_fd();
+	assert!(a.as_fd().as_raw_fd() == raw);
+
+	let fd = OwnedFd::from(a);
+	assert!(fd.as_raw_fd() == raw);
+	let a = UnixSocket::from(fd);
+	assert!(a.as_raw_fd() == raw);
+
+	assert!(let Ok(6) = a.send(b"hello!", 0));
+	let mut buffer = [0; 16];
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+}
+
+#[test]
+fn test_from_borrowed_fd() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let c = UnixSocket::from_borrowed_fd(a.as_fd()).unwrap();
+	assert!(c.as_raw_fd() != a.as_raw_fd());
+	assert!(unsafe { libc::fcntl(c.as_raw_fd(), libc::F_GETFD) } & libc::FD_CLOEXEC != 0);
+
+	drop(a);
+	assert!(let Ok(6) = c.send(b"hello!", 0));
+	let mut buffer = [0; 16];
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+}
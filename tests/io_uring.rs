@@ -0,0 +1,103 @@
+#![cfg(all(feature = "io-uring", target_os = "linux"))]
+
+use assert2::assert;
+use io_uring::{cqueue, IoUring};
+use posix_socket::io_uring::{Accept, Connect, ProvidedBuffers, Recv, RecvMsg, Send, SendMsg};
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+/// Create an io_uring instance, or `None` if io_uring is not available.
+fn ring() -> Option<IoUring> {
+	match IoUring::new(8) {
+		Ok(ring) => Some(ring),
+		Err(e) if matches!(e.raw_os_error(), Some(libc::ENOSYS) | Some(libc::EPERM)) => None,
+		Err(e) => panic!("failed to create io_uring: {}", e),
+	}
+}
+
+/// Submit an entry and wait for its completion.
+fn run(ring: &mut IoUring, entry: io_uring::squeue::Entry) -> cqueue::Entry {
+	unsafe {
+		ring.submission().push(&entry).unwrap();
+	}
+	ring.submit_and_wait(1).unwrap();
+	ring.completion().next().unwrap()
+}
+
+#[test]
+fn test_accept_connect_send_recv() {
+	let mut ring = match ring() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	listener.listen(1).unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	let mut connect = Connect::new(listener.local_addr().unwrap());
+	let result = run(&mut ring, connect.entry(&client)).result();
+	assert!(let Ok(()) = connect.finish(result));
+
+	let mut accept = Accept::new();
+	let result = run(&mut ring, accept.entry(&listener)).result();
+	let (server, address) = accept.finish(result).unwrap();
+	assert!(address.port() == client.local_addr().unwrap().port());
+	assert!(unsafe { libc::fcntl(server.as_raw_fd(), libc::F_GETFD) } & libc::FD_CLOEXEC != 0);
+
+	let mut send = Send::new(b"hello!".to_vec(), 0);
+	let result = run(&mut ring, send.entry(&client)).result();
+	let (_data, len) = send.finish(result);
+	assert!(let Ok(6) = len);
+
+	let mut recv = Recv::new(vec![0; 16], 0);
+	let result = run(&mut ring, recv.entry(&server)).result();
+	assert!(let Ok(b"hello!") = recv.finish(result).as_deref());
+}
+
+#[test]
+fn test_send_msg_recv_msg() {
+	let mut ring = match ring() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let a = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let b = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	a.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	b.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+
+	let mut send = SendMsg::new_to(b.local_addr().unwrap(), b"hello!".to_vec(), 0);
+	let result = run(&mut ring, send.entry(&a)).result();
+	assert!(let (_, Ok(6)) = send.finish(result));
+
+	let mut recv = RecvMsg::new(vec![0; 16], Vec::new(), 0);
+	let result = run(&mut ring, recv.entry(&b)).result();
+	let message = recv.finish(result).unwrap();
+	assert!(message.data == b"hello!");
+	assert!(message.address.map(|x| x.port()) == Some(a.local_addr().unwrap().port()));
+}
+
+#[test]
+fn test_provided_buffers() {
+	let mut ring = match ring() {
+		Some(x) => x,
+		None => return,
+	};
+
+	let (a, b) = posix_socket::UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let mut buffers = ProvidedBuffers::new(7, 4, 16);
+	assert!(buffers.count() == 4);
+	let result = run(&mut ring, buffers.provide_entry()).result();
+	assert!(result >= 0);
+
+	a.send(b"hello!", 0).unwrap();
+	let completion = run(&mut ring, posix_socket::io_uring::recv_select(&b, &buffers, 0));
+	assert!(completion.result() == 6);
+	let buffer_id = cqueue::buffer_select(completion.flags()).unwrap();
+	assert!(buffers.buffer(buffer_id, 6) == b"hello!");
+
+	let result = run(&mut ring, buffers.reprovide_entry(buffer_id)).result();
+	assert!(result >= 0);
+}
@@ -0,0 +1,43 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ioctl::InterfaceFlags;
+use posix_socket::Inet4Socket;
+
+#[test]
+fn test_loopback_interface() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(socket.interface_mtu("lo").unwrap() > 0);
+	assert!(socket.interface_index("lo").unwrap() == posix_socket::interface::name_to_index("lo").unwrap());
+
+	let flags = socket.interface_flags("lo").unwrap();
+	assert!(flags.contains(InterfaceFlags::LOOPBACK));
+
+	let address = socket.interface_hardware_address("lo").unwrap();
+	assert!(address.kind == libc::ARPHRD_LOOPBACK);
+	assert!(address.bytes == [0; 6]);
+}
+
+#[test]
+fn test_invalid_interface() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = socket.interface_mtu("no-such-interface"));
+	assert!(let Err(_) = socket.interface_mtu("interface-name-too-long"));
+	assert!(let Err(_) = socket.interface_flags("nul\0byte"));
+}
+
+#[test]
+fn test_queue_len() {
+	let (a, b) = posix_socket::UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	assert!(a.output_queue_len().unwrap() == 0);
+	assert!(b.input_queue_len().unwrap() == 0);
+
+	a.send(b"hello", 0).unwrap();
+	assert!(a.output_queue_len().unwrap() > 0);
+	assert!(b.input_queue_len().unwrap() == 5);
+
+	let mut buffer = [0u8; 16];
+	assert!(b.recv(&mut buffer, 0).unwrap() == 5);
+	assert!(a.output_queue_len().unwrap() == 0);
+	assert!(b.input_queue_len().unwrap() == 0);
+}
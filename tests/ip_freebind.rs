@@ -0,0 +1,27 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_ip_freebind() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(false) = socket.ip_freebind());
+	assert!(let Ok(()) = socket.set_ip_freebind(true));
+	assert!(let Ok(true) = socket.ip_freebind());
+
+	// 192.0.2.1 is reserved for documentation and should not be configured on the test system.
+	assert!(let Ok(()) = socket.bind(&Inet4SocketAddress::new(&Ipv4Addr::new(192, 0, 2, 1), 0)));
+}
+
+#[test]
+fn test_ip_transparent() {
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(false) = socket.ip_transparent());
+	match socket.set_ip_transparent(true) {
+		Ok(()) => assert!(let Ok(true) = socket.ip_transparent()),
+		// Setting the option requires CAP_NET_ADMIN or CAP_NET_RAW.
+		Err(e) => assert!(e.raw_os_error() == Some(libc::EPERM)),
+	}
+}
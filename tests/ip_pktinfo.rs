@@ -0,0 +1,111 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+use posix_socket::ip::{Ipv4PacketInfo, Ipv6PacketInfo};
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket, Inet6SocketAddress};
+use std::io::{IoSlice, IoSliceMut};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_recv_ipv4_pktinfo() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+	assert!(let Ok(()) = server.set_ip_recv_pktinfo(true));
+	assert!(let Ok(true) = server.ip_recv_pktinfo());
+	let port = server.local_addr().unwrap().port();
+
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.send_to(b"hello!", &Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, port), 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = server.recv_msg_from(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello!");
+
+	let info = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::Ipv4PacketInfo(x)) => Some(x),
+			_ => None,
+		})
+		.unwrap();
+	assert!(info.local_address == Ipv4Addr::LOCALHOST);
+	assert!(info.destination == Ipv4Addr::LOCALHOST);
+	assert!(info.interface_index != 0);
+}
+
+#[test]
+fn test_recv_ipv6_pktinfo() {
+	let server = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet6SocketAddress::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0)).unwrap();
+	assert!(let Ok(()) = server.set_ipv6_recv_pktinfo(true));
+	assert!(let Ok(true) = server.ipv6_recv_pktinfo());
+	let port = server.local_addr().unwrap().port();
+
+	let client = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.send_to(b"hello!", &Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, port, 0, 0), 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = server.recv_msg_from(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello!");
+
+	let info = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::Ipv6PacketInfo(x)) => Some(x),
+			_ => None,
+		})
+		.unwrap();
+	assert!(info.address == Ipv6Addr::LOCALHOST);
+	assert!(info.interface_index != 0);
+}
+
+#[test]
+fn test_send_ipv4_pktinfo() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let server_address = server.local_addr().unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.bind(&Inet4SocketAddress::new(&Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+
+	// Send from 127.0.0.2 instead of the default source address 127.0.0.1.
+	let source = Ipv4Addr::new(127, 0, 0, 2);
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	assert!(ancillary.add_ipv4_packet_info(&Ipv4PacketInfo::new(0, source)));
+	assert!(let Ok(6) = client.send_msg_to(&server_address, &[IoSlice::new(b"hello!")], Some(ancillary.data()), 0));
+
+	let mut buffer = [0u8; 16];
+	let (sender, len) = server.recv_from(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello!");
+	assert!(sender.unwrap().ip() == source);
+}
+
+#[test]
+fn test_send_ipv6_pktinfo() {
+	let server = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 0, 0, 0)).unwrap();
+	let server_address = server.local_addr().unwrap();
+
+	let client = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	assert!(ancillary.add_ipv6_packet_info(&Ipv6PacketInfo::new(0, Ipv6Addr::LOCALHOST)));
+	assert!(let Ok(6) = client.send_msg_to(&server_address, &[IoSlice::new(b"hello!")], Some(ancillary.data()), 0));
+
+	let mut buffer = [0u8; 16];
+	let (sender, len) = server.recv_from(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello!");
+	assert!(sender.unwrap().ip() == Ipv6Addr::LOCALHOST);
+}
+
+#[test]
+fn test_add_packet_info_no_space() {
+	let mut cdata = [0u8; 8];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	assert!(!ancillary.add_ipv4_packet_info(&Ipv4PacketInfo::new(0, Ipv4Addr::LOCALHOST)));
+	assert!(ancillary.len() == 0);
+}
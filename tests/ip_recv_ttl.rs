@@ -0,0 +1,73 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket, Inet6SocketAddress};
+use std::io::IoSliceMut;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_recv_ipv4_tos_ttl() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Ok(()) = server.set_ip_recv_tos(true));
+	assert!(let Ok(true) = server.ip_recv_tos());
+	assert!(let Ok(()) = server.set_ip_recv_ttl(true));
+	assert!(let Ok(true) = server.ip_recv_ttl());
+
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.set_ttl_v4(42).unwrap();
+	client.set_tos_v4(0x20).unwrap();
+	client.send_to(b"hello!", &server.local_addr().unwrap(), 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = server.recv_msg_from(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello!");
+
+	let mut tos = None;
+	let mut ttl = None;
+	for message in ancillary.messages() {
+		match message {
+			Ok(AncillaryData::Ipv4Tos(x)) => tos = Some(x),
+			Ok(AncillaryData::Ipv4Ttl(x)) => ttl = Some(x),
+			_ => (),
+		}
+	}
+	assert!(tos == Some(0x20));
+	assert!(ttl == Some(42));
+}
+
+#[test]
+fn test_recv_ipv6_tclass_hoplimit() {
+	let server = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet6SocketAddress::new(Ipv6Addr::LOCALHOST, 0, 0, 0)).unwrap();
+	assert!(let Ok(()) = server.set_ipv6_recv_tclass(true));
+	assert!(let Ok(true) = server.ipv6_recv_tclass());
+	assert!(let Ok(()) = server.set_ipv6_recv_hoplimit(true));
+	assert!(let Ok(true) = server.ipv6_recv_hoplimit());
+
+	let client = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.set_unicast_hops_v6(17).unwrap();
+	client.set_traffic_class_v6(0x20).unwrap();
+	client.send_to(b"hello!", &server.local_addr().unwrap(), 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = server.recv_msg_from(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello!");
+
+	let mut traffic_class = None;
+	let mut hop_limit = None;
+	for message in ancillary.messages() {
+		match message {
+			Ok(AncillaryData::Ipv6TrafficClass(x)) => traffic_class = Some(x),
+			Ok(AncillaryData::Ipv6HopLimit(x)) => hop_limit = Some(x),
+			_ => (),
+		}
+	}
+	assert!(traffic_class == Some(0x20));
+	assert!(hop_limit == Some(17));
+}
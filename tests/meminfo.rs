@@ -0,0 +1,18 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::UnixSocket;
+
+#[test]
+fn test_memory_info() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	b.set_recv_buffer_size(16 * 1024).unwrap();
+
+	let info = b.memory_info().unwrap();
+	assert!(info.rcvbuf as usize == b.recv_buffer_size().unwrap());
+	assert!(a.memory_info().unwrap().wmem_alloc == 0);
+
+	// Unix sockets charge queued datagrams to the sender until they are received.
+	a.send(b"hello", 0).unwrap();
+	assert!(a.memory_info().unwrap().wmem_alloc > 0);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "mio")]
+
+use assert2::assert;
+use mio::{Events, Interest, Poll, Token};
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket, UnixSocket};
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+#[test]
+fn test_register_and_poll() {
+	let (a, mut b) = UnixSocket::pair_nonblocking(libc::SOCK_STREAM, 0).unwrap();
+	let e = b.recv(&mut [0; 8], 0).unwrap_err();
+	assert!(e.kind() == std::io::ErrorKind::WouldBlock);
+
+	let mut poll = Poll::new().unwrap();
+	poll.registry().register(&mut b, Token(7), Interest::READABLE).unwrap();
+
+	a.send(b"hello!", 0).unwrap();
+	let mut events = Events::with_capacity(4);
+	poll.poll(&mut events, Some(Duration::from_secs(5))).unwrap();
+	let event = events.iter().next().unwrap();
+	assert!(event.token() == Token(7));
+	assert!(event.is_readable());
+
+	poll.registry().deregister(&mut b).unwrap();
+}
+
+#[test]
+fn test_convert_tcp() {
+	use std::convert::TryFrom;
+
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Err(_) = mio::net::TcpListener::try_from(listener.try_clone().unwrap()));
+	listener.listen(1).unwrap();
+	let listener = mio::net::TcpListener::try_from(listener).unwrap();
+	let address = listener.local_addr().unwrap();
+
+	let udp = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = mio::net::TcpStream::try_from(udp));
+	let inet6 = Inet6Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Err(_) = Inet4Socket::try_from(mio::net::TcpStream::try_from(inet6).unwrap()));
+
+	let listener = Inet4Socket::try_from(listener).unwrap();
+	assert!(listener.local_addr().unwrap().port() == address.port());
+}
+
+#[test]
+fn test_convert_unix() {
+	use std::convert::TryFrom;
+
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = mio::net::UnixStream::try_from(a.try_clone().unwrap()));
+	let a = mio::net::UnixDatagram::try_from(a).unwrap();
+	a.send(b"hello!").unwrap();
+
+	let b = UnixSocket::from(mio::net::UnixDatagram::try_from(b).unwrap());
+	let mut buffer = [0; 16];
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+}
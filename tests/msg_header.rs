@@ -0,0 +1,35 @@
+use assert2::assert;
+use posix_socket::ancillary::SocketAncillary;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, MsgHeader, MsgHeaderMut, SocketAddress};
+use std::io::{IoSlice, IoSliceMut};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_send_recv_prepared() {
+	let server = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	server.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let client = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	client.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+
+	// The destination can be given as a generic address.
+	let destination = SocketAddress::from_other(&server.local_addr().unwrap());
+	let data = [IoSlice::new(b"hello "), IoSlice::new(b"world!")];
+	let header = MsgHeader::new(&data).name(&destination);
+	assert!(let Ok(12) = client.send_prepared(&header));
+
+	let mut buffer = [0u8; 8];
+	{
+		let mut cdata = [0u8; 64];
+		let mut ancillary = SocketAncillary::new(&mut cdata);
+		let mut data = [IoSliceMut::new(&mut buffer)];
+		let mut header = MsgHeaderMut::new(&mut data).name().control(&mut ancillary);
+		assert!(let Ok(8) = server.recv_prepared(&mut header));
+		assert!(header.received_flags() & libc::MSG_TRUNC != 0);
+
+		let sender = header.received_name().unwrap().as_inet4().unwrap();
+		assert!(sender.port() == client.local_addr().unwrap().port());
+		assert!(header.received_name().unwrap().family() == libc::AF_INET);
+		assert!(!ancillary.truncated());
+	}
+	assert!(&buffer == b"hello wo");
+}
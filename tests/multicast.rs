@@ -0,0 +1,48 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_multicast_options_v4() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_multicast_loop_v4(false));
+	assert!(let Ok(false) = socket.multicast_loop_v4());
+	assert!(let Ok(()) = socket.set_multicast_ttl_v4(8));
+	assert!(let Ok(8) = socket.multicast_ttl_v4());
+	assert!(let Ok(()) = socket.set_multicast_interface_v4(&Ipv4Addr::LOCALHOST));
+	assert!(socket.multicast_interface_v4().unwrap() == Ipv4Addr::LOCALHOST);
+}
+
+#[test]
+fn test_multicast_options_v6() {
+	let socket = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_multicast_loop_v6(false));
+	assert!(let Ok(false) = socket.multicast_loop_v6());
+	assert!(let Ok(()) = socket.set_multicast_hops_v6(8));
+	assert!(let Ok(8) = socket.multicast_hops_v6());
+	assert!(let Ok(()) = socket.set_multicast_interface_v6(0));
+	assert!(let Ok(0) = socket.multicast_interface_v6());
+}
+
+#[test]
+fn test_join_leave_v4() {
+	let group = Ipv4Addr::new(239, 255, 12, 34);
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	socket.bind(&Inet4SocketAddress::new(&Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+	assert!(let Ok(()) = socket.join_multicast_v4(&group, &Ipv4Addr::LOCALHOST));
+	assert!(let Ok(()) = socket.leave_multicast_v4(&group, &Ipv4Addr::LOCALHOST));
+	assert!(let Err(_) = socket.leave_multicast_v4(&group, &Ipv4Addr::LOCALHOST));
+	assert!(let Err(_) = socket.join_multicast_v4(&Ipv4Addr::LOCALHOST, &Ipv4Addr::LOCALHOST));
+}
+
+#[test]
+fn test_join_leave_v6() {
+	let group: Ipv6Addr = "ff12::1234".parse().unwrap();
+	let socket = Inet6Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = socket.join_multicast_v6(&Ipv6Addr::LOCALHOST, 0));
+	if socket.join_multicast_v6(&group, 1).is_ok() {
+		assert!(let Ok(()) = socket.leave_multicast_v6(&group, 1));
+	}
+}
@@ -0,0 +1,24 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::pfkey::{SadbMsg, PF_KEY_V2, SADB_DUMP, SADB_SATYPE_ESP};
+
+#[test]
+fn test_sadb_msg_round_trip() {
+	let header = SadbMsg::new(SADB_DUMP, SADB_SATYPE_ESP, 7, 1234);
+	assert!(header.version == PF_KEY_V2 as u8);
+	assert!(header.message_len() == SadbMsg::SIZE);
+
+	let bytes = header.to_bytes();
+	assert!(SadbMsg::parse(&bytes).unwrap() == header);
+}
+
+#[test]
+fn test_sadb_msg_invalid_length() {
+	let mut header = SadbMsg::new(SADB_DUMP, SADB_SATYPE_ESP, 7, 1234);
+	assert!(let Err(_) = SadbMsg::parse(&header.to_bytes()[..8]));
+	assert!(let Err(_) = header.set_message_len(20));
+
+	header.set_message_len(24).unwrap();
+	assert!(let Err(_) = SadbMsg::parse(&header.to_bytes()));
+}
@@ -0,0 +1,39 @@
+use assert2::assert;
+use posix_socket::poll::{poll_many, PollEntry, PollFlags};
+use posix_socket::UnixSocket;
+use std::time::Duration;
+
+#[test]
+fn test_poll_many() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let (c, d) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+
+	// Nothing to read yet.
+	let mut entries = [
+		PollEntry::new(&b, PollFlags::READABLE),
+		PollEntry::new(&d, PollFlags::READABLE),
+	];
+	assert!(let Ok(0) = poll_many(&mut entries, Some(Duration::from_millis(10))));
+	assert!(entries[0].revents().is_empty());
+	assert!(entries[1].revents().is_empty());
+
+	// Only the second socket becomes readable.
+	c.send(b"hello", 0).unwrap();
+	assert!(let Ok(1) = poll_many(&mut entries, Some(Duration::from_secs(1))));
+	assert!(entries[0].revents().is_empty());
+	assert!(entries[1].revents().is_readable());
+	assert!(entries[1].fd() == std::os::unix::io::AsRawFd::as_raw_fd(&d));
+
+	// Both sockets are writable.
+	entries[0].set_events(PollFlags::WRITABLE);
+	entries[1].set_events(PollFlags::WRITABLE);
+	assert!(let Ok(2) = poll_many(&mut entries, None));
+	assert!(entries[0].revents() == PollFlags::WRITABLE);
+	assert!(entries[1].revents() == PollFlags::WRITABLE);
+
+	// Closing the peer hangs up the socket.
+	drop(a);
+	let mut entries = [PollEntry::new(&b, PollFlags::READABLE)];
+	assert!(let Ok(1) = poll_many(&mut entries, Some(Duration::from_secs(1))));
+	assert!(entries[0].revents().contains(PollFlags::HANGUP));
+}
@@ -0,0 +1,69 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::{AsSocketAddress, PppoeSocketAddress, SocketAddress};
+use std::mem::MaybeUninit;
+
+const REMOTE: [u8; 6] = [0x02, 0x00, 0x5e, 0x10, 0x20, 0x30];
+
+fn address_bytes(address: &PppoeSocketAddress) -> &[u8] {
+	unsafe { std::slice::from_raw_parts(address.as_sockaddr() as *const u8, address.len() as usize) }
+}
+
+#[test]
+fn test_address_accessors() {
+	let mut address = PppoeSocketAddress::new(0x1234, REMOTE, "eth0").unwrap();
+	assert!(address.session_id() == 0x1234);
+	assert!(address.remote() == REMOTE);
+	assert!(address.device() == "eth0");
+	assert!(address.family() == libc::AF_PPPOX as libc::sa_family_t);
+
+	address.set_session_id(7);
+	address.set_remote([1, 2, 3, 4, 5, 6]);
+	assert!(address.session_id() == 7);
+	assert!(address.remote() == [1, 2, 3, 4, 5, 6]);
+	assert!(address != PppoeSocketAddress::new(7, REMOTE, "eth0").unwrap());
+
+	assert!(let Err(_) = PppoeSocketAddress::new(1, REMOTE, "an-interface-name"));
+	assert!(let Err(_) = PppoeSocketAddress::new(1, REMOTE, "eth\00"));
+}
+
+#[test]
+fn test_address_encoding() {
+	let address = PppoeSocketAddress::new(0x1234, REMOTE, "eth0").unwrap();
+
+	// The kernel struct is packed: family, protocol, session ID, remote MAC and interface name.
+	let bytes = address_bytes(&address);
+	assert!(bytes.len() == 2 + 4 + 2 + 6 + libc::IFNAMSIZ);
+	assert!(&bytes[0..2] == &(libc::AF_PPPOX as libc::sa_family_t).to_ne_bytes());
+	assert!(&bytes[2..6] == &(PppoeSocketAddress::PROTOCOL as u32).to_ne_bytes());
+	assert!(&bytes[6..8] == &[0x12, 0x34]);
+	assert!(&bytes[8..14] == &REMOTE);
+	assert!(&bytes[14..18] == b"eth0");
+	assert!(bytes[18..].iter().all(|&x| x == 0));
+}
+
+#[test]
+fn test_address_round_trip() {
+	fn finalize(bytes: &[u8]) -> std::io::Result<PppoeSocketAddress> {
+		let mut output = MaybeUninit::<PppoeSocketAddress>::zeroed();
+		unsafe {
+			std::ptr::copy_nonoverlapping(bytes.as_ptr(), PppoeSocketAddress::as_sockaddr_mut(&mut output) as *mut u8, bytes.len());
+		}
+		PppoeSocketAddress::finalize(output, bytes.len() as libc::socklen_t)
+	}
+
+	let address = PppoeSocketAddress::new(0x1234, REMOTE, "eth0").unwrap();
+	let bytes = address_bytes(&address).to_vec();
+	assert!(finalize(&bytes).unwrap() == address);
+	assert!(let Err(_) = finalize(&bytes[..bytes.len() - 1]));
+
+	let mut wrong_family = bytes.clone();
+	wrong_family[0..2].copy_from_slice(&(libc::AF_INET as libc::sa_family_t).to_ne_bytes());
+	assert!(let Err(_) = finalize(&wrong_family));
+
+	let generic = SocketAddress::from(&address);
+	assert!(generic.family() == libc::AF_PPPOX);
+	assert!(generic.len() == address.len());
+	assert!(generic == SocketAddress::from(address.clone()));
+}
@@ -0,0 +1,44 @@
+use assert2::assert;
+use posix_socket::resolve::{resolve, Hints, ResolveError};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_resolve_numeric() {
+	let hints = Hints::new()
+		.family(libc::AF_INET)
+		.kind(libc::SOCK_STREAM)
+		.flags(libc::AI_NUMERICHOST | libc::AI_NUMERICSERV);
+	let results: Vec<_> = resolve(Some("127.0.0.1"), Some("80"), &hints).unwrap().collect();
+	assert!(results.len() == 1);
+	assert!(results[0].family == libc::AF_INET);
+	assert!(results[0].kind == libc::SOCK_STREAM);
+	let address = results[0].address.as_inet4().unwrap();
+	assert!(address.ip() == Ipv4Addr::LOCALHOST);
+	assert!(address.port() == 80);
+}
+
+#[test]
+fn test_resolve_error() {
+	let hints = Hints::new().flags(libc::AI_NUMERICHOST);
+	let error = resolve(Some("not an address"), None, &hints).err().unwrap();
+	assert!(let ResolveError::Resolver(_) = error);
+	assert!(!error.to_string().is_empty());
+
+	assert!(let Some(ResolveError::NullByte) = resolve(Some("a\0b"), None, &hints).err());
+}
+
+#[test]
+fn test_name_info_numeric() {
+	use posix_socket::resolve::name_info;
+	use posix_socket::{Inet4SocketAddress, Inet6SocketAddress};
+
+	let address = Inet4SocketAddress::new(&Ipv4Addr::new(192, 0, 2, 1), 8080);
+	let (host, service) = name_info(&address, libc::NI_NUMERICHOST | libc::NI_NUMERICSERV).unwrap();
+	assert!(host == "192.0.2.1");
+	assert!(service == "8080");
+
+	let address = Inet6SocketAddress::new(std::net::Ipv6Addr::LOCALHOST, 443, 0, 0);
+	let (host, service) = name_info(&address, libc::NI_NUMERICHOST | libc::NI_NUMERICSERV).unwrap();
+	assert!(host == "::1");
+	assert!(service == "443");
+}
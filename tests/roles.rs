@@ -0,0 +1,61 @@
+use assert2::assert;
+use posix_socket::{DatagramSocket, Inet4SocketAddress, Listener, StreamSocket};
+use std::io::{Read, Write};
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_listener_and_stream() {
+	let listener = Listener::bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0), 1).unwrap();
+	let mut client = StreamSocket::connect(&listener.local_addr().unwrap()).unwrap();
+	let (mut server, peer) = listener.accept().unwrap();
+	assert!(peer == client.local_addr().unwrap());
+
+	client.write_all(b"hello!").unwrap();
+	let mut buffer = [0; 6];
+	server.read_exact(&mut buffer).unwrap();
+	assert!(&buffer == b"hello!");
+}
+
+#[test]
+fn test_datagram() {
+	let a = DatagramSocket::bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let b = DatagramSocket::bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Ok(6) = a.send_to(b"hello!", &b.local_addr().unwrap(), 0));
+
+	let mut buffer = [0; 16];
+	let (sender, len) = b.recv_from(&mut buffer, 0).unwrap();
+	assert!(sender == Some(a.local_addr().unwrap()));
+	assert!(&buffer[..len] == b"hello!");
+}
+
+#[test]
+fn test_dual_stack_listener() {
+	use posix_socket::Inet6SocketAddress;
+
+	let listener = match Listener::<Inet6SocketAddress>::bind_dual_stack(0, 1) {
+		Ok(x) => x,
+		Err(e) if e.raw_os_error() == Some(libc::EAFNOSUPPORT) => return,
+		Err(e) => panic!("failed to create dual-stack listener: {}", e),
+	};
+	assert!(let Ok(false) = listener.as_socket().ipv6_only());
+
+	let port = listener.local_addr().unwrap().port();
+	let client = StreamSocket::connect(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, port)).unwrap();
+	let (_server, peer) = listener.accept().unwrap();
+	assert!(peer.ip().to_ipv4() == Some(Ipv4Addr::LOCALHOST));
+	assert!(peer.port() == client.local_addr().unwrap().port());
+}
+
+#[test]
+fn test_connect_error_context() {
+	let listener = Listener::bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0), 1).unwrap();
+	let address = listener.local_addr().unwrap();
+	drop(listener);
+
+	let error = StreamSocket::connect(&address).err().unwrap();
+	assert!(error.kind() == std::io::ErrorKind::ConnectionRefused);
+	let context = posix_socket::Error::get(&error).unwrap();
+	assert!(context.operation() == posix_socket::Operation::Connect);
+	assert!(context.address().unwrap().as_inet4() == Some(address));
+	assert!(posix_socket::Error::raw_os_error_of(&error) == Some(libc::ECONNREFUSED));
+}
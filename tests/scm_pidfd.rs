@@ -0,0 +1,61 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ancillary::SocketAncillary;
+use posix_socket::credentials::SCM_PIDFD;
+use posix_socket::UnixSocket;
+use std::io::{IoSlice, IoSliceMut};
+use std::os::unix::io::RawFd;
+
+/// Get the file descriptor from the `SCM_PIDFD` message in the control data, without taking ownership.
+fn raw_pidfd(ancillary: &SocketAncillary) -> RawFd {
+	unsafe {
+		let mut header = std::mem::zeroed::<libc::msghdr>();
+		header.msg_control = ancillary.data().as_ptr() as *mut libc::c_void;
+		header.msg_controllen = ancillary.len() as _;
+		let mut cmsg = libc::CMSG_FIRSTHDR(&header);
+		while let Some(current) = cmsg.as_ref() {
+			if current.cmsg_level == libc::SOL_SOCKET && current.cmsg_type == SCM_PIDFD {
+				return std::ptr::read_unaligned(libc::CMSG_DATA(current) as *const RawFd);
+			}
+			cmsg = libc::CMSG_NXTHDR(&header, current);
+		}
+		panic!("no SCM_PIDFD message in control data");
+	}
+}
+
+fn is_open(fd: RawFd) -> bool {
+	unsafe { libc::fcntl(fd, libc::F_GETFD) != -1 }
+}
+
+// This is the only test in this file, so no other thread can take over a closed file descriptor number.
+#[test]
+fn test_pidfd_closed_on_reuse() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	match b.set_pass_pidfd(true) {
+		Ok(()) => (),
+		// Not supported before Linux 6.5.
+		Err(e) if e.raw_os_error() == Some(libc::ENOPROTOOPT) => return,
+		Err(e) => panic!("failed to enable SO_PASSPIDFD: {}", e),
+	}
+
+	let mut buffer = [0u8; 1];
+	let mut cdata = [0u64; 8];
+	let cdata: &mut [u8] = unsafe { std::slice::from_raw_parts_mut(cdata.as_mut_ptr() as *mut u8, std::mem::size_of_val(&cdata)) };
+	let mut ancillary = SocketAncillary::new(cdata);
+
+	a.send_msg(&[IoSlice::new(b"a")], None, 0).unwrap();
+	b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	let first = raw_pidfd(&ancillary);
+	assert!(is_open(first));
+
+	// Receiving into the same buffer closes the unclaimed pidfd, so the kernel hands out the same number again.
+	a.send_msg(&[IoSlice::new(b"b")], None, 0).unwrap();
+	b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	let second = raw_pidfd(&ancillary);
+	assert!(second == first);
+	assert!(is_open(second));
+
+	drop(ancillary);
+	assert!(!is_open(second));
+}
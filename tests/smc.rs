@@ -0,0 +1,11 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet6Socket};
+
+#[test]
+fn test_smc_or_tcp() {
+	// Whether or not the kernel supports SMC, we should get a usable stream socket.
+	assert!(let Ok(_) = Inet4Socket::new_smc_or_tcp());
+	assert!(let Ok(_) = Inet6Socket::new_smc_or_tcp());
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "socket2")]
+
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket, Inet6SocketAddress, SocketAddress, UnixSocket};
+use std::convert::TryFrom;
+use std::net::Ipv4Addr;
+
+#[test]
+fn test_convert_socket() {
+	let socket = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	socket.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	let address = socket.local_addr().unwrap();
+
+	let socket = socket2::Socket::from(socket);
+	assert!(socket.local_addr().unwrap().as_socket_ipv4().unwrap().port() == address.port());
+
+	assert!(let Err(_) = Inet6Socket::try_from(socket.try_clone().unwrap()));
+	assert!(let Err(_) = UnixSocket::try_from(socket.try_clone().unwrap()));
+	let socket = Inet4Socket::try_from(socket).unwrap();
+	assert!(socket.local_addr().unwrap().port() == address.port());
+}
+
+#[test]
+fn test_convert_address() {
+	let address = Inet4SocketAddress::new(&Ipv4Addr::new(127, 0, 0, 1), 80);
+	let converted = socket2::SockAddr::from(&address);
+	assert!(converted.as_socket() == Some("127.0.0.1:80".parse().unwrap()));
+	assert!(let Ok(_) = SocketAddress::try_from(&converted));
+	assert!(let Err(_) = Inet6SocketAddress::try_from(&converted));
+	let x = Inet4SocketAddress::try_from(converted).unwrap();
+	assert!(x.ip() == address.ip());
+	assert!(x.port() == address.port());
+}
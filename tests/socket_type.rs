@@ -0,0 +1,72 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Protocol, SocketType, UnixSocket, UnixSocketAddress};
+
+#[test]
+fn test_socket_type_raw() {
+	assert!(SocketType::Stream.as_raw() == libc::SOCK_STREAM);
+	assert!(SocketType::from_raw(libc::SOCK_DGRAM) == Some(SocketType::Datagram));
+	assert!(SocketType::from_raw(-1).is_none());
+	assert!(Protocol::TCP.as_raw() == libc::IPPROTO_TCP);
+	assert!(Protocol::from_raw(libc::IPPROTO_UDP) == Protocol::UDP);
+}
+
+#[test]
+fn test_new_with_typed_arguments() {
+	assert!(let Ok(_) = Inet4Socket::new(SocketType::Stream, Protocol::TCP));
+	assert!(let Ok(_) = Inet4Socket::new(SocketType::Datagram, Protocol::DEFAULT));
+	assert!(let Ok(_) = Inet4Socket::new(libc::SOCK_DGRAM, Protocol::UDP));
+	assert!(let Ok(_) = UnixSocket::pair(SocketType::SeqPacket, 0));
+}
+
+#[test]
+fn test_socket_introspection() {
+	let socket = Inet4Socket::new(SocketType::Stream, Protocol::TCP).unwrap();
+	assert!(socket.domain().unwrap() == libc::AF_INET);
+	assert!(SocketType::from_raw(socket.kind().unwrap()) == Some(SocketType::Stream));
+	#[cfg(target_os = "linux")]
+	assert!(socket.protocol().unwrap() == libc::IPPROTO_TCP);
+
+	let (a, _b) = UnixSocket::pair(SocketType::SeqPacket, 0).unwrap();
+	assert!(a.domain().unwrap() == libc::AF_UNIX);
+	assert!(a.kind().unwrap() == libc::SOCK_SEQPACKET);
+}
+
+#[test]
+fn test_is_listening() {
+	let socket = Inet4Socket::new(SocketType::Stream, 0).unwrap();
+	assert!(let Ok(false) = socket.is_listening());
+	socket.bind(&posix_socket::Inet4SocketAddress::new(&std::net::Ipv4Addr::LOCALHOST, 0)).unwrap();
+	socket.listen(1).unwrap();
+	assert!(let Ok(true) = socket.is_listening());
+}
+
+#[test]
+fn test_generic_and_specific() {
+	let socket = UnixSocket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let generic = socket.into_generic();
+	assert!(let Ok(libc::AF_UNIX) = generic.domain());
+	let fd = generic.as_raw_fd();
+
+	// On failure, the original socket is returned and stays open.
+	let (generic, error) = generic.try_into_specific::<Inet4SocketAddress>().err().unwrap();
+	assert!(error.kind() == std::io::ErrorKind::InvalidInput);
+	assert!(generic.as_raw_fd() == fd);
+	assert!(let Ok(libc::AF_UNIX) = generic.domain());
+
+	let socket = generic.try_into_specific::<UnixSocketAddress>().unwrap();
+	assert!(socket.as_raw_fd() == fd);
+}
+
+#[test]
+fn test_generic_addresses() {
+	// Adopt a unix socket as an IPv4 socket, so the typed addresses can not be converted.
+	let (a, _b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let socket = unsafe { Inet4Socket::from_raw_fd(a.into_raw_fd()) };
+	assert!(let Err(_) = socket.local_addr());
+	assert!(let Err(_) = socket.peer_addr());
+
+	let local = socket.local_addr_generic().unwrap();
+	assert!(local.family() == libc::AF_UNIX);
+	let peer = socket.peer_addr_generic().unwrap();
+	assert!(peer.family() == libc::AF_UNIX);
+}
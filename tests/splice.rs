@@ -0,0 +1,36 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::splice::{tee, SpliceFlags};
+use posix_socket::UnixSocket;
+use std::fs::File;
+use std::io::Read;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+/// Create a pipe, returning the read and write end.
+fn pipe() -> (File, File) {
+	let mut fds = [0; 2];
+	assert!(unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } == 0);
+	unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) }
+}
+
+#[test]
+fn test_splice() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let (c, d) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let (pipe_read, pipe_write) = pipe();
+	let (mut copy_read, copy_write) = pipe();
+
+	a.send(b"hello world!", 0).unwrap();
+	assert!(let Ok(12) = b.splice_to(pipe_write.as_raw_fd(), 64, SpliceFlags::MOVE));
+	assert!(let Ok(12) = tee(pipe_read.as_raw_fd(), copy_write.as_raw_fd(), 64, SpliceFlags::NONBLOCK));
+	assert!(let Ok(12) = c.splice_from(pipe_read.as_raw_fd(), 64, SpliceFlags::MORE));
+
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(12) = d.recv(&mut buffer, 0));
+	assert!(&buffer[..12] == b"hello world!");
+
+	let mut copy = [0u8; 16];
+	assert!(let Ok(12) = copy_read.read(&mut copy));
+	assert!(&copy[..12] == b"hello world!");
+}
@@ -0,0 +1,45 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress, Inet6Socket, UnixSocket};
+use std::convert::TryFrom;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::os::unix::net::{UnixDatagram, UnixStream};
+
+#[test]
+fn test_convert_tcp() {
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Err(_) = TcpListener::try_from(listener.try_clone().unwrap()));
+	listener.listen(1).unwrap();
+	let address = listener.local_addr().unwrap();
+	let listener = TcpListener::try_from(listener).unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Err(_) = UdpSocket::try_from(client.try_clone().unwrap()));
+	client.connect(&address).unwrap();
+	let mut client = TcpStream::try_from(client).unwrap();
+	client.write_all(b"hello!").unwrap();
+
+	let (server, _) = listener.accept().unwrap();
+	assert!(let Err(_) = Inet6Socket::try_from(server.try_clone().unwrap()));
+	let server = Inet4Socket::try_from(server).unwrap();
+	let mut buffer = [0; 16];
+	assert!(let Ok(6) = server.recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+}
+
+#[test]
+fn test_convert_unix() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Err(_) = UnixDatagram::try_from(a.try_clone().unwrap()));
+	let mut a = UnixStream::try_from(a).unwrap();
+	a.write_all(b"hello!").unwrap();
+
+	let mut b = UnixStream::try_from(b).unwrap();
+	let mut buffer = [0; 6];
+	b.read_exact(&mut buffer).unwrap();
+	assert!(&buffer == b"hello!");
+
+	let b = UnixSocket::from(b);
+	assert!(let Ok(6) = b.send(b"world!", 0));
+}
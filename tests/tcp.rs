@@ -0,0 +1,399 @@
+use assert2::assert;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::net::Ipv4Addr;
+
+/// Create a connected pair of TCP sockets over the loopback interface.
+fn tcp_pair() -> (Inet4Socket, Inet4Socket) {
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	listener.listen(1).unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&listener.local_addr().unwrap()).unwrap();
+	let (server, _) = listener.accept().unwrap();
+	(client, server)
+}
+
+/// Check if the close-on-exec flag is set on a socket.
+fn is_close_on_exec(socket: &Inet4Socket) -> bool {
+	let flags = unsafe { libc::fcntl(socket.as_raw_fd(), libc::F_GETFD) };
+	assert!(flags != -1);
+	flags & libc::FD_CLOEXEC != 0
+}
+
+#[test]
+fn test_close_on_exec() {
+	let (client, server) = tcp_pair();
+	assert!(is_close_on_exec(&client));
+	assert!(is_close_on_exec(&server));
+}
+
+#[test]
+fn test_send_to_closed_peer() {
+	let (a, b) = tcp_pair();
+	drop(b);
+
+	// The first send may succeed before the peer resets the connection.
+	// Sending should never raise SIGPIPE, but fail with EPIPE or ECONNRESET instead.
+	let _ = a.send(b"hello", 0);
+	std::thread::sleep(std::time::Duration::from_millis(10));
+	let error = loop {
+		if let Err(e) = a.send(b"hello", 0) {
+			break e;
+		}
+	};
+	assert!(error.raw_os_error() == Some(libc::EPIPE) || error.raw_os_error() == Some(libc::ECONNRESET));
+}
+
+#[test]
+fn test_nodelay() {
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(false) = a.tcp_nodelay());
+	assert!(let Ok(()) = a.set_tcp_nodelay(true));
+	assert!(let Ok(true) = a.tcp_nodelay());
+}
+
+#[test]
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly", target_vendor = "apple"))]
+fn test_nopush() {
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(false) = a.tcp_nopush());
+	assert!(let Ok(()) = a.set_tcp_nopush(true));
+	assert!(let Ok(true) = a.tcp_nopush());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_cork_quickack() {
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(false) = a.tcp_cork());
+	assert!(let Ok(()) = a.set_tcp_cork(true));
+	assert!(let Ok(true) = a.tcp_cork());
+	assert!(let Ok(()) = a.set_tcp_cork(false));
+	assert!(let Ok(false) = a.tcp_cork());
+
+	assert!(let Ok(()) = a.set_tcp_quickack(true));
+	assert!(let Ok(true) = a.tcp_quickack());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_tcp_info() {
+	use posix_socket::tcp::TcpState;
+
+	let (a, b) = tcp_pair();
+	a.send(b"hello!", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+
+	let info = a.tcp_info().unwrap();
+	assert!(!info.is_empty());
+	assert!(info.state() == TcpState::Established);
+	assert!(info.snd_cwnd() > 0);
+	assert!(let Some(_) = info.bytes_acked());
+
+	let info = b.tcp_info().unwrap();
+	assert!(info.bytes_received() == Some(6));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_congestion() {
+	let (a, _b) = tcp_pair();
+	let algorithm = a.tcp_congestion().unwrap();
+	assert!(!algorithm.is_empty());
+	assert!(let Ok(()) = a.set_tcp_congestion(&algorithm));
+	assert!(a.tcp_congestion().unwrap() == algorithm);
+
+	assert!(let Err(_) = a.set_tcp_congestion("this-name-is-far-too-long"));
+	assert!(let Err(_) = a.set_tcp_congestion("cu\0bic"));
+	assert!(let Err(_) = a.set_tcp_congestion("no-such-algorithm"));
+}
+
+#[test]
+fn test_option_bytes() {
+	let (a, _b) = tcp_pair();
+	let enable = (1 as std::os::raw::c_int).to_ne_bytes();
+	assert!(let Ok(()) = a.set_option_bytes(libc::SOL_SOCKET, libc::SO_REUSEADDR, &enable));
+	assert!(let Ok(true) = a.reuse_address());
+
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(4) = a.get_option_bytes(libc::SOL_SOCKET, libc::SO_REUSEADDR, &mut buffer));
+	assert!(&buffer[..4] != &[0; 4]);
+
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	{
+		let len = a.get_option_bytes(libc::IPPROTO_TCP, libc::TCP_CONGESTION, &mut buffer).unwrap();
+		let name = buffer[..len].split(|&c| c == 0).next().unwrap();
+		assert!(name == a.tcp_congestion().unwrap().as_bytes());
+	}
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_user_timeout() {
+	use std::time::Duration;
+
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(None) = a.tcp_user_timeout());
+	a.set_tcp_user_timeout(Some(Duration::from_micros(1500))).unwrap();
+	assert!(a.tcp_user_timeout().unwrap() == Some(Duration::from_millis(2)));
+	a.set_tcp_user_timeout(None).unwrap();
+	assert!(let Ok(None) = a.tcp_user_timeout());
+	assert!(let Err(_) = a.set_tcp_user_timeout(Some(Duration::from_secs(u64::MAX))));
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_defer_accept() {
+	use std::time::Duration;
+
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(None) = listener.tcp_defer_accept());
+	listener.set_tcp_defer_accept(Some(Duration::from_secs(5))).unwrap();
+	let timeout = listener.tcp_defer_accept().unwrap();
+	assert!(let Some(_) = timeout);
+	assert!(timeout.unwrap() >= Duration::from_secs(5));
+	listener.set_tcp_defer_accept(None).unwrap();
+	assert!(let Ok(None) = listener.tcp_defer_accept());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_notsent_lowat() {
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(()) = a.set_tcp_notsent_lowat(16 * 1024));
+	assert!(let Ok(16384) = a.tcp_notsent_lowat());
+}
+
+#[test]
+fn test_tcp_maxseg() {
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(()) = socket.set_tcp_maxseg(1000));
+	assert!(let Ok(1000) = socket.tcp_maxseg());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_tuning() {
+	use std::time::Duration;
+
+	let (a, _b) = tcp_pair();
+	assert!(let Ok(()) = a.set_tcp_syncnt(3));
+	assert!(let Ok(3) = a.tcp_syncnt());
+	assert!(let Err(_) = a.set_tcp_syncnt(200));
+
+	assert!(let Ok(()) = a.set_tcp_window_clamp(64 * 1024));
+	assert!(let Ok(65536) = a.tcp_window_clamp());
+
+	assert!(let Ok(()) = a.set_tcp_linger2(Some(Duration::from_millis(4500))));
+	assert!(a.tcp_linger2().unwrap() == Some(Duration::from_secs(5)));
+	assert!(let Ok(()) = a.set_tcp_linger2(None));
+	assert!(let Ok(None) = a.tcp_linger2());
+}
+
+#[test]
+#[cfg(any(target_os = "android", target_os = "linux"))]
+fn test_tcp_saved_syn() {
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	assert!(let Ok(false) = listener.tcp_save_syn());
+	assert!(let Ok(()) = listener.set_tcp_save_syn(true));
+	assert!(let Ok(true) = listener.tcp_save_syn());
+	listener.listen(1).unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&listener.local_addr().unwrap()).unwrap();
+	let (server, _) = listener.accept().unwrap();
+
+	let syn = server.tcp_saved_syn().unwrap().unwrap();
+	// IPv4 header of at least 20 bytes followed by a TCP header of at least 20 bytes.
+	assert!(syn.len() >= 40);
+	assert!(syn[0] >> 4 == 4);
+	assert!(let Ok(None) = server.tcp_saved_syn());
+	assert!(let Ok(None) = client.tcp_saved_syn());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_tcp_inq() {
+	use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+	use std::io::IoSliceMut;
+
+	let (a, b) = tcp_pair();
+	assert!(let Ok(false) = b.tcp_inq());
+	assert!(let Ok(()) = b.set_tcp_inq(true));
+	assert!(let Ok(true) = b.tcp_inq());
+	a.send(b"hello world!", 0).unwrap();
+
+	let mut buffer = [0u8; 5];
+	let mut cdata = [0u8; 64];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello");
+
+	let inq = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::TcpInq(x)) => Some(x),
+			_ => None,
+		});
+	assert!(inq == Some(7));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_tcp_md5sig() {
+	let peer = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0);
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&peer).unwrap();
+	listener.listen(1).unwrap();
+	assert!(let Ok(()) = listener.set_tcp_md5sig(&peer, b"secret"));
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(()) = client.set_tcp_md5sig_prefix(&Inet4SocketAddress::new(&Ipv4Addr::new(127, 0, 0, 0), 0), 8, b"secret"));
+	client.connect(&listener.local_addr().unwrap()).unwrap();
+	let (server, _) = listener.accept().unwrap();
+	client.send(b"signed", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(6) = server.recv(&mut buffer, 0));
+
+	assert!(let Ok(()) = listener.remove_tcp_md5sig(&peer));
+	assert!(let Err(_) = listener.remove_tcp_md5sig(&peer));
+	assert!(let Err(_) = listener.set_tcp_md5sig(&peer, &[0; 81]));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_tcp_ao() {
+	use posix_socket::tcp::TcpAoKey;
+
+	let peer = Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0);
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&peer).unwrap();
+	listener.listen(1).unwrap();
+	match listener.add_tcp_ao_key(&TcpAoKey::new(&peer, 32, "hmac(sha1)", 1, 2, b"secret")) {
+		Err(e) if e.raw_os_error() == Some(libc::ENOPROTOOPT) => return,
+		result => result.unwrap(),
+	}
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.add_tcp_ao_key(&TcpAoKey::new(&peer, 32, "hmac(sha1)", 2, 1, b"secret")).unwrap();
+	client.connect(&listener.local_addr().unwrap()).unwrap();
+	let (server, _) = listener.accept().unwrap();
+	client.send(b"signed", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(6) = server.recv(&mut buffer, 0));
+
+	let info = server.tcp_ao_info().unwrap();
+	assert!(info.packets_good > 0);
+	assert!(info.packets_bad == 0);
+
+	assert!(let Err(_) = listener.add_tcp_ao_key(&TcpAoKey::new(&peer, 32, "hmac(sha1)", 3, 3, &[0; 81])));
+	assert!(let Ok(()) = listener.remove_tcp_ao_key(&peer, 32, 1, 2));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_tcp_repair() {
+	use posix_socket::tcp::{TcpRepairOption, TcpRepairQueue, TcpState};
+
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	match socket.set_tcp_repair(true) {
+		Err(e) if e.raw_os_error() == Some(libc::EPERM) => return,
+		result => result.unwrap(),
+	}
+	assert!(let Ok(true) = socket.tcp_repair());
+	socket.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+
+	assert!(let Ok(()) = socket.set_tcp_repair_queue(TcpRepairQueue::Send));
+	assert!(let Ok(TcpRepairQueue::Send) = socket.tcp_repair_queue());
+	assert!(let Ok(()) = socket.set_tcp_queue_seq(1000));
+	assert!(let Ok(()) = socket.set_tcp_repair_queue(TcpRepairQueue::Recv));
+	assert!(let Ok(()) = socket.set_tcp_queue_seq(2000));
+
+	// In repair mode, connect does not send any packets.
+	socket.connect(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 1)).unwrap();
+	assert!(socket.tcp_info().unwrap().state() == TcpState::Established);
+	assert!(let Ok(()) = socket.set_tcp_repair_options(&[TcpRepairOption::mss(1460), TcpRepairOption::window_scale(7, 7)]));
+
+	assert!(let Ok(2000) = socket.tcp_queue_seq());
+	assert!(let Ok(()) = socket.set_tcp_repair_queue(TcpRepairQueue::Send));
+	assert!(let Ok(1000) = socket.tcp_queue_seq());
+	assert!(let Ok(()) = socket.set_tcp_repair_queue(TcpRepairQueue::None));
+}
+
+#[test]
+fn test_accept_generic() {
+	use posix_socket::{Socket, SocketAddress};
+
+	let listener = Socket::<SocketAddress>::new_generic(libc::AF_INET, libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0).into()).unwrap();
+	listener.listen(1).unwrap();
+	let address = listener.local_addr().unwrap().as_inet4().unwrap();
+
+	let client = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&address).unwrap();
+	let (_server, peer) = listener.accept_generic().unwrap();
+	assert!(peer.as_inet4() == Some(client.local_addr().unwrap()));
+}
+
+#[test]
+fn test_linger() {
+	use std::time::Duration;
+
+	let socket = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	assert!(socket.linger().unwrap().is_none());
+	assert!(let Ok(()) = socket.set_linger(Some(Duration::from_secs(3))));
+	assert!(socket.linger().unwrap() == Some(Duration::from_secs(3)));
+	assert!(let Ok(()) = socket.set_linger(Some(Duration::from_millis(1500))));
+	assert!(socket.linger().unwrap() == Some(Duration::from_secs(2)));
+	assert!(let Ok(()) = socket.set_linger(None));
+	assert!(socket.linger().unwrap().is_none());
+}
+
+#[test]
+fn test_oob() {
+	let (a, b) = tcp_pair();
+	assert!(let Ok(false) = b.oob_inline());
+
+	a.send(b"hello", 0).unwrap();
+	a.send_oob(b"!", 0).unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+
+	// Reading stops at the out-of-band mark.
+	let mut buffer = [0u8; 16];
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello");
+	#[cfg(target_os = "linux")]
+	assert!(let Ok(true) = b.at_mark());
+
+	let len = b.recv_oob(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"!");
+
+	// The read position only moves past the mark when normal data after it is read.
+	#[cfg(target_os = "linux")]
+	assert!(let Ok(true) = b.at_mark());
+	a.send(b"world", 0).unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"world");
+	#[cfg(target_os = "linux")]
+	assert!(let Ok(false) = b.at_mark());
+}
+
+#[test]
+fn test_oob_inline() {
+	let (a, b) = tcp_pair();
+	assert!(let Ok(()) = b.set_oob_inline(true));
+	assert!(let Ok(true) = b.oob_inline());
+
+	a.send(b"hello", 0).unwrap();
+	a.send_oob(b"!", 0).unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+
+	let mut buffer = [0u8; 16];
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello");
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"!");
+}
@@ -0,0 +1,84 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+use posix_socket::timestamping::{TimestampingFlags, TxTimestampKind};
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::io::IoSliceMut;
+use std::net::Ipv4Addr;
+
+fn udp_pair() -> (Inet4Socket, Inet4Socket) {
+	let a = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let b = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	a.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	b.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	a.connect(&b.local_addr().unwrap()).unwrap();
+	b.connect(&a.local_addr().unwrap()).unwrap();
+	(a, b)
+}
+
+#[test]
+fn test_rx_software_timestamp() {
+	let (a, b) = udp_pair();
+	let flags = TimestampingFlags::RX_SOFTWARE | TimestampingFlags::SOFTWARE;
+	assert!(let Ok(()) = b.set_timestamping(flags));
+	assert!(b.timestamping().unwrap() == flags);
+
+	a.send(b"hello!", 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	let mut cdata = [0u8; 128];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == b"hello!");
+
+	let timestamps = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::Timestamping(x)) => Some(x),
+			_ => None,
+		})
+		.unwrap();
+	assert!(let Some(_) = timestamps.software());
+	assert!(let None = timestamps.hardware());
+}
+
+#[test]
+fn test_tx_software_timestamp() {
+	let (a, _b) = udp_pair();
+	let flags = TimestampingFlags::TX_SOFTWARE | TimestampingFlags::SOFTWARE | TimestampingFlags::OPT_ID | TimestampingFlags::OPT_TSONLY;
+	assert!(let Ok(()) = a.set_timestamping(flags));
+	assert!(let Ok(None) = a.recv_tx_timestamp());
+
+	a.send(b"hello!", 0).unwrap();
+	a.send(b"world!", 0).unwrap();
+
+	for id in 0..2 {
+		let timestamp = (0..100)
+			.find_map(|_| {
+				let timestamp = a.recv_tx_timestamp().unwrap();
+				if timestamp.is_none() {
+					std::thread::sleep(std::time::Duration::from_millis(10));
+				}
+				timestamp
+			})
+			.unwrap();
+		assert!(timestamp.kind() == TxTimestampKind::Sent);
+		assert!(timestamp.id() == id);
+		assert!(let Some(_) = timestamp.timestamps().software());
+	}
+}
+
+#[test]
+fn test_last_rx_timestamp() {
+	let (a, b) = udp_pair();
+	assert!(let Err(_) = b.last_rx_timestamp());
+
+	a.send(b"hello!", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	b.recv(&mut buffer, 0).unwrap();
+
+	let timestamp = b.last_rx_timestamp().unwrap();
+	let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+	assert!(timestamp <= now);
+	assert!(now - timestamp < std::time::Duration::from_secs(10));
+}
@@ -0,0 +1,123 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+use std::io::{IoSlice, IoSliceMut};
+use std::net::Ipv4Addr;
+
+/// Create a pair of UDP sockets connected to each other over the loopback interface.
+fn udp_pair() -> (Inet4Socket, Inet4Socket) {
+	let a = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let b = Inet4Socket::new(libc::SOCK_DGRAM, 0).unwrap();
+	a.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	b.bind(&Inet4SocketAddress::new(&Ipv4Addr::LOCALHOST, 0)).unwrap();
+	a.connect(&b.local_addr().unwrap()).unwrap();
+	b.connect(&a.local_addr().unwrap()).unwrap();
+	(a, b)
+}
+
+#[test]
+fn test_udp_segment() {
+	let (a, b) = udp_pair();
+	assert!(let Ok(0) = a.udp_segment());
+	assert!(let Ok(()) = a.set_udp_segment(4));
+	assert!(let Ok(4) = a.udp_segment());
+
+	a.send(b"0123456789", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(4) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..4] == b"0123");
+	assert!(let Ok(4) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..4] == b"4567");
+	assert!(let Ok(2) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..2] == b"89");
+}
+
+#[test]
+fn test_udp_segment_cmsg() {
+	let (a, b) = udp_pair();
+	let mut cdata = [0u8; 64];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	assert!(ancillary.add_udp_segment(3));
+	a.send_msg(&[IoSlice::new(b"abcdef")], Some(ancillary.data()), 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(3) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..3] == b"abc");
+	assert!(let Ok(3) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..3] == b"def");
+}
+
+#[test]
+fn test_udp_gro() {
+	let (a, b) = udp_pair();
+	assert!(let Ok(false) = b.udp_gro());
+	assert!(let Ok(()) = b.set_udp_gro(true));
+	assert!(let Ok(true) = b.udp_gro());
+
+	a.set_udp_segment(4).unwrap();
+	a.send(b"0123456789", 0).unwrap();
+
+	// Whether the datagrams are coalesced depends on the kernel, so only check consistency.
+	let mut buffer = [0u8; 64];
+	let mut cdata = [0u8; 64];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	let len = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
+	assert!(&buffer[..len] == &b"0123456789"[..len]);
+	let segment_size = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::UdpGroSegmentSize(x)) => Some(x),
+			_ => None,
+		});
+	if len > 4 {
+		assert!(segment_size == Some(4));
+	}
+}
+
+#[test]
+fn test_udp_cork() {
+	let (a, b) = udp_pair();
+	assert!(let Ok(false) = a.udp_cork());
+	assert!(let Ok(()) = a.set_udp_cork(true));
+	assert!(let Ok(true) = a.udp_cork());
+
+	a.send(b"hello", 0).unwrap();
+	a.send(b" world", 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(let Err(_) = b.recv(&mut buffer, libc::MSG_DONTWAIT));
+
+	assert!(let Ok(()) = a.set_udp_cork(false));
+	assert!(let Ok(11) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..11] == b"hello world");
+}
+
+#[test]
+fn test_recv_multiple_invalid_address() {
+	use posix_socket::{Inet6Socket, MmsgBuffer};
+
+	// Pretend the IPv4 socket is an IPv6 socket, so the sender addresses can not be decoded.
+	let (a, b) = udp_pair();
+	let b = unsafe { Inet6Socket::from_raw_fd(b.into_raw_fd()) };
+	a.send(b"hello!", 0).unwrap();
+	a.send(b"world", 0).unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+
+	let mut buffer_a = [0u8; 16];
+	let mut buffer_b = [0u8; 16];
+	{
+		let data_a = [IoSliceMut::new(&mut buffer_a)];
+		let data_b = [IoSliceMut::new(&mut buffer_b)];
+		let mut messages = [
+			MmsgBuffer::new(&data_a, SocketAncillary::new(&mut [])),
+			MmsgBuffer::new(&data_b, SocketAncillary::new(&mut [])),
+		];
+		assert!(let Ok(2) = b.recv_multiple(&mut messages, libc::MSG_DONTWAIT));
+		assert!(messages[0].len() == 6);
+		assert!(messages[1].len() == 5);
+		assert!(let None = messages[0].address());
+		assert!(let None = messages[1].address());
+	}
+	assert!(&buffer_a[..6] == b"hello!");
+	assert!(&buffer_b[..5] == b"world");
+}
@@ -1,7 +1,10 @@
 use assert2::assert;
-use posix_socket::{UnixSocket, UnixSocketAddress};
+use posix_socket::{SocketAddress, UnixSocket, UnixSocketAddress};
+#[cfg(target_os = "linux")]
+use posix_socket::{MmsgBuffer, RecvFlags, SendMessage};
 use posix_socket::ancillary::SocketAncillary;
 use std::io::{IoSlice, IoSliceMut};
+use std::time::{Duration, Instant};
 
 mod util;
 
@@ -23,6 +26,116 @@ fn test_socketpair_send_recv() {
 	assert!(let Err(_) = a.send(b"goodbye!", 0));
 }
 
+#[test]
+fn test_nonblocking() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Ok(false) = b.get_nonblocking());
+
+	assert!(let Ok(()) = b.set_nonblocking(true));
+	assert!(let Ok(true) = b.get_nonblocking());
+	let mut buffer = [0u8; 16];
+	let error = b.recv(&mut buffer, 0).unwrap_err();
+	assert!(error.raw_os_error() == Some(libc::EAGAIN));
+	assert!(error.kind() == std::io::ErrorKind::WouldBlock);
+
+	a.send(b"hello!", 0).unwrap();
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+
+	assert!(let Ok(()) = b.set_nonblocking(false));
+	assert!(let Ok(false) = b.get_nonblocking());
+}
+
+#[test]
+fn test_nonblocking_scope() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	let mut buffer = [0u8; 16];
+
+	{
+		let scope = b.nonblocking_scope().unwrap();
+		assert!(let Ok(true) = b.get_nonblocking());
+		let error = scope.recv(&mut buffer, 0).unwrap_err();
+		assert!(error.kind() == std::io::ErrorKind::WouldBlock);
+	}
+	assert!(let Ok(false) = b.get_nonblocking());
+
+	// Restoring a scope that did not change the mode keeps the mode.
+	b.set_nonblocking(true).unwrap();
+	let scope = b.nonblocking_scope().unwrap();
+	assert!(let Ok(()) = scope.restore());
+	assert!(let Ok(true) = b.get_nonblocking());
+
+	let scope = b.blocking_scope().unwrap();
+	assert!(let Ok(false) = b.get_nonblocking());
+	a.send(b"hello!", 0).unwrap();
+	assert!(let Ok(6) = scope.recv(&mut buffer, 0));
+	assert!(let Ok(()) = scope.restore());
+	assert!(let Ok(true) = b.get_nonblocking());
+}
+
+#[test]
+fn test_recv_deadline() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	let mut buffer = [0u8; 16];
+
+	let start = Instant::now();
+	let error = b.recv_deadline(&mut buffer, 0, start + Duration::from_millis(20)).unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::TimedOut);
+	assert!(start.elapsed() >= Duration::from_millis(20));
+
+	// Data that is already available is returned even if the deadline passed.
+	a.send(b"hello!", 0).unwrap();
+	assert!(let Ok(6) = b.recv_deadline(&mut buffer, 0, start));
+	assert!(&buffer[..6] == b"hello!");
+
+	assert!(let Ok(6) = a.send_deadline(b"world!", 0, Instant::now() + Duration::from_secs(1)));
+	let (sender, len) = b.recv_from_deadline(&mut buffer, 0, Instant::now() + Duration::from_secs(1)).unwrap();
+	assert!(&buffer[..len] == b"world!");
+	assert!(sender.is_none());
+}
+
+extern "C" fn ignore_signal(_signal: std::os::raw::c_int) {}
+
+/// Install a SIGUSR1 handler without `SA_RESTART` and send the signal to a thread after a short delay.
+fn interrupt_later(thread: libc::pthread_t) -> std::thread::JoinHandle<()> {
+	unsafe {
+		let mut action: libc::sigaction = std::mem::zeroed();
+		action.sa_sigaction = ignore_signal as extern "C" fn(std::os::raw::c_int) as libc::sighandler_t;
+		assert!(libc::sigaction(libc::SIGUSR1, &action, std::ptr::null_mut()) == 0);
+	}
+	let thread = thread as usize;
+	std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(50));
+		unsafe {
+			libc::pthread_kill(thread as libc::pthread_t, libc::SIGUSR1);
+		}
+	})
+}
+
+#[test]
+fn test_retry_interrupted() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	let mut buffer = [0u8; 16];
+	assert!(!b.retry_interrupted());
+
+	let signaller = interrupt_later(unsafe { libc::pthread_self() });
+	let error = b.recv(&mut buffer, 0).unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::Interrupted);
+	signaller.join().unwrap();
+
+	b.set_retry_interrupted(true);
+	assert!(b.retry_interrupted());
+	let signaller = interrupt_later(unsafe { libc::pthread_self() });
+	let sender = std::thread::spawn(move || {
+		std::thread::sleep(Duration::from_millis(100));
+		a.send(b"hello!", 0).unwrap();
+	});
+	assert!(let Ok(6) = b.recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+	signaller.join().unwrap();
+	sender.join().unwrap();
+}
+
 #[test]
 fn test_send_msg_recv_msg() {
 	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
@@ -30,7 +143,7 @@ fn test_send_msg_recv_msg() {
 
 	let mut buffer = [0u8; 16];
 	let mut ancillary = SocketAncillary::new(&mut []);
-	let (len, _flags) = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	let len = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap().byte_count;
 	assert!(len == 6);
 	assert!(ancillary.len() == 0);
 	assert!(ancillary.truncated() == false);
@@ -40,6 +153,57 @@ fn test_send_msg_recv_msg() {
 	assert!(let Err(_) = a.send_msg(&[IoSlice::new(b"goodbye!")], None, 0));
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_send_multiple_recv_multiple() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	let hello = [IoSlice::new(b"hello!")];
+	let world = [IoSlice::new(b"big "), IoSlice::new(b"world")];
+	let sent = a.send_multiple(&[SendMessage::new(&hello), SendMessage::new(&world)], 0).unwrap();
+	assert!(sent == [6, 9]);
+
+	let mut buffer_a = [0u8; 16];
+	let mut buffer_b = [0u8; 16];
+	let mut buffer_c = [0u8; 16];
+	{
+		let data_a = [IoSliceMut::new(&mut buffer_a)];
+		let data_b = [IoSliceMut::new(&mut buffer_b)];
+		let data_c = [IoSliceMut::new(&mut buffer_c)];
+		let mut messages = [
+			MmsgBuffer::new(&data_a, SocketAncillary::new(&mut [])),
+			MmsgBuffer::new(&data_b, SocketAncillary::new(&mut [])),
+			MmsgBuffer::new(&data_c, SocketAncillary::new(&mut [])),
+		];
+		assert!(let Ok(2) = b.recv_multiple(&mut messages, libc::MSG_DONTWAIT));
+		assert!(messages[0].len() == 6);
+		assert!(messages[1].len() == 9);
+		assert!(messages[2].len() == 0);
+		assert!(messages[0].cdata().len() == 0);
+		assert!(!messages[1].flags().contains(RecvFlags::TRUNC));
+	}
+	assert!(&buffer_a[..6] == b"hello!");
+	assert!(&buffer_b[..9] == b"big world");
+}
+
+#[test]
+fn test_send_to_recv_from_generic() {
+	let tempdir = util::TempDir::new().unwrap();
+	let address_a = UnixSocketAddress::new(tempdir.path().join("a.sock")).unwrap();
+	let address_b = UnixSocketAddress::new(tempdir.path().join("b.sock")).unwrap();
+
+	let a = UnixSocket::new(libc::SOCK_DGRAM, 0).unwrap();
+	let b = UnixSocket::new(libc::SOCK_DGRAM, 0).unwrap();
+	a.bind(&address_a).unwrap();
+	b.bind(&address_b).unwrap();
+
+	assert!(let Ok(6) = a.send_to_generic(b"hello!", &SocketAddress::from(&address_b), 0));
+
+	let mut buffer = [0u8; 16];
+	let (sender, len) = b.recv_from_generic(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello!");
+	assert!(sender.unwrap().as_unix() == Some(address_a));
+}
+
 #[test]
 fn test_unconnected_named_sockets() {
 	let tempdir = util::TempDir::new().unwrap();
@@ -48,8 +212,8 @@ fn test_unconnected_named_sockets() {
 	let path_a = path_a.as_path();
 	let path_b = path_b.as_path();
 
-	let address_a = UnixSocketAddress::new(&path_a).unwrap();
-	let address_b = UnixSocketAddress::new(&path_b).unwrap();
+	let address_a = UnixSocketAddress::new(path_a).unwrap();
+	let address_b = UnixSocketAddress::new(path_b).unwrap();
 	eprintln!("binding socket_a to {}", path_a.display());
 	eprintln!("binding socket_b to {}", path_b.display());
 
@@ -71,6 +235,7 @@ fn test_unconnected_named_sockets() {
 	let mut buffer = [0u8; 16];
 	let (sender, len) = b.recv_from(&mut buffer, 0).unwrap();
 	assert!(&buffer[..len] == b"hello!");
+	let sender = sender.unwrap();
 	assert!(sender.as_path() == Some(path_a));
 
 	drop(b);
@@ -85,8 +250,8 @@ fn test_connected_named_sockets() {
 	let path_a = path_a.as_path();
 	let path_b = path_b.as_path();
 
-	let address_a = UnixSocketAddress::new(&path_a).unwrap();
-	let address_b = UnixSocketAddress::new(&path_b).unwrap();
+	let address_a = UnixSocketAddress::new(path_a).unwrap();
+	let address_b = UnixSocketAddress::new(path_b).unwrap();
 	eprintln!("binding socket_a to {}", path_a.display());
 	eprintln!("binding socket_b to {}", path_b.display());
 
@@ -114,3 +279,107 @@ fn test_connected_named_sockets() {
 	drop(b);
 	assert!(let Err(_) = a.send(b"goodbye!", 0));
 }
+
+#[test]
+fn test_pending_input() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(b.pending_input().unwrap() == 0);
+
+	a.send(b"hello!", 0).unwrap();
+	assert!(b.pending_input().unwrap() == 6);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_next_datagram_size() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	assert!(let Err(_) = b.next_datagram_size(libc::MSG_DONTWAIT));
+
+	a.send(b"hello world!", 0).unwrap();
+	a.send(b"bye", 0).unwrap();
+	assert!(b.next_datagram_size(0).unwrap() == 12);
+
+	let mut buffer = [0u8; 16];
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello world!");
+	assert!(b.next_datagram_size(0).unwrap() == 3);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_peek_size() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	a.send(&[7u8; 100], 0).unwrap();
+	assert!(let Ok(100) = b.peek_size());
+
+	let mut buffer = vec![0u8; b.peek_size().unwrap()];
+	assert!(let Ok(100) = b.recv(&mut buffer, 0));
+	assert!(buffer == [7u8; 100]);
+}
+
+#[test]
+fn test_recv_uninit() {
+	use std::mem::MaybeUninit;
+
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	a.send(b"hello!", 0).unwrap();
+	a.send(b"world!", 0).unwrap();
+
+	let mut buffer = [MaybeUninit::<u8>::uninit(); 16];
+	let len = b.recv_uninit(&mut buffer, 0).unwrap();
+	let data: Vec<u8> = buffer[..len].iter().map(|x| unsafe { x.assume_init() }).collect();
+	assert!(data == b"hello!");
+
+	let (_address, len) = b.recv_from_uninit(&mut buffer, 0).unwrap();
+	let data: Vec<u8> = buffer[..len].iter().map(|x| unsafe { x.assume_init() }).collect();
+	assert!(data == b"world!");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_recv_to_vec() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	let large = vec![0xAAu8; 3000];
+	a.send(&large, 0).unwrap();
+	a.send(b"", 0).unwrap();
+	a.send(b"hello!", 0).unwrap();
+
+	assert!(b.recv_to_vec(0).unwrap() == large);
+	assert!(b.recv_to_vec(0).unwrap().is_empty());
+	let (address, data) = b.recv_from_to_vec(0).unwrap();
+	assert!(address.is_none());
+	assert!(data == b"hello!");
+}
+
+#[test]
+fn test_try_send_recv() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+
+	let mut buffer = [0u8; 16];
+	assert!(let Ok(None) = b.try_recv(&mut buffer, 0));
+
+	assert!(let Ok(Some(6)) = a.try_send(b"hello!", 0));
+	assert!(let Ok(Some(6)) = b.try_recv(&mut buffer, 0));
+	assert!(&buffer[..6] == b"hello!");
+
+	assert!(let Ok(Some(3)) = a.try_send(b"bye", 0));
+	let (_address, len) = b.try_recv_from(&mut buffer, 0).unwrap().unwrap();
+	assert!(&buffer[..len] == b"bye");
+	assert!(b.try_recv_from(&mut buffer, 0).unwrap().is_none());
+}
+
+#[test]
+fn test_recv_msg_truncated() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_DGRAM, 0).unwrap();
+	a.send(b"hello world!", 0).unwrap();
+
+	let mut buffer = [0u8; 5];
+	let mut ancillary = SocketAncillary::new(&mut []);
+	let message = b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	assert!(message.byte_count == 5);
+	assert!(message.truncated);
+	assert!(!message.ctruncated);
+	assert!(message.flags.contains(posix_socket::RecvFlags::TRUNC));
+	assert!(message.address.is_none());
+	assert!(&buffer == b"hello");
+}
@@ -1,5 +1,7 @@
 use assert2::assert;
-use posix_socket::UnixSocket;
+use posix_socket::{RecvFlags, UnixSeqpacket, UnixSeqpacketListener, UnixSocket, UnixSocketAddress};
+
+mod util;
 
 #[test]
 fn test_socketpair() {
@@ -18,3 +20,39 @@ fn test_socketpair() {
 	drop(b);
 	assert!(let Err(_) = a.send(b"goodbye!", 0));
 }
+
+#[test]
+fn test_record_boundaries() {
+	let (a, b) = UnixSeqpacket::pair().unwrap();
+	assert!(let Ok(()) = a.send_record(b"hello"));
+	assert!(let Ok(()) = a.send_record(b"world!"));
+
+	let mut buffer = [0u8; 16];
+	let record = b.recv_record(&mut buffer).unwrap();
+	assert!(record.byte_count == 5);
+	assert!(!record.truncated);
+	assert!(&buffer[..5] == b"hello");
+
+	let mut small = [0u8; 4];
+	let record = b.recv_record(&mut small).unwrap();
+	assert!(record.byte_count == 4);
+	assert!(record.truncated);
+	assert!(record.flags.contains(RecvFlags::TRUNC));
+	assert!(&small == b"worl");
+}
+
+#[test]
+fn test_listener() {
+	let dir = util::TempDir::new().unwrap();
+	let address = UnixSocketAddress::new(dir.path().join("seqpacket.sock")).unwrap();
+	let listener = UnixSeqpacketListener::bind(&address, 1).unwrap();
+	assert!(listener.as_socket().kind().unwrap() == libc::SOCK_SEQPACKET);
+
+	let client = UnixSeqpacket::connect(&address).unwrap();
+	let (server, _) = listener.accept().unwrap();
+	assert!(let Ok(()) = client.send_record(b"ping"));
+
+	let mut buffer = [0u8; 16];
+	let record = server.recv_record(&mut buffer).unwrap();
+	assert!(&buffer[..record.byte_count] == b"ping");
+}
@@ -1,5 +1,8 @@
 use assert2::assert;
-use posix_socket::UnixSocket;
+use posix_socket::{UnixSocket, UnixSocketAddress};
+use std::time::{Duration, Instant};
+
+mod util;
 
 #[test]
 fn test_socketpair() {
@@ -19,3 +22,164 @@ fn test_socketpair() {
 	drop(b);
 	assert!(let Err(_) = a.send(b"goodbye!", 0));
 }
+
+#[test]
+fn test_recv_from_stream() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	a.send(b"hello!", 0).unwrap();
+
+	// Connected stream sockets do not report the address of the sender.
+	let mut buffer = [0u8; 16];
+	let (address, len) = b.recv_from(&mut buffer, 0).unwrap();
+	assert!(address.is_none());
+	assert!(&buffer[..len] == b"hello!");
+}
+
+#[test]
+fn test_accept_deadline() {
+	let tempdir = util::TempDir::new().unwrap();
+	let address = UnixSocketAddress::new(tempdir.path().join("listener.sock")).unwrap();
+	let listener = UnixSocket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&address).unwrap();
+	listener.listen(1).unwrap();
+
+	let error = listener.accept_deadline(Instant::now() + Duration::from_millis(10)).unwrap_err();
+	assert!(error.kind() == std::io::ErrorKind::TimedOut);
+
+	let client = UnixSocket::new(libc::SOCK_STREAM, 0).unwrap();
+	client.connect(&address).unwrap();
+	assert!(let Ok(_) = listener.accept_deadline(Instant::now() + Duration::from_secs(1)));
+}
+
+#[test]
+fn test_peer_credentials() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let credentials = a.peer_credentials().unwrap();
+	assert!(credentials.uid == unsafe { libc::geteuid() });
+	assert!(credentials.gid == unsafe { libc::getegid() });
+	#[cfg(any(target_os = "android", target_os = "linux"))]
+	assert!(credentials.pid == Some(std::process::id() as libc::pid_t));
+	assert!(b.peer_credentials().unwrap() == credentials);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_peer_security_label() {
+	let (a, _b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	// Without an active security module, the kernel reports ENOPROTOOPT.
+	match a.peer_security_label() {
+		Ok(label) => assert!(!label.is_empty()),
+		Err(e) => assert!(e.raw_os_error() == Some(libc::ENOPROTOOPT)),
+	}
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_peer_pidfd() {
+	use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+	use std::io::{IoSlice, IoSliceMut};
+
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	let pidfd = match a.peer_pidfd() {
+		Ok(x) => x,
+		// Not supported before Linux 6.5.
+		Err(e) if e.raw_os_error() == Some(libc::ENOPROTOOPT) => return,
+		Err(e) => panic!("failed to get peer pidfd: {}", e),
+	};
+	assert!(let Ok(true) = pidfd.get_close_on_exec());
+
+	assert!(let Ok(()) = b.set_pass_pidfd(true));
+	a.send_msg(&[IoSlice::new(b"a")], None, 0).unwrap();
+
+	let mut buffer = [0u8; 1];
+	let mut cdata = [0u8; 64];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	let find_pidfd = || ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::ScmPidfd(x)) => Some(x),
+			_ => None,
+		})
+		.unwrap();
+
+	// Each iteration hands out a separate owned file descriptor.
+	let first = find_pidfd();
+	let second = find_pidfd();
+	assert!(first.as_raw_fd() != second.as_raw_fd());
+	assert!(let Ok(true) = first.get_close_on_exec());
+	drop(first);
+	assert!(let Ok(true) = second.get_close_on_exec());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pass_credentials() {
+	use posix_socket::ancillary::{AncillaryData, SocketAncillary};
+	use std::io::{IoSlice, IoSliceMut};
+
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(false) = b.pass_credentials());
+	assert!(let Ok(()) = b.set_pass_credentials(true));
+	assert!(let Ok(true) = b.pass_credentials());
+	assert!(let Ok(false) = b.pass_security());
+	assert!(let Ok(()) = b.set_pass_security(true));
+	assert!(let Ok(true) = b.pass_security());
+
+	a.send_msg(&[IoSlice::new(b"a")], None, 0).unwrap();
+
+	let mut buffer = [0u8; 1];
+	let mut cdata = [0u8; 256];
+	let mut ancillary = SocketAncillary::new(&mut cdata);
+	b.recv_msg(&[IoSliceMut::new(&mut buffer)], &mut ancillary, 0).unwrap();
+	let credentials = ancillary.messages()
+		.find_map(|message| match message {
+			Ok(AncillaryData::ScmCredentials(mut x)) => x.next(),
+			_ => None,
+		})
+		.unwrap();
+	assert!(credentials.get_pid() == std::process::id() as libc::pid_t);
+	assert!(credentials.get_uid() == unsafe { libc::getuid() });
+	assert!(credentials.get_gid() == unsafe { libc::getgid() });
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_peek_at() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+	assert!(let Ok(-1) = b.peek_offset());
+
+	a.send(b"hello world", 0).unwrap();
+
+	let mut buffer = [0u8; 5];
+	assert!(let Ok(5) = b.peek_at(6, &mut buffer));
+	assert!(&buffer == b"world");
+	assert!(let Ok(11) = b.peek_offset());
+
+	let mut buffer = [0u8; 16];
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello world");
+	assert!(let Ok(0) = b.peek_offset());
+}
+
+#[test]
+fn test_recv_full() {
+	let (a, b) = UnixSocket::pair(libc::SOCK_STREAM, 0).unwrap();
+
+	let writer = std::thread::spawn(move || {
+		a.send(b"hello ", 0).unwrap();
+		std::thread::sleep(std::time::Duration::from_millis(20));
+		a.send(b"world!", 0).unwrap();
+		a
+	});
+
+	let mut buffer = [0u8; 12];
+	assert!(let Ok(12) = b.recv_full(&mut buffer, 0));
+	assert!(&buffer == b"hello world!");
+
+	// Closing the connection returns the partial data.
+	let a = writer.join().unwrap();
+	a.send(b"bye", 0).unwrap();
+	drop(a);
+	assert!(let Ok(3) = b.recv_full(&mut buffer, 0));
+	assert!(&buffer[..3] == b"bye");
+}
@@ -0,0 +1,56 @@
+#![cfg(target_os = "linux")]
+
+use assert2::assert;
+use posix_socket::zerocopy::ZerocopyCompletion;
+use posix_socket::{Inet4Socket, Inet4SocketAddress};
+
+#[test]
+fn test_completion_range() {
+	let completion = ZerocopyCompletion::new(3, 5, false);
+	assert!(completion.count() == 3);
+	assert!(!completion.contains(2));
+	assert!(completion.contains(3));
+	assert!(completion.contains(5));
+	assert!(!completion.contains(6));
+
+	let completion = ZerocopyCompletion::new(u32::MAX, 1, true);
+	assert!(completion.count() == 3);
+	assert!(completion.contains(u32::MAX));
+	assert!(completion.contains(0));
+	assert!(completion.contains(1));
+	assert!(!completion.contains(2));
+	assert!(completion.copied());
+}
+
+#[test]
+fn test_send_zerocopy() {
+	let listener = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	listener.bind(&Inet4SocketAddress::new(&std::net::Ipv4Addr::LOCALHOST, 0)).unwrap();
+	listener.listen(1).unwrap();
+	let address = listener.local_addr().unwrap();
+
+	let a = Inet4Socket::new(libc::SOCK_STREAM, 0).unwrap();
+	a.connect(&address).unwrap();
+	let (b, _) = listener.accept().unwrap();
+
+	assert!(let Ok(()) = a.set_zerocopy(true));
+	assert!(let Ok(true) = a.zerocopy());
+	assert!(let Ok(None) = a.recv_zerocopy_completion());
+	assert!(let Ok(6) = a.send_zerocopy(b"hello!", 0));
+
+	let mut buffer = [0u8; 16];
+	let len = b.recv(&mut buffer, 0).unwrap();
+	assert!(&buffer[..len] == b"hello!");
+
+	let completion = (0..100)
+		.find_map(|_| {
+			let completion = a.recv_zerocopy_completion().unwrap();
+			if completion.is_none() {
+				std::thread::sleep(std::time::Duration::from_millis(10));
+			}
+			completion
+		})
+		.unwrap();
+	assert!(completion.first() == 0);
+	assert!(completion.last() == 0);
+}